@@ -8,7 +8,17 @@ fn main() {
         return;
     }
 
+    // Render the same version info as an HTML footer
+    if args.len() > 1 && args[1] == "--html-footer" {
+        println!(
+            "{}",
+            sw_cli::web_footer!(style = "rich", class = "app-footer")
+        );
+        return;
+    }
+
     // Normal CLI operation
     println!("Demo CLI Application");
     println!("Run with -V or --version to see version information");
+    println!("Run with --html-footer to see the HTML footer");
 }
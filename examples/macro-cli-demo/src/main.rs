@@ -1,7 +1,7 @@
 use std::fs::File;
 use std::io::{self, BufRead, BufReader};
 use std::path::PathBuf;
-use sw_cli::{cli_app, cli_command, dispatch, CliConfig};
+use sw_cli::{CliConfig, cli_app, cli_command, dispatch};
 
 // Generate config struct, builder, and parser in ~5 lines!
 cli_app! {
@@ -9,10 +9,10 @@ cli_app! {
     about: "Macro-based CLI demo",
     config: DemoConfig,
     fields: {
-        input: Option<Vec<PathBuf>>, short = 'i', long = "input", help = "Input file(s)", action = Append,
+        input: Option<Vec<PathBuf>>, short = 'i', long = "input", help = "Input file(s)", action = Append, exists,
         output: Option<PathBuf>, short = 'o', long = "output", help = "Output file",
-        pattern: Option<String>, short = 'p', long = "pattern", help = "Pattern to search for",
-        count: bool, long = "count", help = "Count lines in input",
+        pattern: Option<String>, short = 'p', long = "pattern", help = "Pattern to search for", env = "DEMO_PATTERN",
+        count: bool, long = "count", help = "Count lines in input", conflicts_with = "reverse",
         reverse: bool, long = "reverse", help = "Reverse line order",
     }
 }
@@ -100,7 +100,10 @@ fn main() {
 
 // Helper functions (same as working-cli-demo)
 
-fn count_file(path: &std::path::Path, config: &DemoConfig) -> Result<(), Box<dyn std::error::Error>> {
+fn count_file(
+    path: &std::path::Path,
+    config: &DemoConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
     if config.is_dry_run() {
         println!("Would count lines in: {}", path.display());
         return Ok(());
@@ -128,7 +131,11 @@ fn count_stdin(config: &DemoConfig) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn grep_file(path: &std::path::Path, pattern: &str, config: &DemoConfig) -> Result<(), Box<dyn std::error::Error>> {
+fn grep_file(
+    path: &std::path::Path,
+    pattern: &str,
+    config: &DemoConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
     if config.is_dry_run() {
         println!("Would search for '{}' in: {}", pattern, path.display());
         return Ok(());
@@ -162,7 +169,10 @@ fn grep_stdin(pattern: &str, config: &DemoConfig) -> Result<(), Box<dyn std::err
     Ok(())
 }
 
-fn reverse_file(path: &std::path::Path, config: &DemoConfig) -> Result<(), Box<dyn std::error::Error>> {
+fn reverse_file(
+    path: &std::path::Path,
+    config: &DemoConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
     if config.is_dry_run() {
         println!("Would reverse lines in: {}", path.display());
         return Ok(());
@@ -1,4 +1,4 @@
-use sw_cli::{cli_app, cli_command, dispatch, CliConfig};
+use sw_cli::{CliConfig, cli_app, cli_command, dispatch};
 
 // Define our CLI with just 6 lines!
 cli_app! {
@@ -6,9 +6,9 @@ cli_app! {
     about: "Minimal CLI example using sw-cli macros",
     config: MiniConfig,
     fields: {
-        text: Option<String>, short = 't', long = "text", help = "Text to process",
+        text: String, short = 't', long = "text", help = "Text to process", default = "Hello, World!",
         uppercase: bool, short = 'u', long = "uppercase", help = "Convert to uppercase",
-        repeat: Option<usize>, short = 'r', long = "repeat", help = "Repeat N times",
+        repeat: Option<usize>, short = 'r', long = "repeat", help = "Repeat N times", range = 1..=100,
     }
 }
 
@@ -19,7 +19,7 @@ cli_command! {
     config: MiniConfig,
     can_handle: |c: &MiniConfig| c.uppercase,
     execute: |config: &MiniConfig| {
-        let text = config.text.as_deref().unwrap_or("Hello, World!");
+        let text = &config.text;
 
         if config.is_dry_run() {
             println!("Would uppercase: {}", text);
@@ -38,7 +38,7 @@ cli_command! {
     config: MiniConfig,
     can_handle: |c: &MiniConfig| c.repeat.is_some(),
     execute: |config: &MiniConfig| {
-        let text = config.text.as_deref().unwrap_or("Hello, World!");
+        let text = &config.text;
         let count = config.repeat.unwrap_or(1);
 
         if config.is_dry_run() {
@@ -60,7 +60,7 @@ cli_command! {
     config: MiniConfig,
     can_handle: |_c: &MiniConfig| true,
     execute: |config: &MiniConfig| {
-        let text = config.text.as_deref().unwrap_or("Hello, World!");
+        let text = &config.text;
 
         if config.verbosity() > 0 {
             println!("Echoing text...");
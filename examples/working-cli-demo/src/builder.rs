@@ -1,7 +1,9 @@
 use crate::args;
 use crate::config::DemoConfig;
 use clap::{ArgMatches, Command};
-use std::path::PathBuf;
+use sw_cli::builder::{config_error, resolve_base_config};
+use sw_cli::config::ConfigLayers;
+use sw_cli::config::layers::Resolver;
 
 #[must_use]
 pub fn build_cli() -> Command {
@@ -13,15 +15,44 @@ pub fn build_cli() -> Command {
         .args(args::custom_args())
 }
 
+/// Layer config files, `WORKING_CLI_DEMO_*` variables and flags into the config
+///
+/// # Errors
+/// Returns a clap error for unreadable config files, invalid values and
+/// unknown keys.
+pub fn try_parse_config(matches: &ArgMatches) -> Result<DemoConfig, clap::Error> {
+    let layers = ConfigLayers::load("working-cli-demo", matches).map_err(config_error)?;
+    try_parse_config_from(&layers, matches)
+}
+
+/// Like `try_parse_config`, with the layers chosen by the caller, e.g. ones
+/// that leave out the environment
+///
+/// # Errors
+/// Returns a clap error for invalid values and unknown keys.
+pub fn try_parse_config_from(
+    layers: &ConfigLayers,
+    matches: &ArgMatches,
+) -> Result<DemoConfig, clap::Error> {
+    let mut resolver = layers.resolver(matches);
+    let mut config = resolve(&mut resolver).map_err(config_error)?;
+    config.base.env_vars = resolver.env_vars();
+    config.base.sources = resolver.finish().map_err(config_error)?;
+    Ok(config)
+}
+
+#[must_use]
 pub fn parse_config(matches: &ArgMatches) -> DemoConfig {
-    DemoConfig {
-        base: sw_cli::builder::parse_base_config(matches),
-        input: matches
-            .get_many::<String>("input")
-            .map(|vals| vals.map(PathBuf::from).collect()),
-        output: matches.get_one::<String>("output").map(PathBuf::from),
-        pattern: matches.get_one::<String>("pattern").cloned(),
-        count: matches.get_flag("count"),
-        reverse: matches.get_flag("reverse"),
-    }
+    try_parse_config(matches).unwrap_or_else(|e| e.exit())
+}
+
+fn resolve(resolver: &mut Resolver) -> Result<DemoConfig, String> {
+    Ok(DemoConfig {
+        base: resolve_base_config(resolver)?,
+        input: resolver.paths("input")?,
+        output: resolver.path("output")?,
+        pattern: resolver.string("pattern")?,
+        count: resolver.flag("count")?,
+        reverse: resolver.flag("reverse")?,
+    })
 }
@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use sw_cli::output::Value;
 use sw_cli::{BaseConfig, CliConfig as CliConfigTrait};
 
 #[derive(Debug, Clone)]
@@ -16,6 +17,18 @@ impl CliConfigTrait for DemoConfig {
         &self.base
     }
 
+    fn settings(&self) -> Vec<(String, Value)> {
+        let mut settings = self.base.settings();
+        settings.extend([
+            ("input".to_string(), self.input.clone().into()),
+            ("output".to_string(), self.output.clone().into()),
+            ("pattern".to_string(), self.pattern.clone().into()),
+            ("count".to_string(), self.count.into()),
+            ("reverse".to_string(), self.reverse.into()),
+        ]);
+        settings
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -3,5 +3,5 @@ pub mod args;
 pub mod builder;
 pub mod config;
 
-pub use builder::{build_cli, parse_config};
+pub use builder::{build_cli, parse_config, try_parse_config};
 pub use config::DemoConfig;
@@ -1,14 +1,18 @@
+use sw_cli::builder::config_error;
+use sw_cli::config::{ConfigLayers, Source};
 use sw_cli::{BaseConfig, CliConfig, HelpType};
-use working_cli_demo::DemoConfig;
+use working_cli_demo::builder::try_parse_config_from;
+use working_cli_demo::{DemoConfig, build_cli};
 
 #[test]
 fn test_verbosity_normal() {
     let config = DemoConfig {
         base: BaseConfig {
-            verbose: false,
+            verbose: 0,
             dry_run: false,
             help: HelpType::None,
             version: false,
+            ..BaseConfig::default()
         },
         input: None,
         output: None,
@@ -24,10 +28,11 @@ fn test_verbosity_normal() {
 fn test_verbosity_verbose() {
     let config = DemoConfig {
         base: BaseConfig {
-            verbose: true,
+            verbose: 1,
             dry_run: false,
             help: HelpType::None,
             version: false,
+            ..BaseConfig::default()
         },
         input: None,
         output: None,
@@ -43,10 +48,11 @@ fn test_verbosity_verbose() {
 fn test_dry_run() {
     let config = DemoConfig {
         base: BaseConfig {
-            verbose: false,
+            verbose: 0,
             dry_run: true,
             help: HelpType::None,
             version: false,
+            ..BaseConfig::default()
         },
         input: None,
         output: None,
@@ -57,3 +63,72 @@ fn test_dry_run() {
 
     assert!(config.is_dry_run());
 }
+
+/// Parse without the developer's config files and `WORKING_CLI_DEMO_*`
+/// variables: `--no-config` unless `args` name a `--config` file, and no
+/// environment prefix
+fn parse(args: &[&str]) -> Result<DemoConfig, clap::Error> {
+    let no_config = (!args.contains(&"--config")).then_some("--no-config");
+    let matches = build_cli().try_get_matches_from(args.iter().copied().chain(no_config))?;
+    let layers = ConfigLayers::new("working-cli-demo")
+        .with_env_prefix("")
+        .with_files_for(&matches)
+        .map_err(config_error)?;
+    try_parse_config_from(&layers, &matches)
+}
+
+#[test]
+fn test_verbosity_levels() {
+    let config = parse(&["demo", "-vvv"]).unwrap();
+    assert_eq!(config.verbosity(), 3);
+    assert_eq!(config.verbosity_level(), 3);
+    assert!(!config.is_quiet());
+
+    let config = parse(&["demo", "-v", "--verbose"]).unwrap();
+    assert_eq!(config.verbosity_level(), 2);
+
+    let config = parse(&["demo"]).unwrap();
+    assert_eq!(config.verbosity_level(), 0);
+    assert!(!config.is_quiet());
+}
+
+#[test]
+fn test_quiet() {
+    let config = parse(&["demo", "-q"]).unwrap();
+    assert_eq!(config.verbosity(), 0);
+    assert_eq!(config.verbosity_level(), -1);
+    assert!(config.is_quiet());
+
+    let config = parse(&["demo", "-qq"]).unwrap();
+    assert_eq!(config.verbosity_level(), -2);
+
+    let err = parse(&["demo", "-v", "--quiet"]).unwrap_err();
+    assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+}
+
+#[test]
+fn test_config_file() {
+    let dir = std::env::temp_dir().join(format!("working-cli-demo-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.toml");
+    std::fs::write(
+        &path,
+        "pattern = \"TODO\"\ndry_run = true\ninput = [\"notes.txt\"]\n",
+    )
+    .unwrap();
+    let path_arg = path.to_string_lossy().into_owned();
+
+    let config = parse(&["demo", "--config", &path_arg, "--pattern", "FIXME"]).unwrap();
+    assert_eq!(config.pattern.as_deref(), Some("FIXME"));
+    assert!(config.is_dry_run());
+    assert_eq!(config.input, Some(vec![dir.join("notes.txt")]));
+    assert_eq!(config.value_source("pattern"), Source::CommandLine);
+    assert_eq!(config.value_source("dry_run"), Source::File(path.clone()));
+    assert_eq!(config.value_source("reverse"), Source::Default);
+
+    std::fs::write(&path, "reverse = \"yes\"\n").unwrap();
+    let err = parse(&["demo", "--config", &path_arg])
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("'reverse' must be true or false"), "{err}");
+}
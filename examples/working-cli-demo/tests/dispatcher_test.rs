@@ -6,10 +6,11 @@ use working_cli_demo::actions::*;
 fn test_version_command_priority() {
     let config = DemoConfig {
         base: BaseConfig {
-            verbose: false,
+            verbose: 0,
             dry_run: false,
             help: HelpType::None,
             version: true,
+            ..BaseConfig::default()
         },
         input: None,
         output: None,
@@ -18,7 +19,7 @@ fn test_version_command_priority() {
         reverse: false,
     };
 
-    let cmd = VersionCommand;
+    let cmd = VersionCommand::new(sw_cli::build_info!());
     assert!(cmd.can_handle(&config));
     assert_eq!(cmd.priority(), 0);
 }
@@ -27,10 +28,11 @@ fn test_version_command_priority() {
 fn test_count_command_handles_count_flag() {
     let config = DemoConfig {
         base: BaseConfig {
-            verbose: false,
+            verbose: 0,
             dry_run: false,
             help: HelpType::None,
             version: false,
+            ..BaseConfig::default()
         },
         input: None,
         output: None,
@@ -47,10 +49,11 @@ fn test_count_command_handles_count_flag() {
 fn test_copy_command_is_default() {
     let config = DemoConfig {
         base: BaseConfig {
-            verbose: false,
+            verbose: 0,
             dry_run: false,
             help: HelpType::None,
             version: false,
+            ..BaseConfig::default()
         },
         input: None,
         output: None,
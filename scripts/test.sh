@@ -7,6 +7,7 @@ echo
 
 echo "Running tests for sw-cli..."
 cargo test -p sw-cli
+cargo test -p sw-cli --all-features
 
 echo
 echo "Running tests for sw-cli-macros..."
@@ -1,5 +1,10 @@
-use crate::config::{BaseConfig, HelpType};
+use crate::config::layers::{Resolver, arg_flag, arg_string, on_command_line};
+use crate::config::{BaseConfig, ConfigLayers, HelpType, Sources};
+use crate::output::OutputFormat;
+use crate::version::sbom::SbomFormat;
+use clap::error::ErrorKind;
 use clap::{Arg, ArgAction, ArgMatches};
+use std::collections::BTreeMap;
 
 /// Creates standard flags for all Software Wrighter CLIs
 #[must_use]
@@ -10,6 +15,41 @@ pub fn standard_args() -> Vec<Arg> {
             .long("version")
             .action(ArgAction::SetTrue)
             .help("Show version information"),
+        Arg::new("license")
+            .long("license")
+            .action(ArgAction::SetTrue)
+            .help("Show license information and exit"),
+        Arg::new("licenses")
+            .long("licenses")
+            .action(ArgAction::SetTrue)
+            .help("Show licenses of third-party dependencies and exit"),
+        Arg::new("sbom")
+            .long("sbom")
+            .value_name("FORMAT")
+            .num_args(0..=1)
+            .default_missing_value("cyclonedx")
+            .value_parser(SbomFormat::NAMES)
+            .help("Print the software bill of materials (cyclonedx or spdx JSON) and exit"),
+        Arg::new("check-update")
+            .long("check-update")
+            .value_name("MANIFEST")
+            .num_args(0..=1)
+            .help("Check a release manifest for newer versions and exit"),
+        Arg::new("self-update")
+            .long("self-update")
+            .value_name("MANIFEST")
+            .num_args(0..=1)
+            .conflicts_with("check-update")
+            .help("Download, verify and install the newest release, then exit"),
+        Arg::new("rollback-update")
+            .long("rollback-update")
+            .action(ArgAction::SetTrue)
+            .conflicts_with("self-update")
+            .help("Restore the version replaced by the last --self-update and exit"),
+        Arg::new("print-config")
+            .long("print-config")
+            .action(ArgAction::SetTrue)
+            .help("Show the configuration and where each value comes from, then exit"),
         Arg::new("help-short")
             .short('h')
             .action(ArgAction::SetTrue)
@@ -21,31 +61,138 @@ pub fn standard_args() -> Vec<Arg> {
         Arg::new("verbose")
             .short('v')
             .long("verbose")
-            .action(ArgAction::SetTrue)
-            .help("Increase output verbosity"),
+            .action(ArgAction::Count)
+            .help("Increase output verbosity (repeat for more, e.g. -vvv)"),
+        Arg::new("quiet")
+            .short('q')
+            .long("quiet")
+            .action(ArgAction::Count)
+            .conflicts_with("verbose")
+            .help("Decrease output verbosity (repeat for less)"),
         Arg::new("dry-run")
             .short('n')
             .long("dry-run")
             .action(ArgAction::SetTrue)
             .help("Show what would be done without doing it"),
+        Arg::new("config")
+            .long("config")
+            .value_name("FILE")
+            .conflicts_with("no-config")
+            .help("Read settings from FILE instead of the standard config files"),
+        Arg::new("no-config")
+            .long("no-config")
+            .action(ArgAction::SetTrue)
+            .help("Ignore config files"),
+        Arg::new("profile")
+            .long("profile")
+            .value_name("NAME")
+            .conflicts_with("no-config")
+            .help("Use the [profile.NAME] settings of the config files"),
+        Arg::new("format")
+            .long("format")
+            .value_name("FORMAT")
+            .value_parser(OutputFormat::NAMES)
+            .help("Output format for machine-readable output"),
     ]
 }
 
-/// Parse standard flags from `ArgMatches`
+/// Ids of the [`standard_args`] that run a built-in command instead of the
+/// application
+pub const BUILT_IN_ARGS: [&str; 10] = [
+    "help-short",
+    "help-long",
+    "version",
+    "license",
+    "licenses",
+    "sbom",
+    "check-update",
+    "self-update",
+    "rollback-update",
+    "print-config",
+];
+
+/// Parse standard flags from `ArgMatches`, ignoring config files and the
+/// environment
+///
+/// Ids missing from `matches`, as in a `Command` built from an older
+/// [`standard_args`], read as unset.
+///
+/// # Panics
+/// If `format` holds a value that is not an [`OutputFormat`]; the standard
+/// `--format` argument only accepts those.
 #[must_use]
 pub fn parse_base_config(matches: &ArgMatches) -> BaseConfig {
-    let help = if matches.get_flag("help-long") {
+    let layers = ConfigLayers::default();
+    let mut resolver = layers.resolver(matches);
+    let mut base =
+        resolve_base_config(&mut resolver).expect("command-line values are validated by clap");
+    base.sources = resolver
+        .finish()
+        .expect("there are no config files to check");
+    base
+}
+
+/// Resolve standard flags through configuration layers. `verbose`, `quiet`,
+/// `dry_run` and `format` can be set in config files and the environment;
+/// `profile` is the one the layers selected. The other flags are actions
+/// and only come from the command line.
+///
+/// `sources` and `env_vars` are left empty; set them from
+/// `Resolver::finish` and `Resolver::env_vars` once the application's own
+/// values are resolved too.
+///
+/// # Errors
+/// Returns a message if a layer holds an invalid value.
+pub fn resolve_base_config(resolver: &mut Resolver) -> Result<BaseConfig, String> {
+    let matches = resolver.matches();
+    let help = if arg_flag(matches, "help-long") {
         HelpType::Long
-    } else if matches.get_flag("help-short") {
+    } else if arg_flag(matches, "help-short") {
         HelpType::Short
     } else {
         HelpType::None
     };
 
-    BaseConfig {
-        verbose: matches.get_flag("verbose"),
-        dry_run: matches.get_flag("dry-run"),
-        help,
-        version: matches.get_flag("version"),
+    let mut verbose = resolver.count("verbose")?;
+    let mut quiet = resolver.count("quiet")?;
+    // -v and -q conflict; when layers set both, the higher one wins
+    if verbose > 0 && quiet > 0 {
+        if resolver.precedence("verbose") >= resolver.precedence("quiet") {
+            quiet = 0;
+        } else {
+            verbose = 0;
+        }
     }
+
+    Ok(BaseConfig {
+        verbose,
+        quiet,
+        dry_run: resolver.flag("dry-run")?,
+        help,
+        version: arg_flag(matches, "version"),
+        license: arg_flag(matches, "license"),
+        licenses: arg_flag(matches, "licenses"),
+        sbom: arg_string(matches, "sbom").and_then(|f| f.parse().ok()),
+        check_update: on_command_line(matches, "check-update"),
+        self_update: on_command_line(matches, "self-update"),
+        rollback_update: arg_flag(matches, "rollback-update"),
+        print_config: arg_flag(matches, "print-config"),
+        update_manifest: arg_string(matches, "check-update")
+            .or_else(|| arg_string(matches, "self-update")),
+        format: resolver
+            .string("format")?
+            .map(|f| f.parse())
+            .transpose()
+            .map_err(|e| format!("{}: {e}", resolver.source("format")))?
+            .unwrap_or_default(),
+        profile: resolver.profile(),
+        sources: Sources::new(),
+        env_vars: BTreeMap::new(),
+    })
+}
+
+/// Report a configuration problem the way clap reports usage errors
+#[must_use]
+pub fn config_error(message: impl std::fmt::Display) -> clap::Error {
+    clap::Error::raw(ErrorKind::InvalidValue, format!("{message}\n"))
 }
@@ -0,0 +1,252 @@
+use crate::command::Command;
+use crate::config::{CliConfig, Source};
+use crate::output::{OutputFormat, Value, toml_inline, toml_key};
+use crate::version::VersionInfo;
+use std::error::Error;
+use std::fmt::Write;
+
+/// Source comments start in this column unless a setting is wider
+const COMMENT_COLUMN: usize = 32;
+
+/// Prints the resolved configuration and where each value came from
+/// (`--print-config`)
+pub struct PrintConfigCommand {
+    version: VersionInfo,
+}
+
+impl PrintConfigCommand {
+    #[must_use]
+    pub fn new(version: VersionInfo) -> Self {
+        Self { version }
+    }
+}
+
+impl Command for PrintConfigCommand {
+    fn can_handle(&self, config: &dyn CliConfig) -> bool {
+        config.wants_print_config()
+    }
+
+    fn execute(&self, config: &dyn CliConfig) -> Result<(), Box<dyn Error>> {
+        let mut header = format!("{} {}", self.version.name, self.version.version);
+        if let Some(profile) = config.profile() {
+            header = format!("{header} (profile {profile})");
+        }
+        println!(
+            "{}",
+            render(config, &header, config.output_format()).trim_end()
+        );
+        Ok(())
+    }
+
+    fn priority(&self) -> u8 {
+        7
+    }
+}
+
+/// Text and TOML: a config file with the source of each value as a comment,
+/// so the output can be saved and edited. JSON and YAML: each key maps to
+/// its `value` and `source` (`default`, `file`, `env` or `command_line`),
+/// plus the `file` or `env` variable that set it.
+fn render(config: &dyn CliConfig, header: &str, format: OutputFormat) -> String {
+    let settings = config.settings();
+    if matches!(format, OutputFormat::Json | OutputFormat::Yaml) {
+        let mut map = Value::map();
+        for (key, value) in settings {
+            let source = config.value_source(&key);
+            let mut entry = Value::map()
+                .with("value", value)
+                .with("source", source.kind());
+            match source {
+                Source::File(path) => entry = entry.with("file", path),
+                Source::Env(var) => entry = entry.with("env", var),
+                Source::Default | Source::CommandLine => {}
+            }
+            map = map.with(&key, entry);
+        }
+        return map.render(format);
+    }
+
+    let lines: Vec<(String, String)> = settings
+        .into_iter()
+        .map(|(key, value)| {
+            let source = config.value_source(&key);
+            if value == Value::Null {
+                (format!("# {}", toml_key(&key)), "not set".to_string())
+            } else {
+                let setting = format!("{} = {}", toml_key(&key), toml_inline(&value));
+                (setting, source.to_string())
+            }
+        })
+        .collect();
+    let width = lines
+        .iter()
+        .map(|(setting, _)| setting.len())
+        .filter(|&len| len < COMMENT_COLUMN)
+        .max()
+        .unwrap_or_default();
+    let mut out = format!("# {header}: effective configuration\n");
+    for (setting, source) in lines {
+        let _ = writeln!(out, "{setting:width$}  # {source}");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::BaseConfig;
+    use std::path::PathBuf;
+
+    struct TestConfig {
+        base: BaseConfig,
+        pattern: Option<String>,
+        inputs: Vec<PathBuf>,
+    }
+
+    impl CliConfig for TestConfig {
+        fn base(&self) -> &BaseConfig {
+            &self.base
+        }
+
+        fn settings(&self) -> Vec<(String, Value)> {
+            let mut settings = self.base.settings();
+            settings.push(("pattern".to_string(), self.pattern.clone().into()));
+            settings.push(("inputs".to_string(), self.inputs.clone().into()));
+            settings
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn sample() -> TestConfig {
+        let mut base = BaseConfig::new();
+        base.verbose = 2;
+        base.sources
+            .insert("verbose".to_string(), Source::CommandLine);
+        base.sources.insert(
+            "inputs".to_string(),
+            Source::File(PathBuf::from("/etc/tool/config.toml")),
+        );
+        base.sources.insert(
+            "dry_run".to_string(),
+            Source::Env("TOOL_DRY_RUN".to_string()),
+        );
+        TestConfig {
+            base,
+            pattern: None,
+            inputs: vec![PathBuf::from("/var/log/a.log"), PathBuf::from("b.txt")],
+        }
+    }
+
+    #[test]
+    fn test_annotated_toml() {
+        let text = render(&sample(), "tool 1.0.0", OutputFormat::Text);
+        assert_eq!(
+            text,
+            "# tool 1.0.0: effective configuration\n\
+             verbose = 2      # command line\n\
+             quiet = 0        # default\n\
+             dry_run = false  # env TOOL_DRY_RUN\n\
+             format = \"text\"  # default\n\
+             # pattern        # not set\n\
+             inputs = [\"/var/log/a.log\", \"b.txt\"]  # /etc/tool/config.toml\n"
+        );
+        assert_eq!(render(&sample(), "tool 1.0.0", OutputFormat::Toml), text);
+
+        // The output is itself a valid config file
+        let parsed = Value::from_toml(&text).unwrap();
+        let Value::Map(entries) = parsed else {
+            panic!("expected a table")
+        };
+        assert_eq!(entries.len(), 5);
+    }
+
+    mod defaults {
+        use super::*;
+        use crate::cli_app;
+
+        cli_app! {
+            name: "defaults-test",
+            about: "Defaults test",
+            config: DefaultsConfig,
+            fields: {
+                text: String, long = "text", help = "Text", default = "Hello, World!",
+                repeat: usize, long = "repeat", help = "Repeat", default = 2, range = 1..=10,
+                offset: i32, long = "offset", help = "Offset", default = -1,
+                out: PathBuf, long = "out", help = "Output", default = "out.txt",
+                tags: Vec<String>, long = "tag", help = "Tags", default = ["a", "b"],
+                extra: Vec<String>, long = "extra", help = "Extra",
+            }
+        }
+
+        fn parse(args: &[&str]) -> DefaultsConfig {
+            let matches = build_cli()
+                .try_get_matches_from(
+                    ["defaults-test", "--no-config"]
+                        .into_iter()
+                        .chain(args.iter().copied()),
+                )
+                .unwrap();
+            try_parse_config(&matches).unwrap()
+        }
+
+        #[test]
+        fn test_defaults() {
+            let config = parse(&[]);
+            assert_eq!(config.text, "Hello, World!");
+            assert_eq!(config.repeat, 2);
+            assert_eq!(config.offset, -1);
+            assert_eq!(config.out, PathBuf::from("out.txt"));
+            assert_eq!(config.tags, ["a", "b"]);
+            assert!(config.extra.is_empty());
+            assert_eq!(config.value_source("text"), Source::Default);
+
+            let config = parse(&["--text", "hi", "--repeat", "3", "--tag", "c"]);
+            assert_eq!(config.text, "hi");
+            assert_eq!(config.repeat, 3);
+            assert_eq!(config.tags, ["c"]);
+            assert_eq!(config.value_source("repeat"), Source::CommandLine);
+
+            let err = build_cli()
+                .try_get_matches_from(["defaults-test", "--repeat", "0"])
+                .unwrap_err();
+            assert!(err.to_string().contains("0 is not in 1..=10"), "{err}");
+
+            let text = render(&parse(&["--repeat", "3"]), "tool 1.0.0", OutputFormat::Text);
+            assert!(
+                text.contains("\ntext = \"Hello, World!\"  # default\n"),
+                "{text}"
+            );
+            assert!(
+                text.contains("\nrepeat = 3              # command line\n"),
+                "{text}"
+            );
+            assert!(
+                text.contains("\ntags = [\"a\", \"b\"]       # default\n"),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_json_sources() {
+        let json = render(&sample(), "tool 1.0.0", OutputFormat::Json);
+        let Value::Map(entries) = Value::from_json(&json).unwrap() else {
+            panic!("expected an object")
+        };
+        let entry = |key: &str| {
+            entries
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.to_json())
+                .unwrap()
+        };
+        assert!(entry("verbose").contains("\"source\": \"command_line\""));
+        assert!(entry("dry_run").contains("\"env\": \"TOOL_DRY_RUN\""));
+        assert!(entry("inputs").contains("\"file\": \"/etc/tool/config.toml\""));
+        assert!(entry("pattern").contains("\"value\": null"));
+        assert!(entry("quiet").contains("\"source\": \"default\""));
+    }
+}
@@ -1,6 +1,8 @@
 use crate::command::Command;
-use crate::config::CliConfig;
+use crate::config::{CliConfig, Source};
+use crate::output::{Value, toml_inline};
 use std::error::Error;
+use std::fmt::Write as _;
 
 pub struct HelpCommand {
     short_help: String,
@@ -25,6 +27,9 @@ impl Command for HelpCommand {
     fn execute(&self, config: &dyn CliConfig) -> Result<(), Box<dyn Error>> {
         if config.wants_long_help() {
             println!("{}", self.long_help);
+            if let Some(section) = environment_section(config) {
+                println!("{section}");
+            }
         } else {
             // Default to short help for -h
             println!("{}", self.short_help);
@@ -36,3 +41,74 @@ impl Command for HelpCommand {
         1
     }
 }
+
+/// `ENVIRONMENT` section of the long help: each variable, the setting it
+/// sets and where that setting's current value comes from, with the value
+/// for defaults
+fn environment_section(config: &dyn CliConfig) -> Option<String> {
+    let env_vars = &config.base().env_vars;
+    let var_width = env_vars.values().map(String::len).max()?;
+    let key_width = env_vars.keys().map(String::len).max().unwrap_or(0);
+    let settings = config.settings();
+    let mut section = "ENVIRONMENT:\n".to_string();
+    for (key, var) in env_vars {
+        let source = match config.value_source(key) {
+            Source::Default => match settings.iter().find(|(k, _)| k == key) {
+                Some((_, value)) if *value != Value::Null => {
+                    format!("default: {}", toml_inline(value))
+                }
+                _ => "default".to_string(),
+            },
+            source => source.to_string(),
+        };
+        let _ = writeln!(
+            section,
+            "    {var:var_width$}  {key:key_width$}  [{source}]"
+        );
+    }
+    Some(section)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BaseConfig, Source};
+    use std::path::PathBuf;
+
+    struct TestConfig(BaseConfig);
+
+    impl CliConfig for TestConfig {
+        fn base(&self) -> &BaseConfig {
+            &self.0
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_environment_section() {
+        let mut base = BaseConfig::new();
+        assert_eq!(environment_section(&TestConfig(base.clone())), None);
+
+        for (key, var) in [
+            ("dry_run", "TOOL_DRY_RUN"),
+            ("input", "TOOL_INPUT"),
+            ("pattern", "GREP_PATTERN"),
+        ] {
+            base.env_vars.insert(key.to_string(), var.to_string());
+        }
+        base.sources.insert(
+            "pattern".to_string(),
+            Source::File(PathBuf::from("/etc/tool/config.toml")),
+        );
+        assert_eq!(
+            environment_section(&TestConfig(base)).unwrap(),
+            "ENVIRONMENT:\n\
+             \x20   TOOL_DRY_RUN  dry_run  [default: false]\n\
+             \x20   TOOL_INPUT    input    [default]\n\
+             \x20   GREP_PATTERN  pattern  [/etc/tool/config.toml]\n"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use crate::command::Command;
+use crate::config::CliConfig;
+use crate::output::{OutputFormat, Value};
+use crate::version::{Version, VersionInfo};
+use std::error::Error;
+use std::fmt::Write;
+
+/// Prints the license expression, one link per license and any license
+/// texts embedded with `define_build_info!(embed_licenses)`
+pub struct LicenseCommand {
+    version: VersionInfo,
+}
+
+impl LicenseCommand {
+    #[must_use]
+    pub fn new(version: VersionInfo) -> Self {
+        Self { version }
+    }
+}
+
+impl Command for LicenseCommand {
+    fn can_handle(&self, config: &dyn CliConfig) -> bool {
+        config.wants_license()
+    }
+
+    fn execute(&self, config: &dyn CliConfig) -> Result<(), Box<dyn Error>> {
+        let output = render(&self.version.to_version(), config.output_format());
+        println!("{}", output.trim_end());
+        Ok(())
+    }
+
+    fn priority(&self) -> u8 {
+        2
+    }
+}
+
+fn render(version: &Version, format: OutputFormat) -> String {
+    if format != OutputFormat::Text {
+        return Value::map()
+            .with("license", version.license_name.as_str())
+            .with(
+                "licenses",
+                version
+                    .licenses
+                    .iter()
+                    .map(|l| l.to_value(true))
+                    .collect::<Vec<_>>(),
+            )
+            .render(format);
+    }
+
+    let mut out = version.license_line();
+    let mut embedded = false;
+    for license in &version.licenses {
+        if let Some(text) = &license.text {
+            let _ = write!(out, "\n\n== {} ==\n\n{}", license.id, text.trim_end());
+            embedded = true;
+        }
+    }
+    if !embedded {
+        out.push_str("\n\nLicense texts are not embedded in this build; see the link above.");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::{BuildInfo, License};
+
+    fn dual_licensed() -> Version {
+        Version::new(
+            "0.1.0".to_string(),
+            "Copyright (c) 2025 Example Corp".to_string(),
+            "MIT OR Apache-2.0".to_string(),
+            "https://x/LICENSE-MIT".to_string(),
+            BuildInfo::new(String::new(), "abc123def456".to_string(), 0),
+        )
+        .with_licenses(vec![
+            License::new("MIT".to_string(), "https://x/LICENSE-MIT".to_string()).with_text(Some(
+                "MIT License\n\nPermission is hereby granted...\n".to_string(),
+            )),
+            License::new(
+                "Apache-2.0".to_string(),
+                "https://x/LICENSE-APACHE".to_string(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_text_includes_embedded_texts() {
+        let text = render(&dual_licensed(), OutputFormat::Text);
+        assert!(text.starts_with("License: MIT (https://x/LICENSE-MIT) OR Apache-2.0"));
+        assert!(text.contains("\n\n== MIT ==\n\nMIT License\n\nPermission is hereby granted..."));
+        assert!(!text.contains("== Apache-2.0 =="));
+        assert!(!text.contains("not embedded"));
+    }
+
+    #[test]
+    fn test_text_without_embedded_texts() {
+        let mut version = dual_licensed();
+        version.licenses[0].text = None;
+        assert!(
+            render(&version, OutputFormat::Text)
+                .ends_with("not embedded in this build; see the link above.")
+        );
+    }
+
+    #[test]
+    fn test_json_lists_licenses() {
+        let json = render(&dual_licensed(), OutputFormat::Json);
+        assert!(json.contains(r#""license": "MIT OR Apache-2.0""#));
+        assert!(json.contains(r#""text": "MIT License\n\nPermission is hereby granted...\n""#));
+        assert!(json.contains(r#""text": null"#));
+    }
+}
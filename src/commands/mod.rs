@@ -1,5 +1,15 @@
+mod config;
 mod help;
+mod license;
+mod notices;
+mod sbom;
+mod update;
 mod version;
 
+pub use config::PrintConfigCommand;
 pub use help::HelpCommand;
+pub use license::LicenseCommand;
+pub use notices::NoticesCommand;
+pub use sbom::SbomCommand;
+pub use update::{SelfUpdateCommand, UpdateCheckCommand};
 pub use version::VersionCommand;
@@ -0,0 +1,106 @@
+use crate::command::Command;
+use crate::config::CliConfig;
+use crate::output::{OutputFormat, Value};
+use crate::version::license::format_notices;
+use crate::version::{Dependency, Version, VersionInfo};
+use std::error::Error;
+
+/// Prints the third-party dependency notices (`--licenses`): name, version
+/// and license of every crate from Cargo.lock compiled into the binary
+pub struct NoticesCommand {
+    version: VersionInfo,
+}
+
+impl NoticesCommand {
+    #[must_use]
+    pub fn new(version: VersionInfo) -> Self {
+        Self { version }
+    }
+}
+
+impl Command for NoticesCommand {
+    fn can_handle(&self, config: &dyn CliConfig) -> bool {
+        config.wants_licenses()
+    }
+
+    fn execute(&self, config: &dyn CliConfig) -> Result<(), Box<dyn Error>> {
+        let version = self.version.to_version();
+        let output = render(
+            &version,
+            &version.build_info.dependencies,
+            config.output_format(),
+        );
+        println!("{}", output.trim_end());
+        Ok(())
+    }
+
+    fn priority(&self) -> u8 {
+        3
+    }
+}
+
+fn render(version: &Version, dependencies: &[Dependency], format: OutputFormat) -> String {
+    if format != OutputFormat::Text {
+        return Value::map()
+            .with("version", version.version.to_string())
+            .with("license", version.license_name.as_str())
+            .with(
+                "dependencies",
+                dependencies
+                    .iter()
+                    .map(Dependency::to_value)
+                    .collect::<Vec<_>>(),
+            )
+            .render(format);
+    }
+
+    let mut out = format!(
+        "Version {} is licensed under {}.\n\nThird-party dependencies ({}):\n",
+        version.version,
+        version.license_name,
+        dependencies.len()
+    );
+    out.push_str(&format_notices(dependencies));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::BuildInfo;
+
+    fn sample() -> (Version, Vec<Dependency>) {
+        let version = Version::new(
+            "0.1.0".to_string(),
+            "Copyright (c) 2025 Example Corp".to_string(),
+            "MIT".to_string(),
+            String::new(),
+            BuildInfo::new(String::new(), "abc123def456".to_string(), 0),
+        );
+        let dependencies = vec![Dependency::new(
+            "clap".to_string(),
+            "4.5.0".to_string(),
+            "MIT OR Apache-2.0".to_string(),
+        )];
+        (version, dependencies)
+    }
+
+    #[test]
+    fn test_text_notices() {
+        let (version, dependencies) = sample();
+        assert_eq!(
+            render(&version, &dependencies, OutputFormat::Text),
+            "Version 0.1.0 is licensed under MIT.\n\n\
+             Third-party dependencies (1):\n\
+             clap  4.5.0  MIT OR Apache-2.0"
+        );
+    }
+
+    #[test]
+    fn test_json_notices() {
+        let (version, dependencies) = sample();
+        let json = render(&version, &dependencies, OutputFormat::Json);
+        assert!(json.contains(r#""name": "clap""#));
+        assert!(json.contains(r#""license": "MIT OR Apache-2.0""#));
+    }
+}
@@ -0,0 +1,35 @@
+use crate::command::Command;
+use crate::config::CliConfig;
+use crate::version::{VersionInfo, sbom};
+use std::error::Error;
+
+/// Prints the embedded software bill of materials (`--sbom [FORMAT]`)
+pub struct SbomCommand {
+    version: VersionInfo,
+}
+
+impl SbomCommand {
+    #[must_use]
+    pub fn new(version: VersionInfo) -> Self {
+        Self { version }
+    }
+}
+
+impl Command for SbomCommand {
+    fn can_handle(&self, config: &dyn CliConfig) -> bool {
+        config.sbom_format().is_some()
+    }
+
+    fn execute(&self, config: &dyn CliConfig) -> Result<(), Box<dyn Error>> {
+        let format = config.sbom_format().unwrap_or_default();
+        println!(
+            "{}",
+            sbom::render(&self.version.to_version(), format).trim_end()
+        );
+        Ok(())
+    }
+
+    fn priority(&self) -> u8 {
+        4
+    }
+}
@@ -0,0 +1,118 @@
+use crate::command::Command;
+use crate::config::CliConfig;
+use crate::output::OutputFormat;
+#[cfg(feature = "self-update")]
+use crate::update::SelfUpdate;
+use crate::update::UpdateSettings;
+use crate::version::{SemVer, VersionInfo};
+use std::error::Error;
+use std::fmt::Write as _;
+
+/// Compares the running version with a release manifest (`--check-update [MANIFEST]`)
+/// and prints the changelog of every newer release
+pub struct UpdateCheckCommand {
+    version: VersionInfo,
+}
+
+impl UpdateCheckCommand {
+    #[must_use]
+    pub fn new(version: VersionInfo) -> Self {
+        Self { version }
+    }
+}
+
+impl Command for UpdateCheckCommand {
+    fn can_handle(&self, config: &dyn CliConfig) -> bool {
+        config.wants_update_check()
+    }
+
+    fn execute(&self, config: &dyn CliConfig) -> Result<(), Box<dyn Error>> {
+        let current = current_semver(&self.version)?;
+        let settings = update_settings(&self.version, config.update_manifest());
+        let report = settings.check(&current)?;
+
+        let mut output = report.render(config.output_format());
+        if config.output_format() == OutputFormat::Text && config.verbosity() > 0 {
+            let _ = writeln!(
+                output,
+                "\nManifest: {} (checked {}{})",
+                report.location,
+                report.checked_at.format("%Y-%m-%d %H:%M UTC"),
+                if report.cached { ", cached" } else { "" }
+            );
+        }
+        println!("{}", output.trim_end());
+        Ok(())
+    }
+
+    fn priority(&self) -> u8 {
+        5
+    }
+}
+
+/// Installs the newest release from the manifest (`--self-update [MANIFEST]`),
+/// or restores the previous executable (`--rollback-update`). With `-n` the
+/// release is downloaded and verified but not installed.
+pub struct SelfUpdateCommand {
+    version: VersionInfo,
+}
+
+impl SelfUpdateCommand {
+    #[must_use]
+    pub fn new(version: VersionInfo) -> Self {
+        Self { version }
+    }
+}
+
+impl Command for SelfUpdateCommand {
+    fn can_handle(&self, config: &dyn CliConfig) -> bool {
+        config.wants_self_update() || config.wants_rollback_update()
+    }
+
+    #[cfg(feature = "self-update")]
+    fn execute(&self, config: &dyn CliConfig) -> Result<(), Box<dyn Error>> {
+        let executable = std::env::current_exe()
+            .and_then(std::fs::canonicalize)
+            .map_err(|e| format!("cannot locate the running executable: {e}"))?;
+        let updater = SelfUpdate::new(self.version.name, executable, self.version.target)
+            .with_public_key(self.version.update_public_key.map(str::to_string));
+
+        let outcome = if config.wants_rollback_update() {
+            updater.rollback()?
+        } else {
+            let current = current_semver(&self.version)?;
+            let settings = update_settings(&self.version, config.update_manifest());
+            updater.run(&settings, &current, config.is_dry_run())?
+        };
+        println!("{}", outcome.render(config.output_format()).trim_end());
+        Ok(())
+    }
+
+    #[cfg(not(feature = "self-update"))]
+    fn execute(&self, _config: &dyn CliConfig) -> Result<(), Box<dyn Error>> {
+        Err("this build does not include self-update (the `self-update` feature of sw-cli)".into())
+    }
+
+    fn priority(&self) -> u8 {
+        6
+    }
+}
+
+/// Update settings from `[package.metadata.sw-cli]`, then the environment,
+/// then the command line
+fn update_settings(version: &VersionInfo, manifest: Option<&str>) -> UpdateSettings {
+    let mut settings = UpdateSettings::new(version.name)
+        .with_manifest(version.update_manifest.map(str::to_string));
+    if !version.update_check {
+        settings = settings.disabled("update-check = false in [package.metadata.sw-cli]");
+    }
+    settings
+        .with_env()
+        .with_manifest(manifest.map(str::to_string))
+}
+
+fn current_semver(version: &VersionInfo) -> Result<SemVer, String> {
+    version
+        .semver()
+        .ok_or_else(|| format!("version '{}' is not a semantic version", version.version))
+}
@@ -1,36 +1,51 @@
 use crate::command::Command;
 use crate::config::CliConfig;
-use crate::version::{BuildInfo, Version};
+use crate::output::OutputFormat;
+use crate::version::{Component, VersionInfo};
 use std::error::Error;
 
-pub struct VersionCommand;
+/// Prints the application's version information (`-V`, `--version`)
+pub struct VersionCommand {
+    version: VersionInfo,
+    components: Vec<VersionInfo>,
+}
+
+impl VersionCommand {
+    /// Report `version`, usually `sw_cli::build_info!()` of the application
+    #[must_use]
+    pub fn new(version: VersionInfo) -> Self {
+        Self {
+            version,
+            components: Vec::new(),
+        }
+    }
+
+    /// List the build information of libraries or plugins in the component
+    /// versions table of `-V -v` and the machine-readable output
+    #[must_use]
+    pub fn with_components(mut self, components: Vec<VersionInfo>) -> Self {
+        self.components = components;
+        self
+    }
+}
 
 impl Command for VersionCommand {
     fn can_handle(&self, config: &dyn CliConfig) -> bool {
         config.wants_version()
     }
 
-    fn execute(&self, _config: &dyn CliConfig) -> Result<(), Box<dyn Error>> {
-        // Include the generated version_info.rs
-        mod version_info {
-            include!(concat!(env!("OUT_DIR"), "/version_info.rs"));
-        }
-
-        let build_info = BuildInfo::new(
-            version_info::BUILD_HOST.to_string(),
-            version_info::GIT_COMMIT_SHA.to_string(),
-            version_info::BUILD_TIMESTAMP,
-        );
-
-        let version_obj = Version::new(
-            version_info::VERSION.to_string(),
-            version_info::COPYRIGHT.to_string(),
-            version_info::LICENSE_NAME.to_string(),
-            version_info::LICENSE_URL.to_string(),
-            build_info,
-        );
+    fn execute(&self, config: &dyn CliConfig) -> Result<(), Box<dyn Error>> {
+        let version_obj = self
+            .version
+            .to_version()
+            .with_components(self.components.iter().map(Component::from).collect());
 
-        println!("{version_obj}");
+        // -V -v adds toolchain details to the text output
+        let output = match config.output_format() {
+            OutputFormat::Text if config.verbosity() > 0 => format!("{version_obj:#}"),
+            format => version_obj.render(format),
+        };
+        println!("{}", output.trim_end());
         Ok(())
     }
 
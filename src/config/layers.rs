@@ -0,0 +1,1174 @@
+//! Configuration files and environment variables layered under the command
+//! line.
+//!
+//! From lowest to highest precedence: built-in defaults, the system file
+//! (`/etc/<app>/config.toml`), the user file
+//! (`$XDG_CONFIG_HOME/<app>/config.toml`), the project file (the nearest
+//! `.<app>.toml` in the current directory or one of its parents),
+//! environment variables (`<APP>_<KEY>` unless bound to another name), and
+//! flags. `--config <FILE>` reads
+//! only `FILE` instead of the three discovered files; `--no-config` reads
+//! none. Every value remembers which layer it came from.
+//!
+//! A file may also hold `[profile.<name>]` tables selected with `--profile`
+//! or `<APP>_PROFILE`. Within each file the profile's keys override the
+//! top-level ones, and a profile can take the keys it does not set from
+//! another with `inherits = "<name>"`.
+//!
+//! A key no resolver asked for, such as a misspelt setting, is reported as a
+//! warning on stderr; [`ConfigLayers::with_strict_keys`] makes it an error.
+
+use crate::output::Value;
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Where a configuration value came from
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Source {
+    /// Built-in default
+    #[default]
+    Default,
+    /// Configuration file
+    File(PathBuf),
+    /// Environment variable
+    Env(String),
+    /// Command-line flag
+    CommandLine,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Env(var) => write!(f, "env {var}"),
+            Self::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+impl Source {
+    /// `default`, `file`, `env` or `command_line`
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::File(_) => "file",
+            Self::Env(_) => "env",
+            Self::CommandLine => "command_line",
+        }
+    }
+}
+
+/// Source of every value that did not come from the defaults, by key
+pub type Sources = BTreeMap<String, Source>;
+
+/// A parsed configuration file
+#[derive(Debug, Clone)]
+struct ConfigFile {
+    path: PathBuf,
+    values: Value,
+}
+
+impl ConfigFile {
+    /// `[profile.<name>]` of this file
+    fn profile(&self, name: &str) -> Option<&Value> {
+        self.values.get("profile")?.get(name)
+    }
+
+    /// `key` from the first profile of `chain` that sets it, else the top level
+    fn get(&self, chain: &[String], key: &str) -> Option<&Value> {
+        chain
+            .iter()
+            .find_map(|name| self.profile(name)?.get(key))
+            .or_else(|| self.values.get(key))
+    }
+}
+
+/// The configuration files and environment of one run
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLayers {
+    app_name: String,
+    env_prefix: String,
+    /// Variables bound to a key with `with_env_var`, by key
+    env_names: BTreeMap<String, String>,
+    /// Lowest precedence first
+    files: Vec<ConfigFile>,
+    vars: BTreeMap<String, String>,
+    /// Selected profile and who selected it
+    profile: Option<(String, Source)>,
+    /// The selected profile, then the ones it inherits from
+    profile_chain: Vec<String>,
+    /// Unknown keys are errors rather than warnings
+    strict_keys: bool,
+    /// Keys only the command line sets
+    cli_only: BTreeSet<String>,
+}
+
+impl ConfigLayers {
+    /// No files and no environment yet. Variables are named
+    /// `<APP>_<KEY>`, e.g. `MY_TOOL_DRY_RUN` for `my-tool`.
+    #[must_use]
+    pub fn new(app_name: &str) -> Self {
+        Self {
+            app_name: app_name.to_string(),
+            ..Self::default()
+        }
+        .with_env_prefix(&app_name.to_ascii_uppercase().replace('-', "_"))
+    }
+
+    /// Layers for a run with the standard `--config` / `--no-config` flags.
+    ///
+    /// # Errors
+    /// Returns a message if a configuration file cannot be read or parsed,
+    /// or the file given to `--config` does not exist.
+    pub fn load(app_name: &str, matches: &ArgMatches) -> Result<Self, String> {
+        Self::new(app_name).with_env().with_files_for(matches)
+    }
+
+    /// Add the files selected by the standard `--config` / `--no-config`
+    /// flags: the `--config` file, none, or the discovered ones. Then select
+    /// the profile given to `--profile`, or else by the profile variable
+    /// (`<APP>_PROFILE`) if the environment was loaded first.
+    ///
+    /// # Errors
+    /// Returns a message if a configuration file cannot be read or parsed,
+    /// the file given to `--config` does not exist, or the profile is not
+    /// defined in the files.
+    pub fn with_files_for(self, matches: &ArgMatches) -> Result<Self, String> {
+        let layers = if let Some(path) = arg_string(matches, "config") {
+            self.with_file(Path::new(&path))?
+        } else if arg_flag(matches, "no-config") {
+            self
+        } else {
+            let dir = std::env::current_dir().unwrap_or_default();
+            let app_name = self.app_name.clone();
+            self.discover(&app_name, &dir)?
+        };
+        if let Some(name) = arg_string(matches, "profile") {
+            return layers.select_profile(&name, Source::CommandLine);
+        }
+        let from_env = layers
+            .env_var("profile")
+            .and_then(|var| Some((layers.vars.get(&var)?.clone(), var)))
+            .filter(|(name, _)| !name.is_empty());
+        match from_env {
+            Some((name, var)) => layers.select_profile(&name, Source::Env(var)),
+            None => Ok(layers),
+        }
+    }
+
+    /// Make unknown keys in the files an error of [`Resolver::finish`]
+    /// instead of a warning
+    #[must_use]
+    pub fn with_strict_keys(mut self, strict: bool) -> Self {
+        self.strict_keys = strict;
+        self
+    }
+
+    /// Use the `[profile.<name>]` tables of the files added so far.
+    ///
+    /// # Errors
+    /// Returns a message if no file defines the profile or one it inherits
+    /// from, or the profiles inherit from each other in a cycle.
+    pub fn with_profile(self, name: &str) -> Result<Self, String> {
+        self.select_profile(name, Source::Default)
+    }
+
+    fn select_profile(mut self, name: &str, source: Source) -> Result<Self, String> {
+        let mut chain: Vec<String> = Vec::new();
+        let mut next = Some(name.to_string());
+        while let Some(current) = next {
+            if chain.contains(&current) {
+                chain.push(current);
+                return Err(format!(
+                    "profile '{name}' inherits from itself: {}",
+                    chain.join(" -> ")
+                ));
+            }
+            if !self
+                .files
+                .iter()
+                .any(|file| file.profile(&current).is_some())
+            {
+                let defined = self.profiles();
+                let defined = if defined.is_empty() {
+                    "no profiles are defined".to_string()
+                } else {
+                    format!("defined: {}", defined.join(", "))
+                };
+                return Err(match chain.last() {
+                    None => format!("unknown profile '{current}' ({defined})"),
+                    Some(child) => {
+                        format!(
+                            "profile '{child}' inherits unknown profile '{current}' ({defined})"
+                        )
+                    }
+                });
+            }
+            // The highest file that says what `current` inherits decides
+            next = self
+                .files
+                .iter()
+                .rev()
+                .find_map(|file| {
+                    let inherits = file.profile(&current)?.get("inherits")?;
+                    Some(inherits.as_str().map(str::to_string).ok_or_else(|| {
+                        format!(
+                            "{}: 'profile.{current}.inherits' must be a profile name",
+                            file.path.display()
+                        )
+                    }))
+                })
+                .transpose()?;
+            chain.push(current);
+        }
+        self.profile = Some((name.to_string(), source));
+        self.profile_chain = chain;
+        Ok(self)
+    }
+
+    /// The selected profile
+    #[must_use]
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    /// Names of the profiles defined in the files, sorted
+    #[must_use]
+    pub fn profiles(&self) -> Vec<&str> {
+        let names: BTreeSet<&str> = self
+            .files
+            .iter()
+            .filter_map(|file| match file.values.get("profile")? {
+                Value::Map(entries) => Some(entries),
+                _ => None,
+            })
+            .flatten()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        names.into_iter().collect()
+    }
+
+    /// Use `<PREFIX>_<KEY>` variables; an empty prefix disables them
+    #[must_use]
+    pub fn with_env_prefix(mut self, prefix: &str) -> Self {
+        self.env_prefix = prefix.to_string();
+        self
+    }
+
+    /// Read `key` from the variable `var` instead of `<PREFIX>_<KEY>`
+    #[must_use]
+    pub fn with_env_var(mut self, key: &str, var: &str) -> Self {
+        self.env_names
+            .insert(key.replace('-', "_"), var.to_string());
+        self
+    }
+
+    /// Take `key` from the command line only: no variable or file sets it,
+    /// and a file that tries to is reported like an unknown key
+    #[must_use]
+    pub fn with_cli_only(mut self, key: &str) -> Self {
+        self.cli_only.insert(key.replace('-', "_"));
+        self
+    }
+
+    /// Snapshot the process environment
+    #[must_use]
+    pub fn with_env(self) -> Self {
+        self.with_vars(std::env::vars())
+    }
+
+    fn with_vars(mut self, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.vars.extend(vars);
+        self
+    }
+
+    /// Add a file above the ones already loaded.
+    ///
+    /// # Errors
+    /// Returns a message if the file cannot be read or is not valid TOML.
+    pub fn with_file(mut self, path: &Path) -> Result<Self, String> {
+        let text =
+            fs::read_to_string(path).map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+        let values = Value::from_toml(&text).map_err(|e| format!("{}: {e}", path.display()))?;
+        self.files.push(ConfigFile {
+            path: path.to_path_buf(),
+            values,
+        });
+        Ok(self)
+    }
+
+    /// Add the system, user and project files that exist, looking for the
+    /// project file from `dir` upwards.
+    ///
+    /// # Errors
+    /// Returns a message if one of them cannot be read or parsed.
+    pub fn discover(self, app_name: &str, dir: &Path) -> Result<Self, String> {
+        [
+            system_file(app_name),
+            user_file(app_name),
+            project_file(app_name, dir),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|path| path.is_file())
+        .try_fold(self, |layers, path| layers.with_file(&path))
+    }
+
+    /// Files in the order they were loaded, lowest precedence first
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|file| file.path.as_path())
+    }
+
+    /// Environment variable for `key`: its bound name, or `<PREFIX>_<KEY>`
+    /// if there is a prefix. `None` for [command-line only](Self::with_cli_only)
+    /// keys.
+    #[must_use]
+    pub fn env_var(&self, key: &str) -> Option<String> {
+        if self.cli_only.contains(&key.replace('-', "_")) {
+            return None;
+        }
+        if let Some(var) = self.env_names.get(&key.replace('-', "_")) {
+            return Some(var.clone());
+        }
+        (!self.env_prefix.is_empty()).then(|| {
+            format!(
+                "{}_{}",
+                self.env_prefix,
+                key.replace('-', "_").to_ascii_uppercase()
+            )
+        })
+    }
+
+    /// Rank of a source: higher ones override lower ones
+    #[must_use]
+    pub fn precedence(&self, source: &Source) -> usize {
+        match source {
+            Source::Default => 0,
+            Source::File(path) => self
+                .files
+                .iter()
+                .rposition(|f| f.path == *path)
+                .map_or(0, |i| i + 1),
+            Source::Env(_) => self.files.len() + 1,
+            Source::CommandLine => self.files.len() + 2,
+        }
+    }
+
+    /// Resolve values for `matches` against these layers
+    #[must_use]
+    pub fn resolver<'a>(&'a self, matches: &'a ArgMatches) -> Resolver<'a> {
+        Resolver {
+            layers: self,
+            matches,
+            sources: Sources::new(),
+            keys: BTreeSet::new(),
+        }
+    }
+}
+
+/// `/etc/<app>/config.toml` (`%PROGRAMDATA%\<app>\config.toml` on Windows)
+#[must_use]
+pub fn system_file(app_name: &str) -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        PathBuf::from(std::env::var_os("PROGRAMDATA")?)
+    } else {
+        PathBuf::from("/etc")
+    };
+    Some(base.join(app_name).join("config.toml"))
+}
+
+/// `config.toml` in the user's configuration directory for `app_name`
+#[must_use]
+pub fn user_file(app_name: &str) -> Option<PathBuf> {
+    let var = |key| std::env::var_os(key).filter(|v| !v.is_empty());
+    let base = var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| var("APPDATA").map(PathBuf::from))?;
+    Some(base.join(app_name).join("config.toml"))
+}
+
+/// Nearest `.<app>.toml` in `dir` or one of its parents
+#[must_use]
+pub fn project_file(app_name: &str, dir: &Path) -> Option<PathBuf> {
+    let name = format!(".{app_name}.toml");
+    dir.ancestors()
+        .map(|d| d.join(&name))
+        .find(|path| path.is_file())
+}
+
+/// The layer a value was found in
+enum Found<'a> {
+    /// Command line or clap default
+    Matches,
+    Env(&'a str, &'a str),
+    File(&'a Path, &'a Value),
+}
+
+/// Looks up each value in the highest layer that sets it and records where
+/// it came from. Keys are argument ids with `-` replaced by `_`.
+pub struct Resolver<'a> {
+    layers: &'a ConfigLayers,
+    matches: &'a ArgMatches,
+    sources: Sources,
+    keys: BTreeSet<String>,
+}
+
+impl<'a> Resolver<'a> {
+    fn find(&mut self, id: &str) -> Found<'a> {
+        let key = id.replace('-', "_");
+        self.keys.insert(key.clone());
+        let layers = self.layers;
+        let (found, source) = if on_command_line(self.matches, id) {
+            (Found::Matches, Some(Source::CommandLine))
+        } else if let Some((var, value)) = layers
+            .env_var(&key)
+            .and_then(|var| layers.vars.get_key_value(&var))
+        {
+            (Found::Env(var, value), Some(Source::Env(var.clone())))
+        } else if let Some((path, value)) = layers
+            .files
+            .iter()
+            .rev()
+            .filter(|_| !layers.cli_only.contains(&key))
+            .find_map(|file| Some((file.path.as_path(), file.get(&layers.profile_chain, &key)?)))
+        {
+            (
+                Found::File(path, value),
+                Some(Source::File(path.to_path_buf())),
+            )
+        } else {
+            (Found::Matches, None)
+        };
+        match source {
+            Some(source) => self.sources.insert(key, source),
+            None => self.sources.remove(&key),
+        };
+        found
+    }
+
+    /// The parsed command line
+    #[must_use]
+    pub fn matches(&self) -> &'a ArgMatches {
+        self.matches
+    }
+
+    /// Where the value of `key` came from
+    #[must_use]
+    pub fn source(&self, key: &str) -> Source {
+        self.sources.get(key).cloned().unwrap_or_default()
+    }
+
+    /// The selected profile. Without layers that selected one, the value of
+    /// the standard `--profile` flag.
+    pub fn profile(&mut self) -> Option<String> {
+        self.keys.insert("profile".to_string());
+        let (name, source) = match &self.layers.profile {
+            Some((name, source)) => (name.clone(), source.clone()),
+            None => (arg_string(self.matches, "profile")?, Source::CommandLine),
+        };
+        self.sources.insert("profile".to_string(), source);
+        Some(name)
+    }
+
+    /// Rank of the layer `key` came from: higher ones override lower ones
+    #[must_use]
+    pub fn precedence(&self, key: &str) -> usize {
+        self.layers.precedence(&self.source(key))
+    }
+
+    /// A `SetTrue` flag. Files need a boolean; variables accept
+    /// `1/0`, `true/false`, `yes/no` and `on/off`.
+    ///
+    /// # Errors
+    /// Returns a message naming the file or variable with an invalid value.
+    pub fn flag(&mut self, id: &str) -> Result<bool, String> {
+        match self.find(id) {
+            Found::Matches => Ok(arg_flag(self.matches, id)),
+            Found::Env(var, value) => parse_bool(value)
+                .ok_or_else(|| format!("{var}: expected true or false, got '{value}'")),
+            Found::File(path, value) => value
+                .as_bool()
+                .ok_or_else(|| invalid(path, id, "must be true or false")),
+        }
+    }
+
+    /// A `Count` flag such as `-vvv`; files and variables may also use a
+    /// boolean for 0 or 1.
+    ///
+    /// # Errors
+    /// Returns a message naming the file or variable with an invalid value.
+    pub fn count(&mut self, id: &str) -> Result<u8, String> {
+        match self.find(id) {
+            Found::Matches => Ok(arg_count(self.matches, id)),
+            Found::Env(var, value) => value
+                .trim()
+                .parse()
+                .ok()
+                .or_else(|| parse_bool(value).map(u8::from))
+                .ok_or_else(|| format!("{var}: expected a number from 0 to 255, got '{value}'")),
+            Found::File(path, value) => match value {
+                Value::Bool(b) => Some(u8::from(*b)),
+                Value::Int(n) => u8::try_from(*n).ok(),
+                _ => None,
+            }
+            .ok_or_else(|| invalid(path, id, "must be a number from 0 to 255")),
+        }
+    }
+
+    /// A single value, as text
+    ///
+    /// # Errors
+    /// Returns a message if a file sets it to a list or table.
+    pub fn string(&mut self, id: &str) -> Result<Option<String>, String> {
+        match self.find(id) {
+            Found::Matches => Ok(arg_string(self.matches, id)),
+            Found::Env(_, value) => Ok(Some(value.to_string())),
+            Found::File(path, value) => scalar(value)
+                .map(Some)
+                .ok_or_else(|| invalid(path, id, "must be a string, number or boolean")),
+        }
+    }
+
+    /// A single value parsed with `FromStr`, such as a number
+    ///
+    /// # Errors
+    /// Returns a message naming the file or variable whose value does not
+    /// parse.
+    pub fn parse<T>(&mut self, id: &str) -> Result<Option<T>, String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let Some(text) = self.string(id)? else {
+            return Ok(None);
+        };
+        let key = id.replace('-', "_");
+        text.trim().parse().map(Some).map_err(|e| {
+            format!(
+                "{}: invalid value '{text}' for '{key}': {e}",
+                self.source(&key)
+            )
+        })
+    }
+
+    /// A repeatable value. Variables separate items with commas.
+    ///
+    /// # Errors
+    /// Returns a message if a file sets it to a table or nested list.
+    pub fn strings(&mut self, id: &str) -> Result<Option<Vec<String>>, String> {
+        match self.find(id) {
+            Found::Matches => Ok(self
+                .matches
+                .try_get_many::<String>(id)
+                .ok()
+                .flatten()
+                .map(|values| values.cloned().collect())),
+            Found::Env(_, value) => Ok(Some(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            )),
+            Found::File(path, value) => match value {
+                Value::List(items) => items.iter().map(scalar).collect(),
+                other => scalar(other).map(|item| vec![item]),
+            }
+            .map(Some)
+            .ok_or_else(|| invalid(path, id, "must be a string or a list of strings")),
+        }
+    }
+
+    /// A path; relative paths in a file are relative to that file
+    ///
+    /// # Errors
+    /// Returns a message if a file sets it to a list or table.
+    pub fn path(&mut self, id: &str) -> Result<Option<PathBuf>, String> {
+        match self.find(id) {
+            Found::File(path, value) => value
+                .as_str()
+                .map(|item| Some(relative_to(path, item)))
+                .ok_or_else(|| invalid(path, id, "must be a path")),
+            _ => Ok(self.string(id)?.map(PathBuf::from)),
+        }
+    }
+
+    /// A repeatable path. Variables separate items like `PATH` does.
+    ///
+    /// # Errors
+    /// Returns a message if a file sets it to something other than paths.
+    pub fn paths(&mut self, id: &str) -> Result<Option<Vec<PathBuf>>, String> {
+        match self.find(id) {
+            Found::Env(_, value) => Ok(Some(std::env::split_paths(value).collect())),
+            Found::File(path, value) => match value {
+                Value::List(items) => items.iter().map(Value::as_str).collect(),
+                other => other.as_str().map(|item| vec![item]),
+            }
+            .map(|items| {
+                Some(
+                    items
+                        .into_iter()
+                        .map(|item| relative_to(path, item))
+                        .collect(),
+                )
+            })
+            .ok_or_else(|| invalid(path, id, "must be a path or a list of paths")),
+            Found::Matches => Ok(self
+                .matches
+                .try_get_many::<String>(id)
+                .ok()
+                .flatten()
+                .map(|values| values.map(PathBuf::from).collect())),
+        }
+    }
+
+    /// Environment variable of every key resolved so far
+    #[must_use]
+    pub fn env_vars(&self) -> BTreeMap<String, String> {
+        self.keys
+            .iter()
+            .filter_map(|key| Some((key.clone(), self.layers.env_var(key)?)))
+            .collect()
+    }
+
+    /// Keys the files and their profiles set that no resolver asked for,
+    /// one message each, e.g. for a misspelt setting. Profiles are only
+    /// known once [`profile`](Self::profile) has been resolved.
+    ///
+    /// # Errors
+    /// Returns a message if a `profile` entry is not a table of tables.
+    pub fn unknown_keys(&self) -> Result<Vec<String>, String> {
+        let keys: BTreeSet<String> = self
+            .keys
+            .difference(&self.layers.cli_only)
+            .cloned()
+            .collect();
+        let known = || {
+            keys.iter()
+                .filter(|key| *key != "profile")
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut unknown = Vec::new();
+        for file in &self.layers.files {
+            let Value::Map(entries) = &file.values else {
+                continue;
+            };
+            for (key, value) in entries {
+                if key == "profile" && keys.contains(key) {
+                    unknown.extend(check_profiles(&file.path, value, &keys, known)?);
+                } else if key == "profile" || !keys.contains(key) {
+                    unknown.push(format!(
+                        "{}: unknown key '{key}' (expected one of: {})",
+                        file.path.display(),
+                        known()
+                    ));
+                }
+            }
+        }
+        Ok(unknown)
+    }
+
+    /// Return the sources after checking the files for
+    /// [unknown keys](Self::unknown_keys), which are printed to stderr as
+    /// warnings unless the layers are [strict](ConfigLayers::with_strict_keys).
+    ///
+    /// # Errors
+    /// Returns a message if a `profile` entry is not a table of tables or,
+    /// with strict layers, naming the first unknown key.
+    pub fn finish(self) -> Result<Sources, String> {
+        let unknown = self.unknown_keys()?;
+        if self.layers.strict_keys {
+            if let Some(first) = unknown.into_iter().next() {
+                return Err(first);
+            }
+        } else {
+            for message in unknown {
+                eprintln!("warning: {message}");
+            }
+        }
+        Ok(self.sources)
+    }
+}
+
+/// Every `[profile.<name>]` must be a table; returns its keys other than
+/// `inherits` that are not known
+fn check_profiles(
+    path: &Path,
+    profiles: &Value,
+    keys: &BTreeSet<String>,
+    known: impl Fn() -> String,
+) -> Result<Vec<String>, String> {
+    let Value::Map(profiles) = profiles else {
+        return Err(format!(
+            "{}: 'profile' must be a table of [profile.<name>] tables",
+            path.display()
+        ));
+    };
+    let mut unknown = Vec::new();
+    for (name, profile) in profiles {
+        let Value::Map(entries) = profile else {
+            return Err(format!(
+                "{}: 'profile.{name}' must be a table",
+                path.display()
+            ));
+        };
+        unknown.extend(
+            entries
+                .iter()
+                .filter(|(key, _)| *key == "profile" || (*key != "inherits" && !keys.contains(key)))
+                .map(|(key, _)| {
+                    format!(
+                        "{}: unknown key '{key}' in [profile.{name}] (expected inherits or one of: {})",
+                        path.display(),
+                        known()
+                    )
+                }),
+        );
+    }
+    Ok(unknown)
+}
+
+// `ArgMatches` accessors that read an id the command does not define as
+// unset, rather than panicking: a `Command` built from an older
+// `standard_args()` lacks most of today's standard ids.
+
+/// A `SetTrue` flag
+pub(crate) fn arg_flag(matches: &ArgMatches, id: &str) -> bool {
+    matches
+        .try_get_one::<bool>(id)
+        .ok()
+        .flatten()
+        .copied()
+        .unwrap_or(false)
+}
+
+/// A `Count` flag, or a `SetTrue` one counted as 0 or 1 (`-v` before it
+/// became countable)
+pub(crate) fn arg_count(matches: &ArgMatches, id: &str) -> u8 {
+    match matches.try_get_one::<u8>(id) {
+        Ok(count) => count.copied().unwrap_or(0),
+        Err(_) => u8::from(arg_flag(matches, id)),
+    }
+}
+
+/// A single `String` value
+pub(crate) fn arg_string(matches: &ArgMatches, id: &str) -> Option<String> {
+    matches.try_get_one::<String>(id).ok().flatten().cloned()
+}
+
+/// Whether the value of `id` was given on the command line
+pub(crate) fn on_command_line(matches: &ArgMatches, id: &str) -> bool {
+    matches.try_contains_id(id).unwrap_or(false)
+        && matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" | "" => Some(false),
+        _ => None,
+    }
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::Str(s) => Some(s.clone()),
+        Value::Int(i) => Some(i.to_string()),
+        Value::Float(x) => Some(x.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn invalid(path: &Path, id: &str, expected: &str) -> String {
+    format!("{}: '{}' {expected}", path.display(), id.replace('-', "_"))
+}
+
+fn relative_to(file: &Path, item: &str) -> PathBuf {
+    file.parent().unwrap_or(Path::new("")).join(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, ArgAction, Command};
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sw-cli-config-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn try_matches(args: &[&str]) -> Result<ArgMatches, clap::Error> {
+        Command::new("tool")
+            .disable_help_flag(true)
+            .disable_version_flag(true)
+            .args(crate::builder::standard_args())
+            .arg(Arg::new("pattern").long("pattern"))
+            .arg(Arg::new("input").long("input").action(ArgAction::Append))
+            .try_get_matches_from(std::iter::once("tool").chain(args.iter().copied()))
+    }
+
+    fn matches(args: &[&str]) -> ArgMatches {
+        try_matches(args).unwrap()
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_precedence() {
+        let dir = scratch_dir("precedence");
+        let user = dir.join("user.toml");
+        let project = dir.join("project.toml");
+        fs::write(&user, "pattern = \"user\"\ndry_run = true\nverbose = 1\n").unwrap();
+        fs::write(
+            &project,
+            "pattern = \"project\"\ninput = [\"a.txt\", \"/b.txt\"]\n",
+        )
+        .unwrap();
+        let layers = ConfigLayers::new("my-tool")
+            .with_file(&user)
+            .unwrap()
+            .with_file(&project)
+            .unwrap()
+            .with_vars(vars(&[("MY_TOOL_DRY_RUN", "no"), ("OTHER_PATTERN", "x")]));
+
+        let matches = matches(&["-vv"]);
+        let mut resolver = layers.resolver(&matches);
+        assert_eq!(resolver.string("pattern"), Ok(Some("project".to_string())));
+        assert_eq!(resolver.flag("dry-run"), Ok(false));
+        assert_eq!(resolver.count("verbose"), Ok(2));
+        assert_eq!(
+            resolver.paths("input"),
+            Ok(Some(vec![dir.join("a.txt"), PathBuf::from("/b.txt")]))
+        );
+        assert_eq!(resolver.flag("license"), Ok(false));
+        assert_eq!(resolver.source("pattern"), Source::File(project.clone()));
+        assert_eq!(
+            resolver.source("dry_run"),
+            Source::Env("MY_TOOL_DRY_RUN".to_string())
+        );
+        assert_eq!(resolver.source("verbose"), Source::CommandLine);
+        assert_eq!(resolver.source("license"), Source::Default);
+
+        let sources = resolver.finish().unwrap();
+        assert_eq!(sources.len(), 4);
+        assert_eq!(layers.precedence(&Source::File(user)), 1);
+        assert!(layers.precedence(&Source::CommandLine) > layers.precedence(&sources["dry_run"]));
+    }
+
+    #[test]
+    fn test_env_values() {
+        let layers = ConfigLayers::new("tool").with_vars(vars(&[
+            ("TOOL_PATTERN", "from env"),
+            ("TOOL_INPUT", "a, b,"),
+            ("TOOL_VERBOSE", "3"),
+            ("TOOL_DRY_RUN", "maybe"),
+        ]));
+        let matches = matches(&["--pattern", "flag"]);
+        let mut resolver = layers.resolver(&matches);
+        assert_eq!(resolver.string("pattern"), Ok(Some("flag".to_string())));
+        assert_eq!(
+            resolver.strings("input"),
+            Ok(Some(vec!["a".to_string(), "b".to_string()]))
+        );
+        assert_eq!(resolver.count("verbose"), Ok(3));
+        assert_eq!(
+            resolver.flag("dry-run"),
+            Err("TOOL_DRY_RUN: expected true or false, got 'maybe'".to_string())
+        );
+        assert_eq!(ConfigLayers::new("").env_var("verbose"), None);
+    }
+
+    #[test]
+    fn test_env_bindings() {
+        let layers = ConfigLayers::new("my-tool")
+            .with_env_prefix("MT")
+            .with_env_var("pattern", "GREP_PATTERN")
+            .with_vars(vars(&[
+                ("GREP_PATTERN", "bound"),
+                ("MT_PATTERN", "ignored"),
+                ("MT_DRY_RUN", "on"),
+                ("MY_TOOL_VERBOSE", "2"),
+            ]));
+        let matches = matches(&[]);
+        let mut resolver = layers.resolver(&matches);
+        assert_eq!(resolver.string("pattern"), Ok(Some("bound".to_string())));
+        assert_eq!(resolver.flag("dry-run"), Ok(true));
+        assert_eq!(resolver.count("verbose"), Ok(0));
+        assert_eq!(
+            resolver.env_vars().into_iter().collect::<Vec<_>>(),
+            [
+                ("dry_run".to_string(), "MT_DRY_RUN".to_string()),
+                ("pattern".to_string(), "GREP_PATTERN".to_string()),
+                ("verbose".to_string(), "MT_VERBOSE".to_string()),
+            ]
+        );
+        assert_eq!(
+            resolver.source("pattern"),
+            Source::Env("GREP_PATTERN".to_string())
+        );
+    }
+
+    #[test]
+    fn test_file_errors() {
+        let dir = scratch_dir("errors");
+        let path = dir.join("config.toml");
+        fs::write(&path, "dry_run = \"yes\"\npatern = \"typo\"\n").unwrap();
+        let layers = ConfigLayers::new("tool")
+            .with_strict_keys(true)
+            .with_file(&path)
+            .unwrap();
+        let matches = matches(&[]);
+
+        let mut resolver = layers.resolver(&matches);
+        assert_eq!(
+            resolver.flag("dry-run"),
+            Err(format!(
+                "{}: 'dry_run' must be true or false",
+                path.display()
+            ))
+        );
+        assert_eq!(resolver.string("pattern"), Ok(None));
+        assert_eq!(
+            resolver.finish(),
+            Err(format!(
+                "{}: unknown key 'patern' (expected one of: dry_run, pattern)",
+                path.display()
+            ))
+        );
+
+        // Without strict keys the typo is only a warning
+        let layers = layers.with_strict_keys(false);
+        let mut resolver = layers.resolver(&matches);
+        assert_eq!(resolver.string("pattern"), Ok(None));
+        assert_eq!(resolver.count("verbose"), Ok(0));
+        assert_eq!(
+            resolver.unknown_keys(),
+            Ok(vec![
+                format!(
+                    "{}: unknown key 'dry_run' (expected one of: pattern, verbose)",
+                    path.display()
+                ),
+                format!(
+                    "{}: unknown key 'patern' (expected one of: pattern, verbose)",
+                    path.display()
+                ),
+            ])
+        );
+        assert!(resolver.finish().is_ok());
+
+        fs::write(&path, "pattern = \n").unwrap();
+        let err = ConfigLayers::new("tool").with_file(&path).unwrap_err();
+        assert_eq!(
+            err,
+            format!("{}: invalid TOML at line 1: invalid value", path.display())
+        );
+        assert!(
+            ConfigLayers::new("tool")
+                .with_file(&dir.join("missing.toml"))
+                .unwrap_err()
+                .starts_with("cannot read ")
+        );
+    }
+
+    #[test]
+    fn test_discovery() {
+        let dir = scratch_dir("discovery");
+        let nested = dir.join("src").join("deep");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(project_file("tool", &nested), None);
+        fs::write(dir.join(".tool.toml"), "pattern = \"project\"\n").unwrap();
+        assert_eq!(project_file("tool", &nested), Some(dir.join(".tool.toml")));
+
+        let explicit = dir.join("explicit.toml");
+        fs::write(&explicit, "pattern = \"explicit\"\n").unwrap();
+        let explicit_arg = explicit.to_string_lossy().into_owned();
+        let layers = ConfigLayers::load("tool", &matches(&["--config", &explicit_arg])).unwrap();
+        assert_eq!(layers.files().collect::<Vec<_>>(), [explicit.as_path()]);
+
+        let layers = ConfigLayers::load("tool", &matches(&["--no-config"])).unwrap();
+        assert_eq!(layers.files().count(), 0);
+
+        assert!(try_matches(&["--config", "x.toml", "--no-config"]).is_err());
+    }
+
+    #[test]
+    fn test_profiles() {
+        let dir = scratch_dir("profiles");
+        let user = dir.join("user.toml");
+        let project = dir.join("project.toml");
+        fs::write(
+            &user,
+            "pattern = \"user\"\n\
+             [profile.base]\ndry_run = true\nverbose = 1\n",
+        )
+        .unwrap();
+        fs::write(
+            &project,
+            "input = [\"a.txt\"]\n\
+             [profile.ci]\ninherits = \"base\"\npattern = \"ci\"\n\
+             [profile.dev]\nverbose = 3\n",
+        )
+        .unwrap();
+        let project_arg = project.to_string_lossy().into_owned();
+        let layers = ConfigLayers::new("tool")
+            .with_file(&user)
+            .unwrap()
+            .with_file(&project)
+            .unwrap();
+        assert_eq!(layers.profiles(), ["base", "ci", "dev"]);
+
+        let ci = layers.clone().with_profile("ci").unwrap();
+        let no_args = matches(&[]);
+        let mut resolver = ci.resolver(&no_args);
+        assert_eq!(resolver.profile(), Some("ci".to_string()));
+        assert_eq!(resolver.string("pattern"), Ok(Some("ci".to_string())));
+        assert_eq!(resolver.source("pattern"), Source::File(project.clone()));
+        // Inherited from base, which only the user file defines
+        assert_eq!(resolver.flag("dry-run"), Ok(true));
+        assert_eq!(resolver.count("verbose"), Ok(1));
+        assert_eq!(resolver.source("verbose"), Source::File(user.clone()));
+        assert_eq!(resolver.paths("input"), Ok(Some(vec![dir.join("a.txt")])));
+        assert!(resolver.finish().is_ok());
+
+        // Without a profile the tables are ignored
+        let mut resolver = layers.resolver(&no_args);
+        assert_eq!(resolver.profile(), None);
+        assert_eq!(resolver.string("pattern"), Ok(Some("user".to_string())));
+        assert_eq!(resolver.flag("dry-run"), Ok(false));
+
+        // Selected by flag, else by variable
+        let flag_args = matches(&["--config", &project_arg, "--profile", "dev"]);
+        let dev = ConfigLayers::new("tool")
+            .with_vars(vars(&[("TOOL_PROFILE", "ci")]))
+            .with_files_for(&flag_args)
+            .unwrap();
+        let mut resolver = dev.resolver(&flag_args);
+        assert_eq!(resolver.profile(), Some("dev".to_string()));
+        assert_eq!(resolver.source("profile"), Source::CommandLine);
+        assert_eq!(resolver.count("verbose"), Ok(3));
+
+        let env_args = matches(&["--config", &project_arg]);
+        let from_env = ConfigLayers::new("tool")
+            .with_vars(vars(&[("TOOL_PROFILE", "dev")]))
+            .with_files_for(&env_args)
+            .unwrap();
+        let mut resolver = from_env.resolver(&env_args);
+        assert_eq!(resolver.profile(), Some("dev".to_string()));
+        assert_eq!(
+            resolver.source("profile"),
+            Source::Env("TOOL_PROFILE".to_string())
+        );
+
+        assert!(try_matches(&["--no-config", "--profile", "ci"]).is_err());
+    }
+
+    #[test]
+    fn test_profile_errors() {
+        let dir = scratch_dir("profile-errors");
+        let path = dir.join("config.toml");
+        fs::write(
+            &path,
+            "[profile.a]\ninherits = \"b\"\n\
+             [profile.b]\ninherits = \"a\"\n\
+             [profile.c]\ninherits = \"missing\"\n\
+             [profile.d]\npatern = \"typo\"\n",
+        )
+        .unwrap();
+        let layers = ConfigLayers::new("tool").with_file(&path).unwrap();
+
+        assert_eq!(
+            layers.clone().with_profile("x").unwrap_err(),
+            "unknown profile 'x' (defined: a, b, c, d)"
+        );
+        assert_eq!(
+            layers.clone().with_profile("a").unwrap_err(),
+            "profile 'a' inherits from itself: a -> b -> a"
+        );
+        assert_eq!(
+            layers.clone().with_profile("c").unwrap_err(),
+            "profile 'c' inherits unknown profile 'missing' (defined: a, b, c, d)"
+        );
+        assert_eq!(
+            ConfigLayers::new("tool").with_profile("x").unwrap_err(),
+            "unknown profile 'x' (no profiles are defined)"
+        );
+
+        let layers = layers.with_profile("d").unwrap().with_strict_keys(true);
+        let matches = matches(&[]);
+        let mut resolver = layers.resolver(&matches);
+        resolver.profile();
+        assert_eq!(resolver.string("pattern"), Ok(None));
+        assert_eq!(
+            resolver.finish(),
+            Err(format!(
+                "{}: unknown key 'patern' in [profile.d] (expected inherits or one of: pattern)",
+                path.display()
+            ))
+        );
+
+        // Profiles are unknown keys to resolvers that never ask for one
+        let mut resolver = layers.resolver(&matches);
+        assert_eq!(resolver.string("pattern"), Ok(None));
+        assert_eq!(
+            resolver.finish(),
+            Err(format!(
+                "{}: unknown key 'profile' (expected one of: pattern)",
+                path.display()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_older_standard_args() {
+        // The standard arguments of sw-cli 0.1, with a plain -v flag
+        let matches = Command::new("tool")
+            .disable_help_flag(true)
+            .disable_version_flag(true)
+            .arg(Arg::new("version").short('V').action(ArgAction::SetTrue))
+            .arg(Arg::new("help-short").short('h').action(ArgAction::SetTrue))
+            .arg(
+                Arg::new("help-long")
+                    .long("help")
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(Arg::new("verbose").short('v').action(ArgAction::SetTrue))
+            .arg(Arg::new("dry-run").short('n').action(ArgAction::SetTrue))
+            .try_get_matches_from(["tool", "-v", "-n"])
+            .unwrap();
+        let base = crate::builder::parse_base_config(&matches);
+        assert_eq!(base.verbose, 1);
+        assert!(base.dry_run);
+        assert!(!base.version && !base.print_config && !base.check_update);
+        assert_eq!(base.update_manifest, None);
+        assert_eq!(base.profile, None);
+
+        let layers = ConfigLayers::new("tool").with_files_for(&matches).unwrap();
+        let mut resolver = layers.resolver(&matches);
+        assert_eq!(resolver.string("pattern"), Ok(None));
+        assert_eq!(resolver.paths("input"), Ok(None));
+    }
+}
@@ -0,0 +1,380 @@
+pub mod layers;
+pub mod validate;
+
+pub use layers::{ConfigLayers, Source, Sources};
+
+use crate::output::{OutputFormat, Value};
+use crate::version::sbom::SbomFormat;
+use std::collections::BTreeMap;
+
+/// Help type requested by user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum HelpType {
+    #[default]
+    None,
+    Short, // -h
+    Long,  // --help
+}
+
+/// Standard flags common to all Software Wrighter CLIs
+///
+/// With the `serde` feature, the settings a config file can hold are
+/// serialized; missing keys deserialize to their defaults. The one-shot
+/// actions such as `--self-update` and the runtime `sources` and `env_vars`
+/// are skipped, so a saved preset never replays an action.
+// One bool per command-line switch
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct BaseConfig {
+    /// Number of `-v` flags (`-vvv` is 3)
+    pub verbose: u8,
+    /// Number of `-q` flags; conflicts with `-v`
+    pub quiet: u8,
+    pub dry_run: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub help: HelpType,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub version: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub license: bool,
+    /// `--licenses`: third-party dependency notices
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub licenses: bool,
+    /// `--sbom [FORMAT]`: print the software bill of materials
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub sbom: Option<SbomFormat>,
+    /// `--check-update [MANIFEST]`: look for newer releases
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub check_update: bool,
+    /// `--self-update [MANIFEST]`: install the newest release
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub self_update: bool,
+    /// `--rollback-update`: restore the executable replaced by the last update
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub rollback_update: bool,
+    /// `--print-config`: show the resolved configuration and where each value came from
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub print_config: bool,
+    /// Manifest given to `--check-update` or `--self-update`, overriding the built-in one
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub update_manifest: Option<String>,
+    pub format: OutputFormat,
+    /// `--profile NAME`: the `[profile.NAME]` config file tables in use
+    pub profile: Option<String>,
+    /// Where each value came from, for values that are not defaults
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub sources: Sources,
+    /// Environment variable that can set each value, by key
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub env_vars: BTreeMap<String, String>,
+}
+
+impl BaseConfig {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of `-v` flags, 0 when quiet
+    #[must_use]
+    pub fn verbosity(&self) -> u8 {
+        if self.quiet > 0 { 0 } else { self.verbose }
+    }
+
+    /// Signed verbosity: 0 by default, 1 per `-v`, -1 per `-q`
+    #[must_use]
+    pub fn verbosity_level(&self) -> i8 {
+        let level = i16::from(self.verbose) - i16::from(self.quiet);
+        i8::try_from(level).unwrap_or(if level < 0 { i8::MIN } else { i8::MAX })
+    }
+
+    #[must_use]
+    pub fn is_quiet(&self) -> bool {
+        self.verbosity_level() < 0
+    }
+
+    #[must_use]
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Where the value of `key` (e.g. `dry_run`) came from
+    #[must_use]
+    pub fn value_source(&self, key: &str) -> Source {
+        self.sources.get(key).cloned().unwrap_or_default()
+    }
+
+    /// Environment variable that can set `key`
+    #[must_use]
+    pub fn env_var(&self, key: &str) -> Option<&str> {
+        self.env_vars.get(key).map(String::as_str)
+    }
+
+    /// Whether a built-in command such as `--help`, `-V` or `--print-config`
+    /// handles this run, so the application's own values are not used
+    #[must_use]
+    pub fn runs_built_in(&self) -> bool {
+        self.help != HelpType::None
+            || self.version
+            || self.license
+            || self.licenses
+            || self.sbom.is_some()
+            || self.check_update
+            || self.self_update
+            || self.rollback_update
+            || self.print_config
+    }
+
+    /// The values that config files and the environment can set, by key
+    #[must_use]
+    pub fn settings(&self) -> Vec<(String, Value)> {
+        vec![
+            ("verbose".to_string(), self.verbose.into()),
+            ("quiet".to_string(), self.quiet.into()),
+            ("dry_run".to_string(), self.dry_run.into()),
+            ("format".to_string(), self.format.name().into()),
+        ]
+    }
+
+    #[must_use]
+    pub fn output_format(&self) -> OutputFormat {
+        self.format
+    }
+
+    #[must_use]
+    pub fn wants_help(&self) -> bool {
+        self.help != HelpType::None
+    }
+
+    #[must_use]
+    pub fn wants_short_help(&self) -> bool {
+        self.help == HelpType::Short
+    }
+
+    #[must_use]
+    pub fn wants_long_help(&self) -> bool {
+        self.help == HelpType::Long
+    }
+}
+
+/// Trait that all CLI configs must implement
+pub trait CliConfig {
+    fn base(&self) -> &BaseConfig;
+
+    fn wants_help(&self) -> bool {
+        self.base().wants_help()
+    }
+
+    fn wants_short_help(&self) -> bool {
+        self.base().wants_short_help()
+    }
+
+    fn wants_long_help(&self) -> bool {
+        self.base().wants_long_help()
+    }
+
+    fn wants_version(&self) -> bool {
+        self.base().version
+    }
+
+    fn wants_license(&self) -> bool {
+        self.base().license
+    }
+
+    fn wants_licenses(&self) -> bool {
+        self.base().licenses
+    }
+
+    fn sbom_format(&self) -> Option<SbomFormat> {
+        self.base().sbom
+    }
+
+    fn wants_update_check(&self) -> bool {
+        self.base().check_update
+    }
+
+    fn wants_self_update(&self) -> bool {
+        self.base().self_update
+    }
+
+    fn wants_rollback_update(&self) -> bool {
+        self.base().rollback_update
+    }
+
+    fn update_manifest(&self) -> Option<&str> {
+        self.base().update_manifest.as_deref()
+    }
+
+    fn profile(&self) -> Option<&str> {
+        self.base().profile.as_deref()
+    }
+
+    fn wants_print_config(&self) -> bool {
+        self.base().print_config
+    }
+
+    /// Every configurable value by key: the `BaseConfig` settings, then the
+    /// application's own. `cli_app!` configs include their fields.
+    fn settings(&self) -> Vec<(String, Value)> {
+        self.base().settings()
+    }
+
+    /// Check the application's values once every layer is resolved.
+    /// `cli_app!` configs check the validation attributes of their fields.
+    ///
+    /// # Errors
+    /// Returns a message naming the value and where it came from.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn verbosity(&self) -> u8 {
+        self.base().verbosity()
+    }
+
+    fn verbosity_level(&self) -> i8 {
+        self.base().verbosity_level()
+    }
+
+    fn is_quiet(&self) -> bool {
+        self.base().is_quiet()
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.base().is_dry_run()
+    }
+
+    fn value_source(&self, key: &str) -> Source {
+        self.base().value_source(key)
+    }
+
+    fn env_var(&self, key: &str) -> Option<&str> {
+        self.base().env_var(key)
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        self.base().output_format()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any;
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::cli_app;
+    use std::path::PathBuf;
+
+    cli_app! {
+        name: "preset-test",
+        about: "Preset test",
+        config: PresetConfig,
+        fields: {
+            input: Option<Vec<PathBuf>>, short = 'i', long = "input", help = "Input files", action = Append,
+            output: Option<PathBuf>, short = 'o', long = "output", help = "Output file",
+            tags: Option<Vec<String>>, long = "tag", help = "Tags",
+            count: bool, long = "count", help = "Count lines",
+            width: usize, long = "width", help = "Wrap width", default = 80,
+            exclude: Vec<String>, long = "exclude", help = "Patterns to skip", default = ["*.bak"],
+        }
+    }
+
+    #[test]
+    fn test_base_config_round_trip() {
+        let mut base = BaseConfig::new();
+        base.verbose = 2;
+        base.format = OutputFormat::Json;
+        base.profile = Some("ci".to_string());
+        base.sources.insert(
+            "verbose".to_string(),
+            Source::File(PathBuf::from("/etc/tool/config.toml")),
+        );
+        base.env_vars
+            .insert("dry_run".to_string(), "TOOL_DRY_RUN".to_string());
+        // One-shot actions are never saved
+        base.sbom = Some(SbomFormat::Spdx);
+        base.help = HelpType::Long;
+        base.self_update = true;
+        base.update_manifest = Some("https://example.com/releases.json".to_string());
+
+        let json = serde_json::to_string(&base).unwrap();
+        assert_eq!(
+            json,
+            r#"{"verbose":2,"quiet":0,"dry_run":false,"format":"json","profile":"ci"}"#
+        );
+
+        let back: BaseConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+        assert!(!back.self_update && back.update_manifest.is_none());
+        assert_eq!(back.value_source("verbose"), Source::Default);
+
+        // Nor replayed from a hand-edited preset
+        let edited: BaseConfig =
+            serde_json::from_str(r#"{"self_update":true,"print_config":true}"#).unwrap();
+        assert!(!edited.self_update && !edited.print_config);
+
+        // Presets may leave keys out
+        let partial: BaseConfig = serde_json::from_str(r#"{"dry_run":true}"#).unwrap();
+        assert!(partial.dry_run);
+        assert_eq!(partial.format, OutputFormat::Text);
+    }
+
+    #[test]
+    fn test_generated_config_round_trip() {
+        let config = parse_config(&build_cli().get_matches_from([
+            "preset-test",
+            "-i",
+            "a.txt",
+            "-i",
+            "/var/log/b.log",
+            "-o",
+            "out dir/result.txt",
+            "--tag",
+            "x",
+            "--tag",
+            "y",
+            "--no-config",
+        ]));
+
+        let json = serde_json::to_string(&config).unwrap();
+        let back: PresetConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            back.input,
+            Some(vec![
+                PathBuf::from("a.txt"),
+                PathBuf::from("/var/log/b.log")
+            ])
+        );
+        assert_eq!(back.output, Some(PathBuf::from("out dir/result.txt")));
+        assert_eq!(
+            back.tags.as_deref(),
+            Some(&["x".to_string(), "y".to_string()][..])
+        );
+        assert!(!back.count);
+        assert_eq!(back.width, 80);
+        assert_eq!(back.exclude, ["*.bak"]);
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+
+        let preset: PresetConfig =
+            serde_json::from_str(r#"{"output":"o.txt","count":true}"#).unwrap();
+        assert!(preset.count);
+        assert_eq!(preset.input, None);
+        assert_eq!(preset.base.verbose, 0);
+
+        // Fields a preset leaves out take their defaults
+        let preset: PresetConfig = serde_json::from_str(r#"{"output":"o.txt"}"#).unwrap();
+        assert!(!preset.count);
+        assert_eq!(preset.width, 80);
+        assert_eq!(preset.exclude, ["*.bak"]);
+    }
+}
@@ -0,0 +1,353 @@
+//! Checks behind the validation attributes of `cli_app!` fields.
+//!
+//! Each check returns a message without the key or source; the generated
+//! clap value parsers and `CliConfig::validate` add those.
+
+use std::fmt::Display;
+use std::ops::RangeBounds;
+use std::path::Path;
+
+/// `range = 1..=100`: `text` is the range as written in the macro
+///
+/// # Errors
+/// Returns a message if `value` is outside `range`.
+pub fn in_range<T: PartialOrd + Display>(
+    value: &T,
+    range: &impl RangeBounds<T>,
+    text: &str,
+) -> Result<(), String> {
+    if range.contains(value) {
+        Ok(())
+    } else {
+        Err(format!("{value} is not in {text}"))
+    }
+}
+
+/// `regex = "..."`: the whole value must match `pattern`
+///
+/// # Errors
+/// Returns a message if `value` does not match or `pattern` is invalid.
+#[cfg(feature = "regex")]
+pub fn matches_regex(value: &str, pattern: &str) -> Result<(), String> {
+    let regex = regex::Regex::new(&format!("^(?:{pattern})$"))
+        .map_err(|e| format!("invalid regex '{pattern}': {e}"))?;
+    if regex.is_match(value) {
+        Ok(())
+    } else {
+        Err(format!("'{value}' does not match '{pattern}'"))
+    }
+}
+
+/// `exists`: the path must exist
+///
+/// # Errors
+/// Returns a message if nothing exists at `path`.
+pub fn exists(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        Ok(())
+    } else {
+        Err(format!("{} does not exist", path.display()))
+    }
+}
+
+/// Message for a `required` value that no layer set
+#[must_use]
+pub fn missing(key: &str, long: &str, env_var: Option<&str>) -> String {
+    let env = env_var
+        .map(|var| format!(", set {var}"))
+        .unwrap_or_default();
+    format!("'{key}' is required: use --{long}{env} or set it in a config file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli_app;
+    use crate::config::CliConfig;
+    use clap::error::ErrorKind;
+    use std::fs;
+    use std::path::PathBuf;
+
+    cli_app! {
+        name: "validate-test",
+        about: "Validation test",
+        config: CheckedConfig,
+        env_prefix: "",
+        strict_config: true,
+        fields: {
+            repeat: Option<usize>, long = "repeat", help = "Repeat", range = 1..=100,
+            ratio: Option<f64>, long = "ratio", help = "Ratio", range = 0.0..1.0,
+            pattern: Option<String>, short = 'p', long = "pattern", help = "Pattern", required,
+            input: Option<Vec<PathBuf>>, short = 'i', long = "input", help = "Inputs", exists,
+            output: Option<PathBuf>, long = "output", help = "Output", requires = "input",
+            count: bool, long = "count", help = "Count", conflicts_with = "reverse",
+            reverse: bool, long = "reverse", help = "Reverse",
+        }
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("sw-cli-validate-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn parse(args: &[&str]) -> Result<CheckedConfig, clap::Error> {
+        let matches = build_cli().try_get_matches_from(
+            ["validate-test", "--no-config"]
+                .into_iter()
+                .chain(args.iter().copied()),
+        )?;
+        try_parse_config(&matches)
+    }
+
+    fn error(args: &[&str]) -> (ErrorKind, String) {
+        let err = parse(args).unwrap_err();
+        (err.kind(), err.to_string())
+    }
+
+    #[test]
+    fn test_checks() {
+        assert_eq!(in_range(&5, &(1..=100), "1..=100"), Ok(()));
+        assert_eq!(
+            in_range(&0, &(1..=100), "1..=100"),
+            Err("0 is not in 1..=100".to_string())
+        );
+        assert!(in_range(&1.0, &(0.0..1.0), "0.0..1.0").is_err());
+        assert!(exists(Path::new(".")).is_ok());
+        assert_eq!(
+            exists(Path::new("/no/such/file")),
+            Err("/no/such/file does not exist".to_string())
+        );
+        assert_eq!(
+            missing("pattern", "pattern", Some("APP_PATTERN")),
+            "'pattern' is required: use --pattern, set APP_PATTERN or set it in a config file"
+        );
+    }
+
+    #[test]
+    fn test_command_line() {
+        let config = parse(&["-p", "x", "--repeat", " 7", "--ratio", "0.5"]).unwrap();
+        assert_eq!(config.repeat, Some(7));
+        assert_eq!(config.ratio, Some(0.5));
+
+        let (kind, message) = error(&["-p", "x", "--repeat", "abc"]);
+        assert_eq!(kind, ErrorKind::ValueValidation);
+        assert!(
+            message.contains("invalid value 'abc' for '--repeat <repeat>': invalid digit"),
+            "{message}"
+        );
+        let (kind, message) = error(&["-p", "x", "--repeat", "0"]);
+        assert_eq!(kind, ErrorKind::ValueValidation);
+        assert!(message.contains("0 is not in 1..=100"), "{message}");
+        let (_, message) = error(&["-p", "x", "-i", "/no/such/file"]);
+        assert!(
+            message.contains("/no/such/file does not exist"),
+            "{message}"
+        );
+
+        let (kind, _) = error(&["-p", "x", "--count", "--reverse"]);
+        assert_eq!(kind, ErrorKind::ArgumentConflict);
+    }
+
+    #[test]
+    fn test_resolved_values() {
+        let (kind, message) = error(&[]);
+        assert_eq!(kind, ErrorKind::InvalidValue);
+        assert!(
+            message.contains("'pattern' is required: use --pattern or set it in a config file"),
+            "{message}"
+        );
+        // Built-in commands run without the application's values
+        assert!(parse(&["--help"]).is_ok());
+        assert!(parse(&["--print-config"]).is_ok());
+
+        let (_, message) = error(&["-p", "x", "--output", "out.txt"]);
+        assert!(
+            message.contains("'output' (command line) requires 'input'"),
+            "{message}"
+        );
+
+        let dir = scratch_dir("files");
+        let path = dir.join("config.toml");
+        let config_arg = path.to_string_lossy().into_owned();
+        let from_file = |toml: &str, args: &[&str]| {
+            fs::write(&path, toml).unwrap();
+            let matches = build_cli()
+                .try_get_matches_from(
+                    ["validate-test", "--config", &config_arg]
+                        .into_iter()
+                        .chain(args.iter().copied()),
+                )
+                .unwrap();
+            try_parse_config(&matches)
+        };
+
+        let config = from_file("pattern = \"x\"\nrepeat = 3\n", &[]).unwrap();
+        assert_eq!(config.repeat, Some(3));
+        assert!(config.validate().is_ok());
+
+        let err = from_file("pattern = \"x\"\nrepeat = \"many\"\n", &[]).unwrap_err();
+        assert!(
+            err.to_string().contains(&format!(
+                "{}: invalid value 'many' for 'repeat': invalid digit found in string",
+                path.display()
+            )),
+            "{err}"
+        );
+        let err = from_file("pattern = \"x\"\nrepeat = 500\n", &[]).unwrap_err();
+        assert!(
+            err.to_string().contains(&format!(
+                "{}: invalid value for 'repeat': 500 is not in 1..=100",
+                path.display()
+            )),
+            "{err}"
+        );
+        let err = from_file("pattern = \"x\"\ninput = [\"missing.txt\"]\n", &[]).unwrap_err();
+        assert!(
+            err.to_string().contains(&format!(
+                "{} does not exist",
+                dir.join("missing.txt").display()
+            )),
+            "{err}"
+        );
+        let err = from_file("pattern = \"x\"\nreverse = true\n", &["--count"]).unwrap_err();
+        assert!(
+            err.to_string().contains(&format!(
+                "'count' (command line) cannot be used with 'reverse' ({})",
+                path.display()
+            )),
+            "{err}"
+        );
+        let err = from_file("pattern = \"x\"\nrepaet = 3\n", &[]).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains(&format!("{}: unknown key 'repaet'", path.display())),
+            "{err}"
+        );
+        fs::write(dir.join("in.txt"), "").unwrap();
+        assert!(from_file("input = [\"in.txt\"]\n", &["-p", "x", "--output", "o"]).is_ok());
+    }
+
+    mod cli_only {
+        use super::*;
+
+        cli_app! {
+            name: "cli-only-test",
+            about: "Command-line only test",
+            config: JobConfig,
+            strict_config: true,
+            fields: {
+                job: Option<String>, long = "job", help = "Job name", required, cli_only,
+                output: Option<PathBuf>, long = "output", help = "Output", requires = "input", cli_only,
+                input: Option<PathBuf>, long = "input", help = "Input", cli_only,
+                label: Option<String>, long = "label", help = "Label", requires = "input",
+            }
+        }
+
+        fn matches(args: &[&str]) -> Result<clap::ArgMatches, clap::Error> {
+            build_cli().try_get_matches_from(
+                ["cli-only-test", "--no-config"]
+                    .into_iter()
+                    .chain(args.iter().copied()),
+            )
+        }
+
+        #[test]
+        fn test_checked_by_clap() {
+            let err = matches(&[]).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+            assert!(err.to_string().contains("--job <job>"), "{err}");
+            let err = matches(&["--job", "x", "--output", "o"]).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::MissingRequiredArgument);
+            assert!(err.to_string().contains("--input <input>"), "{err}");
+            assert!(matches(&["--job", "x", "--output", "o", "--input", "i"]).is_ok());
+            // Built-in commands run without them
+            assert!(matches(&["--help"]).is_ok());
+            assert!(matches(&["--print-config"]).is_ok());
+
+            // `label` can come from another layer, so `validate()` checks it
+            let config = try_parse_config(&matches(&["--job", "x", "--label", "l"]).unwrap());
+            assert!(
+                config
+                    .unwrap_err()
+                    .to_string()
+                    .contains("'label' (command line) requires 'input'")
+            );
+        }
+
+        #[test]
+        fn test_not_layered() {
+            let dir = scratch_dir("cli-only");
+            let path = dir.join("config.toml");
+            fs::write(&path, "job = \"from file\"\n").unwrap();
+            let config_arg = path.to_string_lossy().into_owned();
+            let from_file = build_cli()
+                .try_get_matches_from(["cli-only-test", "--config", &config_arg, "--job", "x"])
+                .unwrap();
+            let err = try_parse_config(&from_file).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains(&format!("{}: unknown key 'job'", path.display())),
+                "{err}"
+            );
+
+            let matches = matches(&["--job", "x"]).unwrap();
+            let config = try_parse_config(&matches).unwrap();
+            assert_eq!(config.env_var("job"), None);
+            assert_eq!(config.env_var("label"), Some("CLI_ONLY_TEST_LABEL"));
+        }
+    }
+
+    #[cfg(feature = "regex")]
+    mod regex_rules {
+        use super::*;
+
+        cli_app! {
+            name: "regex-test",
+            about: "Regex test",
+            config: RegexConfig,
+            fields: {
+                name: Option<String>, long = "name", help = "Name", regex = "[a-z][a-z0-9-]*",
+            }
+        }
+
+        #[test]
+        fn test_regex() {
+            assert_eq!(matches_regex("abc-1", "[a-z][a-z0-9-]*"), Ok(()));
+            assert_eq!(
+                matches_regex("x abc", "[a-z]+"),
+                Err("'x abc' does not match '[a-z]+'".to_string())
+            );
+            assert!(
+                matches_regex("x", "(")
+                    .unwrap_err()
+                    .starts_with("invalid regex '(': ")
+            );
+
+            let parse = |args: &[&str]| {
+                let matches = build_cli()
+                    .try_get_matches_from(
+                        ["regex-test", "--no-config"]
+                            .into_iter()
+                            .chain(args.iter().copied()),
+                    )
+                    .unwrap_or_else(|e| panic!("{e}"));
+                try_parse_config(&matches)
+            };
+            assert_eq!(
+                parse(&["--name", "web-1"]).unwrap().name.as_deref(),
+                Some("web-1")
+            );
+            let err = build_cli()
+                .try_get_matches_from(["regex-test", "--name", "Web"])
+                .unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("'Web' does not match '[a-z][a-z0-9-]*'"),
+                "{err}"
+            );
+        }
+    }
+}
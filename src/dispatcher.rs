@@ -1,32 +1,82 @@
 use crate::command::Command;
-use crate::commands::{HelpCommand, VersionCommand};
+use crate::commands::{
+    HelpCommand, LicenseCommand, NoticesCommand, PrintConfigCommand, SbomCommand,
+    SelfUpdateCommand, UpdateCheckCommand, VersionCommand,
+};
 use crate::config::CliConfig;
+use crate::version::VersionInfo;
 use std::error::Error;
 
 pub struct Dispatcher {
+    version: VersionInfo,
+    components: Vec<VersionInfo>,
+    short_help: String,
+    long_help: String,
     commands: Vec<Box<dyn Command>>,
 }
 
 impl Dispatcher {
-    /// Create a new Dispatcher with `VersionCommand` and `HelpCommand` automatically registered.
+    /// Create a new Dispatcher with `VersionCommand`, `HelpCommand`, `LicenseCommand`,
+    /// `NoticesCommand`, `SbomCommand`, `UpdateCheckCommand`, `SelfUpdateCommand`
+    /// and `PrintConfigCommand` automatically registered. The version commands report `version`,
+    /// normally `sw_cli::build_info!()` of the application; `dispatch!()`
+    /// passes it automatically.
     ///
     /// # Arguments
+    /// * `version` - Build information reported by `-V`, `--license`, `--sbom`, ...
     /// * `short_help` - The short help text to display when -h is used
     /// * `long_help` - The long help text to display when --help is used
     #[must_use]
-    pub fn new(short_help: String, long_help: String) -> Self {
-        let mut dispatcher = Self {
+    pub fn with_version(version: VersionInfo, short_help: String, long_help: String) -> Self {
+        Self {
+            version,
+            components: Vec::new(),
+            short_help,
+            long_help,
             commands: Vec::new(),
-        };
+        }
+    }
+
+    /// Add libraries or plugins to the component versions table shown by
+    /// `-V -v`. Each component publishes its own `sw_cli::build_info!()`.
+    ///
+    /// # Arguments
+    /// * `components` - Build information of each component, in display order
+    #[must_use]
+    pub fn with_components(mut self, components: impl IntoIterator<Item = VersionInfo>) -> Self {
+        self.components.extend(components);
+        self
+    }
 
-        // Auto-register VersionCommand (priority 0) and HelpCommand (priority 1)
-        dispatcher.commands.push(Box::new(VersionCommand));
-        dispatcher
-            .commands
-            .push(Box::new(HelpCommand::new(short_help, long_help)));
-        dispatcher.commands.sort_by_key(|c| c.priority());
+    /// The built-in commands: `VersionCommand` (priority 0), `HelpCommand` (priority 1),
+    /// `LicenseCommand` (priority 2), `NoticesCommand` (priority 3),
+    /// `SbomCommand` (priority 4), `UpdateCheckCommand` (priority 5),
+    /// `SelfUpdateCommand` (priority 6) and `PrintConfigCommand` (priority 7)
+    fn built_in_commands(&self) -> Vec<Box<dyn Command>> {
+        let version = self.version;
+        vec![
+            Box::new(VersionCommand::new(version).with_components(self.components.clone())),
+            Box::new(HelpCommand::new(
+                self.short_help.clone(),
+                self.long_help.clone(),
+            )),
+            Box::new(LicenseCommand::new(version)),
+            Box::new(NoticesCommand::new(version)),
+            Box::new(SbomCommand::new(version)),
+            Box::new(UpdateCheckCommand::new(version)),
+            Box::new(SelfUpdateCommand::new(version)),
+            Box::new(PrintConfigCommand::new(version)),
+        ]
+    }
 
-        dispatcher
+    /// Create a new Dispatcher whose version commands report sw-cli's own
+    /// build information.
+    #[deprecated(
+        note = "reports the build information of sw-cli itself; use `dispatch!()` or `Dispatcher::with_version(sw_cli::build_info!(), ..)`"
+    )]
+    #[must_use]
+    pub fn new(short_help: String, long_help: String) -> Self {
+        Self::with_version(crate::build_info!(), short_help, long_help)
     }
 
     /// Register a command with the dispatcher.
@@ -36,7 +86,6 @@ impl Dispatcher {
     #[must_use]
     pub fn register<C: Command + 'static>(mut self, command: C) -> Self {
         self.commands.push(Box::new(command));
-        self.commands.sort_by_key(|c| c.priority());
         self
     }
 
@@ -45,7 +94,15 @@ impl Dispatcher {
     /// # Errors
     /// Returns an error if no command can handle the request or if command execution fails.
     pub fn dispatch(&self, config: &dyn CliConfig) -> Result<(), Box<dyn Error>> {
-        for command in &self.commands {
+        let built_in = self.built_in_commands();
+        // Stable sort: built-ins first, then registration order, within a priority
+        let mut commands: Vec<&dyn Command> = built_in
+            .iter()
+            .chain(&self.commands)
+            .map(AsRef::as_ref)
+            .collect();
+        commands.sort_by_key(|c| c.priority());
+        for command in commands {
             if command.can_handle(config) {
                 return command.execute(config);
             }
@@ -1,19 +1,32 @@
+// Lets `::sw_cli::...` paths in macro expansions resolve inside this crate too
+extern crate self as sw_cli;
+
 pub mod builder;
 pub mod command;
 pub mod commands;
 pub mod config;
 pub mod dispatcher;
+pub mod output;
+pub mod update;
 pub mod version;
 
+// Used by the Serialize/Deserialize derives that `cli_app!` generates
+#[cfg(feature = "serde")]
+pub use serde;
+
 // Re-export macros from sw-cli-macros for convenient usage
 pub use sw_cli_macros::{
-    cli_app, cli_command, create_version, define_build_info, define_help_info, dispatch, long_help,
-    short_help, version,
+    build_info, cli_app, cli_command, create_version, define_build_info, define_help_info,
+    dispatch, long_help, short_help, version, web_footer,
 };
 
 // Re-export commonly used types
 pub use command::Command;
-pub use commands::{HelpCommand, VersionCommand};
+pub use commands::{
+    HelpCommand, LicenseCommand, NoticesCommand, PrintConfigCommand, SbomCommand,
+    SelfUpdateCommand, UpdateCheckCommand, VersionCommand,
+};
 pub use config::{BaseConfig, CliConfig, HelpType};
 pub use dispatcher::Dispatcher;
+pub use output::OutputFormat;
 pub use version::check_version_flag;
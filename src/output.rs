@@ -0,0 +1,1589 @@
+use std::fmt;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Output format selected with the standard `--format` flag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Names accepted by `--format`, in declaration order
+    pub const NAMES: [&'static str; 4] = ["text", "json", "toml", "yaml"];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Json => "json",
+            Self::Toml => "toml",
+            Self::Yaml => "yaml",
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "toml" => Ok(Self::Toml),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            _ => Err(format!(
+                "unknown output format '{s}' (expected one of: {})",
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+/// Structured value rendered by the machine-readable output formats.
+///
+/// Maps keep insertion order so field order in the output is stable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    List(Vec<Value>),
+    Map(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Start an empty map; use [`Value::with`] to add entries.
+    #[must_use]
+    pub fn map() -> Self {
+        Self::Map(Vec::new())
+    }
+
+    /// Append an entry to a map value. Has no effect on other variants.
+    #[must_use]
+    pub fn with(mut self, key: &str, value: impl Into<Value>) -> Self {
+        if let Self::Map(entries) = &mut self {
+            entries.push((key.to_string(), value.into()));
+        }
+        self
+    }
+
+    /// Entry of a map value
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Self::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Items of a list value (empty for other variants)
+    #[must_use]
+    pub fn as_list(&self) -> &[Value] {
+        match self {
+            Self::List(items) => items,
+            _ => &[],
+        }
+    }
+
+    /// Parse a JSON document.
+    ///
+    /// # Errors
+    /// Returns a message with the byte offset of the first syntax error.
+    pub fn from_json(text: &str) -> Result<Value, String> {
+        let mut parser = JsonParser {
+            bytes: text.as_bytes(),
+            text,
+            pos: 0,
+        };
+        let value = parser.value(0)?;
+        parser.skip_whitespace();
+        if parser.pos < parser.bytes.len() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(value)
+    }
+
+    /// Parse a TOML document into a map.
+    ///
+    /// Covers the parts of TOML used by configuration files: tables, arrays
+    /// of tables, dotted and quoted keys, all string forms, integers, floats,
+    /// booleans, arrays and inline tables. Dates and times are kept as
+    /// strings.
+    ///
+    /// # Errors
+    /// Returns a message with the line of the first syntax error or
+    /// duplicate key.
+    pub fn from_toml(text: &str) -> Result<Value, String> {
+        let mut parser = TomlParser {
+            bytes: text.as_bytes(),
+            text,
+            pos: 0,
+        };
+        parser.document().map(Value::Map)
+    }
+
+    /// Render the value in the requested format.
+    ///
+    /// `Text` has no generic representation and falls back to YAML, which is
+    /// the most readable of the structured formats.
+    #[must_use]
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Json => self.to_json(),
+            OutputFormat::Toml => self.to_toml(),
+            OutputFormat::Text | OutputFormat::Yaml => self.to_yaml(),
+        }
+    }
+
+    /// Render as pretty-printed JSON (two-space indent, trailing newline).
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        write_json(&mut out, self, 0);
+        out.push('\n');
+        out
+    }
+
+    /// Render as a TOML document.
+    ///
+    /// Scalars that are not inside a map are emitted under a `value` key,
+    /// since a TOML document must be a table. `Null` entries are omitted.
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        let mut out = String::new();
+        match self {
+            Self::Map(entries) => write_toml_table(&mut out, &[], entries),
+            other => write_toml_table(&mut out, &[], &[("value".to_string(), other.clone())]),
+        }
+        out
+    }
+
+    /// Render as a YAML document.
+    #[must_use]
+    pub fn to_yaml(&self) -> String {
+        let mut out = String::new();
+        match self {
+            Self::Map(entries) if !entries.is_empty() => write_yaml_map(&mut out, entries, 0),
+            Self::List(items) if !items.is_empty() => write_yaml_list(&mut out, items, 0),
+            scalar => {
+                out.push_str(&yaml_scalar(scalar));
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        Self::Float(f64::from(value))
+    }
+}
+
+macro_rules! int_values {
+    ($($ty:ty),*) => {$(
+        impl From<$ty> for Value {
+            fn from(value: $ty) -> Self {
+                Self::Int(i64::from(value))
+            }
+        }
+    )*};
+}
+
+int_values!(i8, i16, i32, u8, u16, u32);
+
+macro_rules! wide_int_values {
+    ($($ty:ty),*) => {$(
+        /// Kept as text when it does not fit in an `i64`
+        impl From<$ty> for Value {
+            fn from(value: $ty) -> Self {
+                i64::try_from(value).map_or_else(|_| Self::Str(value.to_string()), Self::Int)
+            }
+        }
+    )*};
+}
+
+wide_int_values!(isize, u64, usize);
+
+impl From<&Path> for Value {
+    fn from(value: &Path) -> Self {
+        Self::Str(value.to_string_lossy().into_owned())
+    }
+}
+
+impl From<PathBuf> for Value {
+    fn from(value: PathBuf) -> Self {
+        Self::from(value.as_path())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::Str(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(value: Vec<T>) -> Self {
+        Self::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Quote a string using the escapes shared by JSON, TOML basic strings and
+/// YAML double-quoted scalars.
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn indent(out: &mut String, level: usize) {
+    for _ in 0..level {
+        out.push_str("  ");
+    }
+}
+
+fn write_json(out: &mut String, value: &Value, level: usize) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Int(i) => out.push_str(&i.to_string()),
+        // JSON has no NaN or infinity
+        Value::Float(x) if !x.is_finite() => out.push_str("null"),
+        Value::Float(x) => {
+            let _ = write!(out, "{x:?}");
+        }
+        Value::Str(s) => out.push_str(&quote(s)),
+        Value::List(items) if items.is_empty() => out.push_str("[]"),
+        Value::List(items) => {
+            out.push_str("[\n");
+            for (i, item) in items.iter().enumerate() {
+                indent(out, level + 1);
+                write_json(out, item, level + 1);
+                out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+            }
+            indent(out, level);
+            out.push(']');
+        }
+        Value::Map(entries) if entries.is_empty() => out.push_str("{}"),
+        Value::Map(entries) => {
+            out.push_str("{\n");
+            for (i, (key, item)) in entries.iter().enumerate() {
+                indent(out, level + 1);
+                out.push_str(&quote(key));
+                out.push_str(": ");
+                write_json(out, item, level + 1);
+                out.push_str(if i + 1 < entries.len() { ",\n" } else { "\n" });
+            }
+            indent(out, level);
+            out.push('}');
+        }
+    }
+}
+
+pub(crate) fn toml_key(key: &str) -> String {
+    if is_bare_key(key) {
+        key.to_string()
+    } else {
+        quote(key)
+    }
+}
+
+pub(crate) fn toml_inline(value: &Value) -> String {
+    match value {
+        Value::Null => "\"\"".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Int(i) => i.to_string(),
+        Value::Float(x) => toml_float(*x),
+        Value::Str(s) => quote(s),
+        Value::List(items) => {
+            let items: Vec<String> = items
+                .iter()
+                .filter(|v| **v != Value::Null)
+                .map(toml_inline)
+                .collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Map(entries) => {
+            let entries: Vec<String> = entries
+                .iter()
+                .filter(|(_, v)| *v != Value::Null)
+                .map(|(k, v)| format!("{} = {}", toml_key(k), toml_inline(v)))
+                .collect();
+            if entries.is_empty() {
+                "{}".to_string()
+            } else {
+                format!("{{ {} }}", entries.join(", "))
+            }
+        }
+    }
+}
+
+fn toml_float(x: f64) -> String {
+    if x.is_nan() {
+        "nan".to_string()
+    } else if x.is_infinite() {
+        if x > 0.0 { "inf" } else { "-inf" }.to_string()
+    } else {
+        format!("{x:?}")
+    }
+}
+
+fn is_table_array(value: &Value) -> bool {
+    matches!(value, Value::List(items)
+        if !items.is_empty() && items.iter().all(|v| matches!(v, Value::Map(_))))
+}
+
+fn write_toml_table(out: &mut String, path: &[String], entries: &[(String, Value)]) {
+    for (key, value) in entries {
+        match value {
+            Value::Null | Value::Map(_) => {}
+            v if is_table_array(v) => {}
+            v => {
+                let _ = writeln!(out, "{} = {}", toml_key(key), toml_inline(v));
+            }
+        }
+    }
+
+    for (key, value) in entries {
+        let mut child = path.to_vec();
+        child.push(toml_key(key));
+        match value {
+            Value::Map(sub) => {
+                if !out.is_empty() {
+                    out.push('\n');
+                }
+                let _ = writeln!(out, "[{}]", child.join("."));
+                write_toml_table(out, &child, sub);
+            }
+            Value::List(items) if is_table_array(value) => {
+                for item in items {
+                    if let Value::Map(sub) = item {
+                        if !out.is_empty() {
+                            out.push('\n');
+                        }
+                        let _ = writeln!(out, "[[{}]]", child.join("."));
+                        write_toml_table(out, &child, sub);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn yaml_key(key: &str) -> String {
+    if is_bare_key(key) {
+        key.to_string()
+    } else {
+        quote(key)
+    }
+}
+
+fn yaml_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Int(i) => i.to_string(),
+        Value::Float(x) => yaml_float(*x),
+        Value::Str(s) => quote(s),
+        Value::List(_) => "[]".to_string(),
+        Value::Map(_) => "{}".to_string(),
+    }
+}
+
+fn yaml_float(x: f64) -> String {
+    if x.is_nan() {
+        ".nan".to_string()
+    } else if x.is_infinite() {
+        if x > 0.0 { ".inf" } else { "-.inf" }.to_string()
+    } else {
+        format!("{x:?}")
+    }
+}
+
+fn is_yaml_block(value: &Value) -> bool {
+    match value {
+        Value::List(items) => !items.is_empty(),
+        Value::Map(entries) => !entries.is_empty(),
+        _ => false,
+    }
+}
+
+fn write_yaml_entry(out: &mut String, key: &str, value: &Value, level: usize) {
+    out.push_str(&yaml_key(key));
+    if is_yaml_block(value) {
+        out.push_str(":\n");
+        match value {
+            Value::Map(entries) => write_yaml_map(out, entries, level + 1),
+            Value::List(items) => write_yaml_list(out, items, level + 1),
+            _ => {}
+        }
+    } else {
+        let _ = writeln!(out, ": {}", yaml_scalar(value));
+    }
+}
+
+fn write_yaml_map(out: &mut String, entries: &[(String, Value)], level: usize) {
+    for (key, value) in entries {
+        indent(out, level);
+        write_yaml_entry(out, key, value, level);
+    }
+}
+
+fn write_yaml_list(out: &mut String, items: &[Value], level: usize) {
+    for item in items {
+        indent(out, level);
+        out.push_str("- ");
+        match item {
+            Value::Map(entries) if !entries.is_empty() => {
+                // First entry shares the dash line, the rest align under it
+                let (key, value) = &entries[0];
+                write_yaml_entry(out, key, value, level + 1);
+                write_yaml_map(out, &entries[1..], level + 1);
+            }
+            Value::List(items) if !items.is_empty() => {
+                out.push('\n');
+                write_yaml_list(out, items, level + 1);
+            }
+            scalar => {
+                out.push_str(&yaml_scalar(scalar));
+                out.push('\n');
+            }
+        }
+    }
+}
+
+/// Length of the unsigned decimal number at the start of `bytes`, using
+/// the JSON grammar that TOML shares: no leading zeros, and digits on both
+/// sides of a decimal point and after an exponent
+fn decimal_end(bytes: &[u8]) -> Option<usize> {
+    let digits = |from: usize| {
+        bytes[from..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count()
+    };
+    let mut pos = match bytes.first() {
+        Some(b'0') => 1,
+        Some(b'1'..=b'9') => digits(0),
+        _ => return None,
+    };
+    if bytes.get(pos) == Some(&b'.') {
+        let fraction = digits(pos + 1);
+        if fraction == 0 {
+            return None;
+        }
+        pos += 1 + fraction;
+    }
+    if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+        pos += 1;
+        if matches!(bytes.get(pos), Some(b'+' | b'-')) {
+            pos += 1;
+        }
+        let exponent = digits(pos);
+        if exponent == 0 {
+            return None;
+        }
+        pos += exponent;
+    }
+    Some(pos)
+}
+
+/// Nesting limit, so hostile input cannot exhaust the stack
+const MAX_JSON_DEPTH: usize = 128;
+
+struct JsonParser<'a> {
+    bytes: &'a [u8],
+    text: &'a str,
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self, message: &str) -> String {
+        format!("invalid JSON at byte {}: {message}", self.pos)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.bytes.get(self.pos), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, literal: &str, value: Value) -> Result<Value, String> {
+        if self.text[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error("unexpected token"))
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, String> {
+        if depth > MAX_JSON_DEPTH {
+            return Err(self.error("nested too deeply"));
+        }
+        self.skip_whitespace();
+        match self.bytes.get(self.pos) {
+            None => Err(self.error("unexpected end of input")),
+            Some(b'n') => self.expect("null", Value::Null),
+            Some(b't') => self.expect("true", Value::Bool(true)),
+            Some(b'f') => self.expect("false", Value::Bool(false)),
+            Some(b'"') => self.string().map(Value::Str),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if self.bytes.get(self.pos) == Some(&b']') {
+                    self.pos += 1;
+                    return Ok(Value::List(items));
+                }
+                loop {
+                    items.push(self.value(depth + 1)?);
+                    self.skip_whitespace();
+                    match self.bytes.get(self.pos) {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(Value::List(items));
+                        }
+                        _ => return Err(self.error("expected ',' or ']'")),
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut entries = Vec::new();
+                self.skip_whitespace();
+                if self.bytes.get(self.pos) == Some(&b'}') {
+                    self.pos += 1;
+                    return Ok(Value::Map(entries));
+                }
+                loop {
+                    self.skip_whitespace();
+                    if self.bytes.get(self.pos) != Some(&b'"') {
+                        return Err(self.error("expected a string key"));
+                    }
+                    let key = self.string()?;
+                    self.skip_whitespace();
+                    if self.bytes.get(self.pos) != Some(&b':') {
+                        return Err(self.error("expected ':'"));
+                    }
+                    self.pos += 1;
+                    entries.push((key, self.value(depth + 1)?));
+                    self.skip_whitespace();
+                    match self.bytes.get(self.pos) {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(Value::Map(entries));
+                        }
+                        _ => return Err(self.error("expected ',' or '}'")),
+                    }
+                }
+            }
+            Some(_) => self.number(),
+        }
+    }
+
+    /// `-? (0 | [1-9][0-9]*) (. [0-9]+)? ([eE] [+-]? [0-9]+)?`
+    fn number(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        if self.bytes.get(self.pos) == Some(&b'-') {
+            self.pos += 1;
+        }
+        let end = decimal_end(&self.bytes[self.pos..]).map(|len| self.pos + len);
+        let Some(end) = end else {
+            self.pos = start;
+            return Err(self.error("invalid number"));
+        };
+        self.pos = end;
+        let literal = &self.text[start..end];
+        if let Ok(int) = literal.parse::<i64>() {
+            return Ok(Value::Int(int));
+        }
+        match literal.parse::<f64>() {
+            Ok(float) if float.is_finite() => Ok(Value::Float(float)),
+            _ => {
+                self.pos = start;
+                Err(self.error("invalid number"))
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        // Opening quote
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let rest = &self.text[self.pos..];
+            let Some(offset) = rest.find(['"', '\\']) else {
+                return Err(self.error("unterminated string"));
+            };
+            if rest[..offset].bytes().any(|b| b < 0x20) {
+                return Err(self.error("control character in string"));
+            }
+            out.push_str(&rest[..offset]);
+            self.pos += offset;
+            if self.bytes[self.pos] == b'"' {
+                self.pos += 1;
+                return Ok(out);
+            }
+            self.pos += 1;
+            let escape = self.bytes.get(self.pos).copied();
+            self.pos += 1;
+            match escape {
+                Some(b'"') => out.push('"'),
+                Some(b'\\') => out.push('\\'),
+                Some(b'/') => out.push('/'),
+                Some(b'b') => out.push('\u{8}'),
+                Some(b'f') => out.push('\u{c}'),
+                Some(b'n') => out.push('\n'),
+                Some(b'r') => out.push('\r'),
+                Some(b't') => out.push('\t'),
+                Some(b'u') => {
+                    let high = self.hex4()?;
+                    let code = if (0xD800..0xDC00).contains(&high) {
+                        // Surrogate pair
+                        if !self.text[self.pos..].starts_with("\\u") {
+                            return Err(self.error("unpaired surrogate"));
+                        }
+                        self.pos += 2;
+                        let low = self.hex4()?;
+                        if !(0xDC00..0xE000).contains(&low) {
+                            return Err(self.error("unpaired surrogate"));
+                        }
+                        0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                    } else {
+                        high
+                    };
+                    out.push(char::from_u32(code).ok_or_else(|| self.error("invalid escape"))?);
+                }
+                _ => return Err(self.error("invalid escape")),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("invalid \\u escape"))?;
+        let code = u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid \\u escape"))?;
+        self.pos += 4;
+        Ok(code)
+    }
+}
+
+/// Nesting limit for TOML arrays and inline tables
+const MAX_TOML_DEPTH: usize = 128;
+
+struct TomlParser<'a> {
+    bytes: &'a [u8],
+    text: &'a str,
+    pos: usize,
+}
+
+impl TomlParser<'_> {
+    fn error(&self, message: &str) -> String {
+        let line = self.text[..self.pos].split('\n').count();
+        format!("invalid TOML at line {line}: {message}")
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, literal: &str) -> bool {
+        if self.text[self.pos..].starts_with(literal) {
+            self.pos += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, literal: &str) -> Result<(), String> {
+        if self.eat(literal) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{literal}'")))
+        }
+    }
+
+    /// Spaces, tabs and a trailing comment, but not the line break
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t')) {
+            self.pos += 1;
+        }
+        if self.peek() == Some(b'#') {
+            while !matches!(self.peek(), None | Some(b'\n')) {
+                self.pos += 1;
+            }
+        }
+    }
+
+    /// Whitespace, comments and line breaks
+    fn skip_blank(&mut self) {
+        loop {
+            self.skip_whitespace();
+            if !self.eat("\n") && !self.eat("\r\n") {
+                return;
+            }
+        }
+    }
+
+    fn end_of_line(&mut self) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.peek().is_none() || self.eat("\n") || self.eat("\r\n") {
+            Ok(())
+        } else {
+            Err(self.error("expected a line break"))
+        }
+    }
+
+    fn document(&mut self) -> Result<Vec<(String, Value)>, String> {
+        let mut root = Vec::new();
+        let mut current = Vec::new();
+        // `[table]` headers seen so far; a table is defined only once
+        let mut defined: Vec<Vec<String>> = Vec::new();
+        loop {
+            self.skip_blank();
+            let start = self.pos;
+            match self.peek() {
+                None => return Ok(root),
+                Some(b'[') => {
+                    let array = self.eat("[[");
+                    if !array {
+                        self.pos += 1;
+                    }
+                    self.skip_whitespace();
+                    let path = self.key()?;
+                    self.expect(if array { "]]" } else { "]" })?;
+                    self.end_of_line()?;
+                    if array {
+                        // Sub-tables of the previous element may be defined again
+                        defined.retain(|table| !table.starts_with(&path));
+                    } else if defined.contains(&path) {
+                        self.pos = start;
+                        return Err(
+                            self.error(&format!("table [{}] is defined twice", path.join(".")))
+                        );
+                    } else {
+                        defined.push(path.clone());
+                    }
+                    let created = if array {
+                        toml_push_table(&mut root, &path)
+                    } else {
+                        toml_table(&mut root, &path).map(|_| ())
+                    };
+                    created.map_err(|e| {
+                        self.pos = start;
+                        self.error(&e)
+                    })?;
+                    current = path;
+                }
+                Some(_) => {
+                    let key = self.key()?;
+                    self.expect("=")?;
+                    self.skip_whitespace();
+                    let value = self.value(0)?;
+                    self.end_of_line()?;
+                    toml_table(&mut root, &current)
+                        .and_then(|table| toml_insert(table, &key, value))
+                        .map_err(|e| {
+                            self.pos = start;
+                            self.error(&e)
+                        })?;
+                }
+            }
+        }
+    }
+
+    /// Dotted key, followed by optional whitespace
+    fn key(&mut self) -> Result<Vec<String>, String> {
+        let mut parts = Vec::new();
+        loop {
+            let part = match self.peek() {
+                Some(b'"') => self.basic_string()?,
+                Some(b'\'') => self.literal_string()?,
+                _ => {
+                    let start = self.pos;
+                    while self
+                        .peek()
+                        .is_some_and(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-'))
+                    {
+                        self.pos += 1;
+                    }
+                    if start == self.pos {
+                        return Err(self.error("expected a key"));
+                    }
+                    self.text[start..self.pos].to_string()
+                }
+            };
+            parts.push(part);
+            self.skip_whitespace();
+            if !self.eat(".") {
+                return Ok(parts);
+            }
+            self.skip_whitespace();
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, String> {
+        if depth > MAX_TOML_DEPTH {
+            return Err(self.error("nested too deeply"));
+        }
+        let rest = &self.text[self.pos..];
+        if rest.starts_with("\"\"\"") || rest.starts_with("'''") {
+            return self.multiline_string().map(Value::Str);
+        }
+        match self.peek() {
+            None => Err(self.error("expected a value")),
+            Some(b'"') => self.basic_string().map(Value::Str),
+            Some(b'\'') => self.literal_string().map(Value::Str),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                loop {
+                    self.skip_blank();
+                    if self.eat("]") {
+                        return Ok(Value::List(items));
+                    }
+                    items.push(self.value(depth + 1)?);
+                    self.skip_blank();
+                    if !self.eat(",") && self.peek() != Some(b']') {
+                        return Err(self.error("expected ',' or ']'"));
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut entries = Vec::new();
+                self.skip_whitespace();
+                if self.eat("}") {
+                    return Ok(Value::Map(entries));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let key = self.key()?;
+                    self.expect("=")?;
+                    self.skip_whitespace();
+                    let value = self.value(depth + 1)?;
+                    toml_insert(&mut entries, &key, value).map_err(|e| self.error(&e))?;
+                    self.skip_whitespace();
+                    if self.eat("}") {
+                        return Ok(Value::Map(entries));
+                    }
+                    self.expect(",")?;
+                }
+            }
+            Some(_) => self.scalar(),
+        }
+    }
+
+    /// Boolean, number, or date and time
+    fn scalar(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| {
+            b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'_' | b'.' | b':')
+        }) {
+            self.pos += 1;
+        }
+        let literal = &self.text[start..self.pos];
+        let unsigned = literal.strip_prefix(['+', '-']).unwrap_or(literal);
+        let (radix, body) = match unsigned.get(..2) {
+            Some("0x") => (16, &unsigned[2..]),
+            Some("0o") => (8, &unsigned[2..]),
+            Some("0b") => (2, &unsigned[2..]),
+            _ => (10, unsigned),
+        };
+        let is_digit = |b: &u8| {
+            if radix == 16 {
+                b.is_ascii_hexdigit()
+            } else {
+                b.is_ascii_digit()
+            }
+        };
+        // Each underscore must sit between two digits
+        let bytes = body.as_bytes();
+        let underscores_ok = bytes.iter().enumerate().all(|(i, &b)| {
+            b != b'_'
+                || (i > 0 && is_digit(&bytes[i - 1]) && bytes.get(i + 1).is_some_and(is_digit))
+        });
+        let digits = body.replace('_', "");
+        let value = match literal {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            "inf" | "+inf" => Some(Value::Float(f64::INFINITY)),
+            "-inf" => Some(Value::Float(f64::NEG_INFINITY)),
+            "nan" | "+nan" | "-nan" => Some(Value::Float(f64::NAN)),
+            _ if !unsigned.starts_with(|c: char| c.is_ascii_digit()) => None,
+            // Dates and times: 1979-05-27, 07:32:00, 1979-05-27T07:32:00Z
+            _ if unsigned.get(4..5) == Some("-") || unsigned.get(2..3) == Some(":") => {
+                Some(Value::Str(literal.to_string()))
+            }
+            _ if !underscores_ok => None,
+            // No sign before a prefix
+            _ if radix != 10 => Some(&digits)
+                .filter(|_| unsigned.len() == literal.len())
+                .filter(|digits| digits.bytes().all(|b| b.is_ascii_alphanumeric()))
+                .and_then(|digits| i64::from_str_radix(digits, radix).ok())
+                .map(Value::Int),
+            _ if decimal_end(digits.as_bytes()) != Some(digits.len()) => None,
+            _ => {
+                let number = format!("{}{digits}", &literal[..literal.len() - unsigned.len()]);
+                if digits.contains(['.', 'e', 'E']) {
+                    number
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|x| x.is_finite())
+                        .map(Value::Float)
+                } else {
+                    // Integers must fit in 64 bits
+                    number.parse::<i64>().ok().map(Value::Int)
+                }
+            }
+        };
+        value.ok_or_else(|| {
+            self.pos = start;
+            self.error("invalid value")
+        })
+    }
+
+    fn literal_string(&mut self) -> Result<String, String> {
+        // Opening quote
+        self.pos += 1;
+        let rest = &self.text[self.pos..];
+        match rest.find(['\'', '\n']) {
+            Some(end) if rest.as_bytes()[end] == b'\'' => {
+                self.pos += end + 1;
+                Ok(rest[..end].to_string())
+            }
+            _ => Err(self.error("unterminated string")),
+        }
+    }
+
+    fn basic_string(&mut self) -> Result<String, String> {
+        // Opening quote
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            let rest = &self.text[self.pos..];
+            let Some(offset) = rest.find(['"', '\\', '\n']) else {
+                return Err(self.error("unterminated string"));
+            };
+            if rest[..offset].chars().any(|c| c.is_control() && c != '\t') {
+                return Err(self.error("control character in string"));
+            }
+            out.push_str(&rest[..offset]);
+            self.pos += offset;
+            match self.bytes[self.pos] {
+                b'"' => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                b'\n' => return Err(self.error("unterminated string")),
+                _ => self.escape(&mut out)?,
+            }
+        }
+    }
+
+    /// `"""` or `'''` string; a line break right after the opening
+    /// delimiter is dropped
+    fn multiline_string(&mut self) -> Result<String, String> {
+        let delimiter = &self.text[self.pos..self.pos + 3];
+        let literal = delimiter == "'''";
+        self.pos += 3;
+        if !self.eat("\n") {
+            self.eat("\r\n");
+        }
+        let mut out = String::new();
+        loop {
+            let rest = &self.text[self.pos..];
+            let Some(offset) = rest.find(if literal { &['\''][..] } else { &['"', '\\'] }) else {
+                return Err(self.error("unterminated string"));
+            };
+            out.push_str(&rest[..offset]);
+            self.pos += offset;
+            if self.bytes[self.pos] == b'\\' {
+                // Line-ending backslash: drop the break and the whitespace after it
+                let after = &self.text[self.pos + 1..];
+                let trimmed = after.trim_start_matches([' ', '\t']);
+                if trimmed.starts_with('\n') || trimmed.starts_with("\r\n") {
+                    self.pos += 1 + after.len() - after.trim_start().len();
+                } else {
+                    self.escape(&mut out)?;
+                }
+            } else if self.eat(delimiter) {
+                // Up to two quotes may directly precede the closing delimiter
+                let quote = &delimiter[..1];
+                for _ in 0..2 {
+                    if self.eat(quote) {
+                        out.push_str(quote);
+                    }
+                }
+                return Ok(out);
+            } else {
+                out.push(char::from(self.bytes[self.pos]));
+                self.pos += 1;
+            }
+        }
+    }
+
+    /// Backslash escape at `pos`, appended to `out`
+    fn escape(&mut self, out: &mut String) -> Result<(), String> {
+        let start = self.pos;
+        self.pos += 2;
+        let simple = match self.bytes.get(start + 1) {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'e') => '\u{1b}',
+            Some(b'u' | b'U') => {
+                let digits = if self.bytes[start + 1] == b'u' { 4 } else { 8 };
+                let code = self
+                    .text
+                    .get(self.pos..self.pos + digits)
+                    .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error("invalid unicode escape"))?;
+                self.pos += digits;
+                code
+            }
+            _ => {
+                self.pos = start;
+                return Err(self.error("invalid escape"));
+            }
+        };
+        out.push(simple);
+        Ok(())
+    }
+}
+
+/// Table at `path`, created if missing. Inside an array of tables the last
+/// element is used, as for `[[items]]` followed by `[items.sub]`.
+fn toml_table<'a>(
+    mut entries: &'a mut Vec<(String, Value)>,
+    path: &[String],
+) -> Result<&'a mut Vec<(String, Value)>, String> {
+    for key in path {
+        let index = if let Some(index) = entries.iter().position(|(k, _)| k == key) {
+            index
+        } else {
+            entries.push((key.clone(), Value::map()));
+            entries.len() - 1
+        };
+        entries = match &mut entries[index].1 {
+            Value::Map(sub) => sub,
+            Value::List(items) => match items.last_mut() {
+                Some(Value::Map(sub)) => sub,
+                _ => return Err(format!("'{key}' is not a table")),
+            },
+            _ => return Err(format!("'{key}' is not a table")),
+        };
+    }
+    Ok(entries)
+}
+
+/// Start a new element of the array of tables at `path`
+fn toml_push_table(root: &mut Vec<(String, Value)>, path: &[String]) -> Result<(), String> {
+    let Some((last, parents)) = path.split_last() else {
+        return Err("empty table name".to_string());
+    };
+    let table = toml_table(root, parents)?;
+    match table.iter_mut().find(|(k, _)| k == last) {
+        Some((_, Value::List(items))) => items.push(Value::map()),
+        Some(_) => return Err(format!("'{last}' is not an array of tables")),
+        None => table.push((last.clone(), Value::List(vec![Value::map()]))),
+    }
+    Ok(())
+}
+
+/// Add a dotted key, rejecting duplicates
+fn toml_insert(
+    entries: &mut Vec<(String, Value)>,
+    key: &[String],
+    value: Value,
+) -> Result<(), String> {
+    let Some((last, parents)) = key.split_last() else {
+        return Err("empty key".to_string());
+    };
+    let table = toml_table(entries, parents)?;
+    if table.iter().any(|(k, _)| k == last) {
+        return Err(format!("duplicate key '{}'", key.join(".")));
+    }
+    table.push((last.clone(), value));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Value {
+        Value::map()
+            .with("name", "demo \"tool\"")
+            .with("count", 3_i64)
+            .with("missing", None::<String>)
+            .with("tags", vec!["a", "b"])
+            .with("build", Value::map().with("dirty", false).with("host", "h"))
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let value = sample().with("ratio", 0.5).with("empty", Value::map());
+        assert_eq!(Value::from_json(&value.to_json()), Ok(value));
+    }
+
+    #[test]
+    fn test_json_parsing() {
+        let value = Value::from_json(
+            r#" {"a": [1, -2.5e3, true, null], "s": "\u00e9\n\"x\" \ud83d\ude00", "o": {}} "#,
+        )
+        .unwrap();
+        assert_eq!(
+            value.get("a").map(Value::as_list),
+            Some(
+                &[
+                    Value::Int(1),
+                    Value::Float(-2500.0),
+                    Value::Bool(true),
+                    Value::Null
+                ][..]
+            )
+        );
+        assert_eq!(
+            value.get("s").and_then(Value::as_str),
+            Some("\u{e9}\n\"x\" \u{1f600}")
+        );
+        assert_eq!(value.get("o"), Some(&Value::map()));
+
+        for invalid in [
+            "",
+            "{",
+            "[1,]",
+            "{\"a\" 1}",
+            "\"\\x\"",
+            "01x",
+            "nul",
+            "[1] 2",
+            "\"a\nb\"",
+        ] {
+            assert!(Value::from_json(invalid).is_err(), "{invalid}");
+        }
+        assert!(Value::from_json(&"[".repeat(1000)).is_err());
+    }
+
+    #[test]
+    fn test_json_conformance() {
+        for (text, expected) in [
+            ("0", Value::Int(0)),
+            ("-0", Value::Int(0)),
+            ("10", Value::Int(10)),
+            ("0.5", Value::Float(0.5)),
+            ("-1.25e+3", Value::Float(-1250.0)),
+            ("1E2", Value::Float(100.0)),
+            ("2e-1", Value::Float(0.2)),
+            (
+                "12345678901234567890",
+                Value::Float(12_345_678_901_234_567_890.0),
+            ),
+            ("\"\\/\"", Value::from("/")),
+            ("\"\u{7f}\"", Value::from("\u{7f}")),
+            (" \r\n\t[ ] ", Value::List(Vec::new())),
+        ] {
+            assert_eq!(Value::from_json(text), Ok(expected), "{text:?}");
+        }
+
+        for invalid in [
+            // Numbers
+            "01",
+            "-01",
+            "00",
+            "[01]",
+            "1.",
+            ".5",
+            "+1",
+            "-",
+            "1e",
+            "1e+",
+            "1.e5",
+            "0x10",
+            "NaN",
+            "Infinity",
+            "-Infinity",
+            "1e400",
+            // Structure
+            "[,1]",
+            "[1 2]",
+            "{\"a\": 1,}",
+            "{\"a\"}",
+            "{1: 2}",
+            "['a']",
+            "[\"a\"",
+            "tru",
+            "\u{c}[]",
+            // Strings
+            "\"\t\"",
+            "\"\\u12\"",
+            "\"\\u+123\"",
+            "\"\\ud800\"",
+            "\"\\udc00\"",
+            "\"\\ud800\\u0041\"",
+            "\"\\a\"",
+        ] {
+            assert!(Value::from_json(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn test_output_format_parsing() {
+        assert_eq!("json".parse::<OutputFormat>(), Ok(OutputFormat::Json));
+        assert_eq!("YAML".parse::<OutputFormat>(), Ok(OutputFormat::Yaml));
+        assert!("xml".parse::<OutputFormat>().is_err());
+        for name in OutputFormat::NAMES {
+            assert_eq!(name.parse::<OutputFormat>().unwrap().name(), name);
+        }
+    }
+
+    #[test]
+    fn test_json_rendering() {
+        assert_eq!(
+            sample().to_json(),
+            r#"{
+  "name": "demo \"tool\"",
+  "count": 3,
+  "missing": null,
+  "tags": [
+    "a",
+    "b"
+  ],
+  "build": {
+    "dirty": false,
+    "host": "h"
+  }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_toml_rendering() {
+        assert_eq!(
+            sample().to_toml(),
+            r#"name = "demo \"tool\""
+count = 3
+tags = ["a", "b"]
+
+[build]
+dirty = false
+host = "h"
+"#
+        );
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let items = Value::List(vec![
+            Value::map().with("id", 1_i64),
+            Value::map().with("id", 2_i64),
+        ]);
+        let value = sample().with("ratio", 0.5).with("items", items.clone());
+        let expected = Value::map()
+            .with("name", "demo \"tool\"")
+            .with("count", 3_i64)
+            .with("tags", vec!["a", "b"])
+            .with("ratio", 0.5)
+            .with("build", Value::map().with("dirty", false).with("host", "h"))
+            .with("items", items);
+        assert_eq!(Value::from_toml(&value.to_toml()), Ok(expected));
+    }
+
+    #[test]
+    fn test_toml_parsing() {
+        let value = Value::from_toml(
+            r#"
+# Comment
+title = 'C:\path' # trailing comment
+"quoted key" = "tab\tand \u00e9"
+numbers = [ 1_000, -2.5e3, 0xff, +7,
+    inf, ]
+point = { x = 1, y.z = "deep" }
+when = 1979-05-27T07:32:00Z
+text = """
+first \
+    second"""
+
+[server.tls]
+enabled = true
+"#,
+        )
+        .unwrap();
+        assert_eq!(value.get("title").and_then(Value::as_str), Some(r"C:\path"));
+        assert_eq!(
+            value.get("quoted key").and_then(Value::as_str),
+            Some("tab\tand \u{e9}")
+        );
+        assert_eq!(
+            value.get("numbers").map(Value::as_list),
+            Some(
+                &[
+                    Value::Int(1000),
+                    Value::Float(-2500.0),
+                    Value::Int(255),
+                    Value::Int(7),
+                    Value::Float(f64::INFINITY),
+                ][..]
+            )
+        );
+        assert_eq!(
+            value
+                .get("point")
+                .and_then(|p| p.get("y"))
+                .and_then(|y| y.get("z")),
+            Some(&Value::from("deep"))
+        );
+        assert_eq!(
+            value.get("when").and_then(Value::as_str),
+            Some("1979-05-27T07:32:00Z")
+        );
+        assert_eq!(
+            value.get("text").and_then(Value::as_str),
+            Some("first second")
+        );
+        assert_eq!(
+            value
+                .get("server")
+                .and_then(|s| s.get("tls"))
+                .and_then(|t| t.get("enabled")),
+            Some(&Value::Bool(true))
+        );
+    }
+
+    #[test]
+    fn test_toml_errors() {
+        for (text, message) in [
+            ("a = 1\nb = \n", "invalid TOML at line 2: invalid value"),
+            (
+                "a = 1\na = 2\n",
+                "invalid TOML at line 2: duplicate key 'a'",
+            ),
+            (
+                "a = 1 b = 2",
+                "invalid TOML at line 1: expected a line break",
+            ),
+            (
+                "a = \"open\n",
+                "invalid TOML at line 1: unterminated string",
+            ),
+            ("a = 12abc", "invalid TOML at line 1: invalid value"),
+            ("a = 1\n[a]\n", "invalid TOML at line 2: 'a' is not a table"),
+            ("[t\n", "invalid TOML at line 1: expected ']'"),
+            (
+                "[t]\na = 1\n[u]\n[t]\nb = 2\n",
+                "invalid TOML at line 4: table [t] is defined twice",
+            ),
+            (
+                "[t.u]\n[t]\n[\"t\".u]\n",
+                "invalid TOML at line 3: table [t.u] is defined twice",
+            ),
+        ] {
+            assert_eq!(Value::from_toml(text), Err(message.to_string()), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_toml_numbers() {
+        for (text, expected) in [
+            ("0", Value::Int(0)),
+            ("-0", Value::Int(0)),
+            ("+17", Value::Int(17)),
+            ("1_000_000", Value::Int(1_000_000)),
+            ("9223372036854775807", Value::Int(i64::MAX)),
+            ("0xdead_beef", Value::Int(0xdead_beef)),
+            ("0o755", Value::Int(0o755)),
+            ("0b1101", Value::Int(0b1101)),
+            ("+0.5", Value::Float(0.5)),
+            ("1e5", Value::Float(100_000.0)),
+            ("3.0E+2", Value::Float(300.0)),
+            ("6.626e-34", Value::Float(6.626e-34)),
+            ("1_000.000_1", Value::Float(1_000.000_1)),
+        ] {
+            let value = Value::from_toml(&format!("a = {text}\n"));
+            assert_eq!(
+                value.as_ref().ok().and_then(|v| v.get("a")),
+                Some(&expected),
+                "{text}"
+            );
+        }
+
+        for invalid in [
+            "01",
+            "+01",
+            "00.5",
+            "1.",
+            ".5",
+            "1e",
+            "1.e5",
+            "1__0",
+            "_1",
+            "1_",
+            "1_.5",
+            "1._5",
+            "1_e5",
+            "0x_ff",
+            "0xff_",
+            "+0xff",
+            "0x+ff",
+            "0o8",
+            "0b102",
+            "9223372036854775808",
+            "--1",
+            "\"\\u+123\"",
+        ] {
+            assert!(
+                Value::from_toml(&format!("a = {invalid}\n")).is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_toml_table_arrays() {
+        let value = Value::map().with(
+            "items",
+            Value::List(vec![
+                Value::map().with("id", 1_i64),
+                Value::map().with("id", 2_i64),
+            ]),
+        );
+        assert_eq!(value.to_toml(), "[[items]]\nid = 1\n\n[[items]]\nid = 2\n");
+
+        // Each element can define the same sub-table
+        let text = "[[items]]\n[items.meta]\nid = 1\n[[items]]\n[items.meta]\nid = 2\n";
+        assert!(Value::from_toml(text).is_ok(), "{text}");
+    }
+
+    #[test]
+    fn test_yaml_rendering() {
+        assert_eq!(
+            sample().to_yaml(),
+            r#"name: "demo \"tool\""
+count: 3
+missing: null
+tags:
+  - "a"
+  - "b"
+build:
+  dirty: false
+  host: "h"
+"#
+        );
+    }
+
+    #[test]
+    fn test_control_characters_are_escaped() {
+        assert_eq!(quote("a\nb\u{1}"), "\"a\\nb\\u0001\"");
+    }
+}
@@ -0,0 +1,325 @@
+//! Reading manifests and artifacts from a local path, a `file://` URL or a
+//! plain `http://` URL, using only `std`.
+//!
+//! There is deliberately no TLS: update sources are internal artifact
+//! directories or a local stand-in server, and artifact integrity comes from
+//! the checksums and signatures in the manifest, not the transport.
+
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Connect, read and write timeout for HTTP requests
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Redirects followed before giving up
+const MAX_REDIRECTS: usize = 5;
+
+/// Read the resource at `location`.
+///
+/// # Errors
+/// Returns a message naming the location if it cannot be read.
+pub fn fetch(location: &str) -> Result<Vec<u8>, String> {
+    let mut location = location.to_string();
+    for _ in 0..=MAX_REDIRECTS {
+        if let Some(rest) = location.strip_prefix("http://") {
+            match http_get(rest).map_err(|e| format!("{location}: {e}"))? {
+                Response::Body(body) => return Ok(body),
+                Response::Redirect(target) => location = resolve(&location, &target),
+            }
+        } else if location.starts_with("https://") {
+            return Err(format!(
+                "{location}: https is not supported; use a plain http:// URL or a file path"
+            ));
+        } else {
+            let path = local_path(&location)?;
+            return std::fs::read(&path).map_err(|e| format!("{}: {e}", path.display()));
+        }
+    }
+    Err(format!("{location}: too many redirects"))
+}
+
+/// Read the resource at `location` as UTF-8 text.
+///
+/// # Errors
+/// Returns a message if the resource cannot be read or is not UTF-8.
+pub fn fetch_text(location: &str) -> Result<String, String> {
+    String::from_utf8(fetch(location)?).map_err(|_| format!("{location}: not UTF-8 text"))
+}
+
+/// Resolve `reference` against the manifest location `base`, so artifact
+/// URLs can be relative to the manifest.
+#[must_use]
+pub fn resolve(base: &str, reference: &str) -> String {
+    if reference.contains("://") || base.is_empty() {
+        return reference.to_string();
+    }
+    if reference.starts_with('/') {
+        // Host-relative for URLs, absolute for paths
+        return match base.split_once("://") {
+            Some((scheme, rest)) if scheme != "file" => {
+                let authority = rest.split('/').next().unwrap_or_default();
+                format!("{scheme}://{authority}{reference}")
+            }
+            Some((scheme, _)) => format!("{scheme}://{reference}"),
+            None => reference.to_string(),
+        };
+    }
+    // Everything up to and including the last '/' after the scheme
+    let directory_end = match base.split_once("://") {
+        Some((_, rest)) => rest.rfind('/').map(|i| base.len() - rest.len() + i + 1),
+        None => base.rfind('/').map(|i| i + 1),
+    };
+    match directory_end {
+        Some(end) => format!("{}{reference}", &base[..end]),
+        None if base.contains("://") => format!("{base}/{reference}"),
+        None => reference.to_string(),
+    }
+}
+
+/// Filesystem path of a plain path or `file://` URL
+fn local_path(location: &str) -> Result<PathBuf, String> {
+    let Some(rest) = location.strip_prefix("file://") else {
+        if let Some((scheme, _)) = location.split_once("://") {
+            return Err(format!("{location}: unsupported URL scheme '{scheme}'"));
+        }
+        return Ok(PathBuf::from(location));
+    };
+    let path = rest.strip_prefix("localhost").unwrap_or(rest);
+    if !path.starts_with('/') {
+        return Err(format!("{location}: file URLs must have an absolute path"));
+    }
+    Ok(PathBuf::from(percent_decode(path)))
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = text
+            .get(i + 1..i + 3)
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                out.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+enum Response {
+    Body(Vec<u8>),
+    Redirect(String),
+}
+
+/// HTTP/1.0 GET of `host[:port]/path` (the part after `http://`)
+fn http_get(url: &str) -> Result<Response, String> {
+    let (authority, path) = match url.find('/') {
+        Some(i) => (&url[..i], &url[i..]),
+        None => (url, "/"),
+    };
+    let path = path.split('#').next().unwrap_or("/");
+    let address = if authority
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| !port.contains(']'))
+    {
+        authority.to_string()
+    } else {
+        format!("{authority}:80")
+    };
+
+    let mut stream = connect(&address)?;
+    let request = format!(
+        "GET {path} HTTP/1.0\r\nHost: {authority}\r\nUser-Agent: sw-cli\r\nAccept: */*\r\nConnection: close\r\n\r\n"
+    );
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| e.to_string())?;
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).map_err(|e| e.to_string())?;
+    parse_response(&raw)
+}
+
+fn connect(address: &str) -> Result<TcpStream, String> {
+    let mut last_error = format!("cannot resolve {address}");
+    for addr in address.to_socket_addrs().map_err(|e| e.to_string())? {
+        match TcpStream::connect_timeout(&addr, TIMEOUT) {
+            Ok(stream) => {
+                stream
+                    .set_read_timeout(Some(TIMEOUT))
+                    .and_then(|()| stream.set_write_timeout(Some(TIMEOUT)))
+                    .map_err(|e| e.to_string())?;
+                return Ok(stream);
+            }
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+    Err(last_error)
+}
+
+fn parse_response(raw: &[u8]) -> Result<Response, String> {
+    let end = raw
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or("malformed HTTP response")?;
+    let head = String::from_utf8_lossy(&raw[..end]);
+    let body = &raw[end + 4..];
+    let mut lines = head.lines();
+    let status_line = lines.next().unwrap_or_default();
+    let status: u16 = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| format!("malformed HTTP status line '{status_line}'"))?;
+    let header = |name: &str| {
+        head.lines().skip(1).find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.trim()
+                .eq_ignore_ascii_case(name)
+                .then(|| value.trim().to_string())
+        })
+    };
+
+    match status {
+        200 => {}
+        301 | 302 | 303 | 307 | 308 => {
+            return header("location")
+                .map(Response::Redirect)
+                .ok_or_else(|| format!("HTTP {status} without a Location header"));
+        }
+        _ => return Err(format!("HTTP error: {}", status_line.trim())),
+    }
+    if header("transfer-encoding").is_some_and(|v| v.eq_ignore_ascii_case("chunked")) {
+        return dechunk(body).map(Response::Body);
+    }
+    if let Some(length) = header("content-length").and_then(|v| v.parse::<usize>().ok())
+        && body.len() < length
+    {
+        return Err(format!(
+            "response truncated ({} of {length} bytes)",
+            body.len()
+        ));
+    }
+    Ok(Response::Body(body.to_vec()))
+}
+
+/// Decode a `Transfer-Encoding: chunked` body
+fn dechunk(mut body: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    loop {
+        let line_end = body
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or("malformed chunked body")?;
+        let size_line = String::from_utf8_lossy(&body[..line_end]);
+        let size_hex = size_line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size_hex, 16).map_err(|_| "malformed chunk size")?;
+        body = &body[line_end + 2..];
+        if size == 0 {
+            return Ok(out);
+        }
+        let chunk = body.get(..size).ok_or("truncated chunked body")?;
+        out.extend_from_slice(chunk);
+        body = body.get(size + 2..).ok_or("truncated chunked body")?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn body(response: Response) -> Vec<u8> {
+        match response {
+            Response::Body(body) => body,
+            Response::Redirect(to) => panic!("unexpected redirect to {to}"),
+        }
+    }
+
+    #[test]
+    fn test_local_paths() {
+        assert_eq!(local_path("/srv/m.json"), Ok(PathBuf::from("/srv/m.json")));
+        assert_eq!(
+            local_path("file:///srv/a%20b.json"),
+            Ok(PathBuf::from("/srv/a b.json"))
+        );
+        assert_eq!(
+            local_path("file://localhost/srv/m.json"),
+            Ok(PathBuf::from("/srv/m.json"))
+        );
+        assert!(local_path("file://srv/m.json").is_err());
+        assert!(local_path("ftp://host/m.json").is_err());
+        assert!(
+            fetch("https://example.com/m.json")
+                .unwrap_err()
+                .contains("not supported")
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(
+            resolve("http://h:8080/rel/m.json", "a.bin"),
+            "http://h:8080/rel/a.bin"
+        );
+        assert_eq!(
+            resolve("http://h:8080/rel/m.json", "/x/a.bin"),
+            "http://h:8080/x/a.bin"
+        );
+        assert_eq!(resolve("http://h", "a.bin"), "http://h/a.bin");
+        assert_eq!(resolve("file:///srv/m.json", "a.bin"), "file:///srv/a.bin");
+        assert_eq!(resolve("/srv/m.json", "a.bin"), "/srv/a.bin");
+        assert_eq!(resolve("m.json", "a.bin"), "a.bin");
+        assert_eq!(resolve("/srv/m.json", "http://o/a.bin"), "http://o/a.bin");
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let ok = b"HTTP/1.0 200 OK\r\nContent-Length: 2\r\n\r\n{}";
+        assert_eq!(body(parse_response(ok).unwrap()), b"{}");
+
+        let chunked = b"HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\n3\r\nabc\r\n1;x=y\r\nd\r\n0\r\n\r\n";
+        assert_eq!(body(parse_response(chunked).unwrap()), b"abcd");
+
+        let moved = b"HTTP/1.1 302 Found\r\nLocation: /new.json\r\n\r\n";
+        assert!(matches!(parse_response(moved), Ok(Response::Redirect(to)) if to == "/new.json"));
+
+        let missing = b"HTTP/1.0 404 Not Found\r\n\r\n";
+        assert_eq!(
+            parse_response(missing).err().as_deref(),
+            Some("HTTP error: HTTP/1.0 404 Not Found")
+        );
+        assert!(parse_response(b"HTTP/1.0 200 OK\r\nContent-Length: 9\r\n\r\n{}").is_err());
+    }
+
+    #[test]
+    fn test_http_fetch() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let responses: [&[u8]; 2] = [
+                b"HTTP/1.0 301 Moved\r\nLocation: /releases.json\r\n\r\n",
+                b"HTTP/1.0 200 OK\r\n\r\n{\"releases\": []}",
+            ];
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = [0; 1024];
+                let n = stream.read(&mut request).unwrap();
+                assert!(request[..n].starts_with(b"GET /"));
+                stream.write_all(response).unwrap();
+            }
+        });
+        let text = fetch_text(&format!("http://127.0.0.1:{port}/latest")).unwrap();
+        assert_eq!(text, "{\"releases\": []}");
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,504 @@
+//! Self-update: download the newest release for this target from the
+//! manifest, verify it, and swap it in for the running executable.
+//!
+//! The new binary is staged next to the executable and renamed over it, so
+//! the executable is never half-written. The previous binary is kept as
+//! `<executable>.old` for [`SelfUpdate::rollback`].
+
+use super::{UpdateSettings, fetch, verify};
+use crate::output::{OutputFormat, Value};
+use crate::version::SemVer;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Replaces an executable with a verified release
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfUpdate {
+    /// Package name the release signatures must be made for
+    pub name: String,
+    /// Executable to replace
+    pub executable: PathBuf,
+    /// Target triple whose artifact is installed
+    pub target: String,
+    /// Base64 ed25519 key the artifact must be signed with
+    pub public_key: Option<String>,
+}
+
+/// What a self-update did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateOutcome {
+    /// No newer release
+    UpToDate(SemVer),
+    /// Downloaded and verified, but not installed (dry run)
+    Verified { from: SemVer, to: SemVer },
+    /// Installed; the previous executable was kept at `backup`
+    Installed {
+        from: SemVer,
+        to: SemVer,
+        backup: PathBuf,
+    },
+    /// The previous executable was restored from its backup
+    RolledBack(PathBuf),
+}
+
+impl SelfUpdate {
+    #[must_use]
+    pub fn new(name: &str, executable: PathBuf, target: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            executable,
+            target: target.to_string(),
+            public_key: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_public_key(mut self, public_key: Option<String>) -> Self {
+        self.public_key = public_key;
+        self
+    }
+
+    /// Where the previous executable is kept
+    #[must_use]
+    pub fn backup_path(&self) -> PathBuf {
+        sibling(&self.executable, "", ".old")
+    }
+
+    /// Install the newest release newer than `current`. With `dry_run` the
+    /// artifact is downloaded and verified but the executable is left alone.
+    ///
+    /// The signature must cover [`verify::release_message`] for this tool,
+    /// the release's version, this target and the artifact's digest, so a
+    /// tampered manifest cannot pass off an older signed binary as newer.
+    ///
+    /// # Errors
+    /// Returns a message if checks are disabled, no release key is embedded,
+    /// the newest release has no artifact for this target, verification
+    /// fails, or the executable cannot be replaced.
+    pub fn run(
+        &self,
+        settings: &UpdateSettings,
+        current: &SemVer,
+        dry_run: bool,
+    ) -> Result<UpdateOutcome, String> {
+        // Refuse before downloading anything that could not be verified
+        let public_key = self.public_key.as_deref().ok_or(
+            "self-update needs a release key; set `update-public-key` in \
+             [package.metadata.sw-cli] or SW_CLI_UPDATE_PUBLIC_KEY when building",
+        )?;
+
+        // An install always reads the current manifest, not the cached one
+        let loaded = settings.clone().with_cache_ttl(Duration::ZERO).load()?;
+        let Some(release) = loaded.manifest.newer_than(current).into_iter().next() else {
+            return Ok(UpdateOutcome::UpToDate(current.clone()));
+        };
+        let artifact = release.artifact(&self.target).ok_or_else(|| {
+            format!(
+                "release {} has no artifact for target {}",
+                release.version, self.target
+            )
+        })?;
+
+        let location = fetch::resolve(&loaded.location, &artifact.url);
+        let data = fetch::fetch(&location)?;
+        verify::verify_checksum(&data, &artifact.sha256).map_err(|e| format!("{location}: {e}"))?;
+        let signature = artifact
+            .signature
+            .as_deref()
+            .ok_or_else(|| format!("{location}: the manifest has no signature for it"))?;
+        let message =
+            verify::release_message(&self.name, &release.version, &self.target, &artifact.sha256);
+        verify::verify_signature(message.as_bytes(), signature, public_key)
+            .map_err(|e| format!("{location}: {e}"))?;
+
+        if dry_run {
+            return Ok(UpdateOutcome::Verified {
+                from: current.clone(),
+                to: release.version.clone(),
+            });
+        }
+        let backup = self.install(&data)?;
+        Ok(UpdateOutcome::Installed {
+            from: current.clone(),
+            to: release.version.clone(),
+            backup,
+        })
+    }
+
+    /// Replace the executable with `data`, keeping the old one as a backup.
+    ///
+    /// # Errors
+    /// Returns a message if the new binary cannot be staged or swapped in.
+    pub fn install(&self, data: &[u8]) -> Result<PathBuf, String> {
+        let exe = &self.executable;
+        let staged = sibling(exe, ".", &format!(".update-{}", std::process::id()));
+        let result = stage(exe, &staged, data).and_then(|()| {
+            let backup = self.backup_path();
+            swap(exe, &staged, &backup)?;
+            Ok(backup)
+        });
+        if result.is_err() {
+            let _ = fs::remove_file(&staged);
+        }
+        result.map_err(|e| format!("cannot replace {}: {e}", exe.display()))
+    }
+
+    /// Restore the executable kept by the last update.
+    ///
+    /// # Errors
+    /// Returns a message if there is no backup or it cannot be restored.
+    pub fn rollback(&self) -> Result<UpdateOutcome, String> {
+        let backup = self.backup_path();
+        if !backup.is_file() {
+            return Err(format!(
+                "no previous version to restore ({} does not exist)",
+                backup.display()
+            ));
+        }
+        restore(&self.executable, &backup)
+            .map_err(|e| format!("cannot restore {}: {e}", backup.display()))?;
+        Ok(UpdateOutcome::RolledBack(backup))
+    }
+}
+
+/// `dir/<prefix><file name><suffix>` next to `path`
+fn sibling(path: &Path, prefix: &str, suffix: &str) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{prefix}{name}{suffix}"))
+}
+
+/// Write the new binary next to the executable, with its permissions
+fn stage(exe: &Path, staged: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut file = fs::File::create(staged)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    fs::set_permissions(staged, fs::metadata(exe)?.permissions())
+}
+
+#[cfg(not(windows))]
+fn swap(exe: &Path, staged: &Path, backup: &Path) -> std::io::Result<()> {
+    let _ = fs::remove_file(backup);
+    if fs::hard_link(exe, backup).is_err() {
+        fs::copy(exe, backup)?;
+    }
+    // Atomic: the path always names either the old or the new binary
+    fs::rename(staged, exe)
+}
+
+#[cfg(windows)]
+fn swap(exe: &Path, staged: &Path, backup: &Path) -> std::io::Result<()> {
+    // A running executable cannot be overwritten on Windows, but it can be renamed
+    let _ = fs::remove_file(backup);
+    fs::rename(exe, backup)?;
+    fs::rename(staged, exe).inspect_err(|_| {
+        let _ = fs::rename(backup, exe);
+    })
+}
+
+#[cfg(not(windows))]
+fn restore(exe: &Path, backup: &Path) -> std::io::Result<()> {
+    fs::rename(backup, exe)
+}
+
+#[cfg(windows)]
+fn restore(exe: &Path, backup: &Path) -> std::io::Result<()> {
+    let replaced = sibling(exe, "", ".replaced");
+    let _ = fs::remove_file(&replaced);
+    fs::rename(exe, &replaced)?;
+    fs::rename(backup, exe).inspect_err(|_| {
+        let _ = fs::rename(&replaced, exe);
+    })
+}
+
+impl UpdateOutcome {
+    #[must_use]
+    pub fn to_value(&self) -> Value {
+        match self {
+            Self::UpToDate(version) => Value::map()
+                .with("status", "up-to-date")
+                .with("version", version.to_string()),
+            Self::Verified { from, to } => Value::map()
+                .with("status", "verified")
+                .with("from", from.to_string())
+                .with("to", to.to_string()),
+            Self::Installed { from, to, backup } => Value::map()
+                .with("status", "installed")
+                .with("from", from.to_string())
+                .with("to", to.to_string())
+                .with("backup", backup.display().to_string()),
+            Self::RolledBack(backup) => Value::map()
+                .with("status", "rolled-back")
+                .with("backup", backup.display().to_string()),
+        }
+    }
+
+    #[must_use]
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => self.to_string(),
+            format => self.to_value().render(format),
+        }
+    }
+}
+
+impl fmt::Display for UpdateOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UpToDate(version) => writeln!(f, "Version {version} is up to date."),
+            Self::Verified { from, to } => writeln!(
+                f,
+                "Update {from} -> {to} downloaded and verified (dry run, nothing installed)."
+            ),
+            Self::Installed { from, to, backup } => writeln!(
+                f,
+                "Updated {from} -> {to}.\nPrevious version kept at {}; restore it with --rollback-update.",
+                backup.display()
+            ),
+            Self::RolledBack(backup) => {
+                writeln!(
+                    f,
+                    "Restored the previous version from {}.",
+                    backup.display()
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::verify::tests::{release_key, sign};
+    use super::*;
+    use ed25519_dalek::SigningKey;
+
+    const TARGET: &str = "x86_64-unknown-linux-gnu";
+
+    /// What a release of `name` at `version` with `binary` is signed as
+    fn message(name: &str, version: &str, binary: &[u8]) -> String {
+        let sha256 = verify::sha256_hex(binary);
+        verify::release_message(name, &version.parse().unwrap(), TARGET, &sha256)
+    }
+
+    /// Manifest entry for `binary` published at `url`, with a signature
+    /// of `signed`
+    fn artifact(key: &SigningKey, url: &str, binary: &[u8], signed: &str) -> Value {
+        Value::map()
+            .with("target", TARGET)
+            .with("url", url)
+            .with("sha256", verify::sha256_hex(binary))
+            .with("signature", sign(key, signed.as_bytes()))
+    }
+
+    fn write_manifest(dir: &Path, version: &str, artifact: Value) {
+        let manifest = Value::map().with(
+            "releases",
+            vec![
+                Value::map().with("version", "0.1.0"),
+                Value::map()
+                    .with("version", version)
+                    .with("artifacts", vec![artifact]),
+            ],
+        );
+        fs::write(dir.join("releases/releases.json"), manifest.to_json()).unwrap();
+    }
+
+    /// A release directory with `releases.json` and a signed 0.2.0 binary,
+    /// plus an installed 0.1.0 "executable"
+    struct ReleaseDir {
+        dir: PathBuf,
+        public_key: String,
+    }
+
+    impl ReleaseDir {
+        fn new(name: &str, tamper: bool) -> Self {
+            let dir = std::env::temp_dir()
+                .join(format!("sw-cli-self-update-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(dir.join("releases")).unwrap();
+            fs::create_dir_all(dir.join("bin")).unwrap();
+
+            let (public_key, key) = release_key();
+            let binary = b"#!/bin/sh\necho 0.2.0\n";
+            fs::write(dir.join("releases/demo-0.2.0"), binary).unwrap();
+            let signed: &[u8] = if tamper { b"something else" } else { binary };
+            let signed = message("demo", "0.2.0", signed);
+            write_manifest(&dir, "0.2.0", artifact(&key, "demo-0.2.0", binary, &signed));
+            fs::write(dir.join("bin/demo"), b"#!/bin/sh\necho 0.1.0\n").unwrap();
+            Self { dir, public_key }
+        }
+
+        fn settings(&self) -> UpdateSettings {
+            UpdateSettings::new("demo")
+                .with_manifest(Some(format!(
+                    "file://{}/releases/releases.json",
+                    self.dir.display()
+                )))
+                .with_cache_dir(None)
+        }
+
+        fn updater(&self) -> SelfUpdate {
+            SelfUpdate::new("demo", self.dir.join("bin/demo"), TARGET)
+                .with_public_key(Some(self.public_key.clone()))
+        }
+
+        fn installed(&self) -> String {
+            fs::read_to_string(self.dir.join("bin/demo")).unwrap()
+        }
+    }
+
+    impl Drop for ReleaseDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    #[test]
+    fn test_install_and_rollback() {
+        let release = ReleaseDir::new("install", false);
+        let updater = release.updater();
+        let current = SemVer::new(0, 1, 0);
+
+        let outcome = updater.run(&release.settings(), &current, true).unwrap();
+        assert_eq!(
+            outcome,
+            UpdateOutcome::Verified {
+                from: current.clone(),
+                to: SemVer::new(0, 2, 0)
+            }
+        );
+        assert!(release.installed().contains("0.1.0"));
+
+        let outcome = updater.run(&release.settings(), &current, false).unwrap();
+        let backup = release.dir.join("bin/demo.old");
+        assert_eq!(
+            outcome,
+            UpdateOutcome::Installed {
+                from: current,
+                to: SemVer::new(0, 2, 0),
+                backup: backup.clone()
+            }
+        );
+        assert!(release.installed().contains("0.2.0"));
+        assert!(fs::read_to_string(&backup).unwrap().contains("0.1.0"));
+        // Nothing staged is left behind
+        assert_eq!(fs::read_dir(release.dir.join("bin")).unwrap().count(), 2);
+
+        let outcome = updater
+            .run(&release.settings(), &SemVer::new(0, 2, 0), false)
+            .unwrap();
+        assert_eq!(outcome, UpdateOutcome::UpToDate(SemVer::new(0, 2, 0)));
+
+        assert_eq!(updater.rollback(), Ok(UpdateOutcome::RolledBack(backup)));
+        assert!(release.installed().contains("0.1.0"));
+        assert!(
+            updater
+                .rollback()
+                .unwrap_err()
+                .starts_with("no previous version")
+        );
+    }
+
+    #[test]
+    fn test_verification_failures() {
+        let release = ReleaseDir::new("tampered", true);
+        let current = SemVer::new(0, 1, 0);
+        let err = release
+            .updater()
+            .run(&release.settings(), &current, false)
+            .unwrap_err();
+        assert!(
+            err.ends_with("demo-0.2.0: signature verification failed"),
+            "{err}"
+        );
+        assert!(release.installed().contains("0.1.0"));
+
+        fs::write(release.dir.join("releases/demo-0.2.0"), b"corrupt").unwrap();
+        let err = release
+            .updater()
+            .run(&release.settings(), &current, false)
+            .unwrap_err();
+        assert!(err.contains("SHA-256 mismatch"), "{err}");
+
+        let err = release
+            .updater()
+            .with_public_key(None)
+            .run(&release.settings(), &current, false)
+            .unwrap_err();
+        assert!(err.starts_with("self-update needs a release key"), "{err}");
+
+        let err = SelfUpdate::new(
+            "demo",
+            release.dir.join("bin/demo"),
+            "riscv64gc-unknown-linux-gnu",
+        )
+        .with_public_key(Some(release.public_key.clone()))
+        .run(&release.settings(), &current, false)
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "release 0.2.0 has no artifact for target riscv64gc-unknown-linux-gnu"
+        );
+    }
+
+    #[test]
+    fn test_relabelled_release_is_rejected() {
+        let release = ReleaseDir::new("relabelled", false);
+        let (_, key) = release_key();
+        let current = SemVer::new(0, 2, 0);
+        fs::write(release.dir.join("bin/demo"), b"#!/bin/sh\necho 0.2.0\n").unwrap();
+        let old = b"#!/bin/sh\necho 0.1.0\n";
+        fs::write(release.dir.join("releases/demo-0.1.0"), old).unwrap();
+
+        // A genuinely signed 0.1.0 binary listed as 0.3.0, for another tool,
+        // or for another target
+        for signed in [
+            message("demo", "0.1.0", old),
+            message("other", "0.3.0", old),
+            message("demo", "0.3.0", old).replace(TARGET, "aarch64-apple-darwin"),
+        ] {
+            write_manifest(
+                &release.dir,
+                "0.3.0",
+                artifact(&key, "demo-0.1.0", old, &signed),
+            );
+            let err = release
+                .updater()
+                .run(&release.settings(), &current, false)
+                .unwrap_err();
+            assert!(
+                err.ends_with("demo-0.1.0: signature verification failed"),
+                "{err}"
+            );
+            assert!(release.installed().contains("0.2.0"));
+        }
+
+        // Signed for what it is listed as, the same binary installs
+        let signed = message("demo", "0.3.0", old);
+        write_manifest(
+            &release.dir,
+            "0.3.0",
+            artifact(&key, "demo-0.1.0", old, &signed),
+        );
+        assert!(
+            release
+                .updater()
+                .run(&release.settings(), &current, true)
+                .is_ok()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_install_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+        let release = ReleaseDir::new("permissions", false);
+        let exe = release.dir.join("bin/demo");
+        fs::set_permissions(&exe, fs::Permissions::from_mode(0o755)).unwrap();
+        release.updater().install(b"new").unwrap();
+        let mode = fs::metadata(&exe).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+}
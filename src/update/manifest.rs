@@ -0,0 +1,254 @@
+//! Release manifests: the JSON document an artifact directory publishes to
+//! announce the available versions of a tool.
+//!
+//! ```json
+//! {
+//!   "name": "mycli",
+//!   "releases": [
+//!     {
+//!       "version": "0.2.0",
+//!       "date": "2025-12-01",
+//!       "changelog": ["Add --sbom", "Fix crash on empty input"],
+//!       "artifacts": [
+//!         {
+//!           "target": "x86_64-unknown-linux-gnu",
+//!           "url": "mycli-0.2.0-x86_64-unknown-linux-gnu",
+//!           "sha256": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+//!           "signature": "base64 ed25519 signature"
+//!         }
+//!       ]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! `changelog` may also be a single string. Unknown keys are ignored, so
+//! manifests can carry extra data for other tools.
+
+use crate::output::Value;
+use crate::version::SemVer;
+
+/// A parsed release manifest
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Manifest {
+    /// Name of the tool the manifest describes, if given
+    pub name: Option<String>,
+    pub releases: Vec<Release>,
+}
+
+/// One published version
+#[derive(Debug, Clone, PartialEq)]
+pub struct Release {
+    pub version: SemVer,
+    /// Release date as written in the manifest
+    pub date: Option<String>,
+    /// Changelog entries, one per line of text
+    pub changelog: Vec<String>,
+    pub artifacts: Vec<Artifact>,
+}
+
+/// A downloadable binary of a release
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    /// Target triple the binary was built for
+    pub target: String,
+    /// Location of the binary, absolute or relative to the manifest
+    pub url: String,
+    /// Hex SHA-256 digest of the binary
+    pub sha256: String,
+    /// Base64 ed25519 signature of the release's
+    /// [`release_message`](super::verify::release_message)
+    pub signature: Option<String>,
+}
+
+impl Manifest {
+    /// Parse a manifest from its JSON text.
+    ///
+    /// # Errors
+    /// Returns a message if the text is not JSON or a release is malformed.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let value = Value::from_json(text)?;
+        let Value::Map(_) = value else {
+            return Err("release manifest must be a JSON object".to_string());
+        };
+        let releases = match value.get("releases") {
+            Some(Value::List(items)) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    Release::from_value(item).map_err(|e| format!("releases[{i}]: {e}"))
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("`releases` must be a list".to_string()),
+            None => Vec::new(),
+        };
+        Ok(Self {
+            name: value
+                .get("name")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            releases,
+        })
+    }
+
+    /// The newest release, ignoring pre-releases unless `include_prerelease`
+    #[must_use]
+    pub fn latest(&self, include_prerelease: bool) -> Option<&Release> {
+        self.releases
+            .iter()
+            .filter(|r| include_prerelease || !r.version.is_prerelease())
+            .max_by(|a, b| a.version.cmp_precedence(&b.version))
+    }
+
+    /// Releases newer than `current`, newest first.
+    ///
+    /// Pre-releases are only offered to users who already run one.
+    #[must_use]
+    pub fn newer_than(&self, current: &SemVer) -> Vec<&Release> {
+        let mut newer: Vec<&Release> = self
+            .releases
+            .iter()
+            .filter(|r| current.is_prerelease() || !r.version.is_prerelease())
+            .filter(|r| r.version.cmp_precedence(current).is_gt())
+            .collect();
+        newer.sort_by(|a, b| b.version.cmp_precedence(&a.version));
+        newer
+    }
+}
+
+impl Release {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        let version = required_str(value, "version")?.parse()?;
+        let changelog = match value.get("changelog") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Str(text)) => text
+                .lines()
+                .map(str::trim_end)
+                .filter(|l| !l.trim().is_empty())
+                .map(str::to_string)
+                .collect(),
+            Some(Value::List(items)) => items
+                .iter()
+                .map(|item| {
+                    item.as_str()
+                        .map(str::to_string)
+                        .ok_or("`changelog` entries must be strings")
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("`changelog` must be a string or a list".to_string()),
+        };
+        let artifacts = value
+            .get("artifacts")
+            .map(Value::as_list)
+            .unwrap_or_default()
+            .iter()
+            .map(Artifact::from_value)
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            version,
+            date: value
+                .get("date")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            changelog,
+            artifacts,
+        })
+    }
+
+    /// The artifact built for `target`
+    #[must_use]
+    pub fn artifact(&self, target: &str) -> Option<&Artifact> {
+        self.artifacts.iter().find(|a| a.target == target)
+    }
+
+    #[must_use]
+    pub fn to_value(&self) -> Value {
+        Value::map()
+            .with("version", self.version.to_string())
+            .with("date", self.date.clone())
+            .with("changelog", self.changelog.clone())
+    }
+}
+
+impl Artifact {
+    fn from_value(value: &Value) -> Result<Self, String> {
+        Ok(Self {
+            target: required_str(value, "target")?.to_string(),
+            url: required_str(value, "url")?.to_string(),
+            sha256: required_str(value, "sha256")?.to_ascii_lowercase(),
+            signature: value
+                .get("signature")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        })
+    }
+}
+
+fn required_str<'a>(value: &'a Value, key: &str) -> Result<&'a str, String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("missing string `{key}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"{
+        "name": "demo",
+        "releases": [
+            {"version": "0.1.0", "changelog": "Initial release"},
+            {"version": "0.3.0-beta.1", "changelog": ["Try the new parser"]},
+            {"version": "0.2.0", "date": "2025-12-01", "changelog": "Add --sbom\n\nFix crash\n",
+             "artifacts": [{"target": "x86_64-unknown-linux-gnu", "url": "demo-0.2.0", "sha256": "ABC"}]}
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        assert_eq!(manifest.name.as_deref(), Some("demo"));
+        assert_eq!(manifest.releases.len(), 3);
+        let release = &manifest.releases[2];
+        assert_eq!(release.date.as_deref(), Some("2025-12-01"));
+        assert_eq!(release.changelog, ["Add --sbom", "Fix crash"]);
+        let artifact = release.artifact("x86_64-unknown-linux-gnu").unwrap();
+        assert_eq!(artifact.sha256, "abc");
+        assert_eq!(artifact.signature, None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Manifest::parse("[]").is_err());
+        assert!(Manifest::parse(r#"{"releases": {}}"#).is_err());
+        let err = Manifest::parse(r#"{"releases": [{"version": "1.0"}]}"#).unwrap_err();
+        assert!(err.starts_with("releases[0]: "), "{err}");
+        assert!(
+            Manifest::parse(r#"{"releases": [{"version": "1.0.0", "artifacts": [{}]}]}"#).is_err()
+        );
+        assert_eq!(Manifest::parse("{}"), Ok(Manifest::default()));
+    }
+
+    #[test]
+    fn test_newer_releases() {
+        let manifest = Manifest::parse(MANIFEST).unwrap();
+        let versions = |current: &str| {
+            manifest
+                .newer_than(&current.parse().unwrap())
+                .iter()
+                .map(|r| r.version.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(versions("0.1.0"), ["0.2.0"]);
+        assert_eq!(versions("0.2.0"), Vec::<String>::new());
+        assert_eq!(versions("0.2.1-alpha"), ["0.3.0-beta.1"]);
+        assert_eq!(versions("0.0.9-rc.1"), ["0.3.0-beta.1", "0.2.0", "0.1.0"]);
+
+        assert_eq!(manifest.latest(false).unwrap().version.to_string(), "0.2.0");
+        assert_eq!(
+            manifest.latest(true).unwrap().version.to_string(),
+            "0.3.0-beta.1"
+        );
+    }
+}
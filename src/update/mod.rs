@@ -0,0 +1,423 @@
+//! Update checks against a release manifest published next to the binaries
+//! (a local directory, a `file://` URL or a plain-HTTP artifact server).
+//!
+//! The manifest location comes from `update-manifest` in
+//! `[package.metadata.sw-cli]`, or at run time from `SW_CLI_UPDATE_MANIFEST`
+//! or `--check-update <MANIFEST>`. Results are cached for a day so repeated
+//! checks do not hit the artifact server.
+//!
+//! With the `self-update` feature (on by default), [`SelfUpdate`] installs
+//! the newest release after verifying its checksum and signature.
+
+pub mod fetch;
+#[cfg(feature = "self-update")]
+pub mod install;
+pub mod manifest;
+#[cfg(feature = "self-update")]
+pub mod verify;
+
+#[cfg(feature = "self-update")]
+pub use install::{SelfUpdate, UpdateOutcome};
+pub use manifest::{Artifact, Manifest, Release};
+
+use crate::output::{OutputFormat, Value};
+use crate::version::SemVer;
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Environment variable overriding the manifest location
+pub const MANIFEST_ENV: &str = "SW_CLI_UPDATE_MANIFEST";
+/// Environment variable that disables update checks when set (to anything but `0`/`false`)
+pub const DISABLE_ENV: &str = "SW_CLI_NO_UPDATE_CHECK";
+/// Environment variable overriding the cache lifetime in seconds (`0` disables caching)
+pub const CACHE_TTL_ENV: &str = "SW_CLI_UPDATE_CACHE_TTL";
+
+/// How long a fetched manifest is reused
+// `Duration::from_hours` needs a newer Rust than the crate otherwise does
+#[allow(clippy::duration_suboptimal_units)]
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+const CACHE_FILE: &str = "update-check.json";
+
+/// Where and whether to look for updates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateSettings {
+    /// Manifest location: a path, `file://` or `http://` URL
+    pub manifest: Option<String>,
+    pub enabled: bool,
+    /// Why checks are disabled, for the message shown to the user
+    pub disabled_by: Option<String>,
+    /// Directory of the cached manifest (`None` disables caching)
+    pub cache_dir: Option<PathBuf>,
+    pub cache_ttl: Duration,
+}
+
+impl UpdateSettings {
+    /// Enabled settings without a manifest, caching in the user cache
+    /// directory of `app_name`
+    #[must_use]
+    pub fn new(app_name: &str) -> Self {
+        Self {
+            manifest: None,
+            enabled: true,
+            disabled_by: None,
+            cache_dir: default_cache_dir(app_name),
+            cache_ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+
+    #[must_use]
+    pub fn with_manifest(mut self, manifest: Option<String>) -> Self {
+        if manifest.is_some() {
+            self.manifest = manifest;
+        }
+        self
+    }
+
+    /// Disable checks, recording the reason (e.g. `update-check = false`)
+    #[must_use]
+    pub fn disabled(mut self, reason: &str) -> Self {
+        self.enabled = false;
+        self.disabled_by = Some(reason.to_string());
+        self
+    }
+
+    #[must_use]
+    pub fn with_cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.cache_dir = cache_dir;
+        self
+    }
+
+    #[must_use]
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// Apply `SW_CLI_UPDATE_MANIFEST`, `SW_CLI_NO_UPDATE_CHECK` and
+    /// `SW_CLI_UPDATE_CACHE_TTL`
+    #[must_use]
+    pub fn with_env(self) -> Self {
+        self.with_vars(|key| std::env::var(key).ok())
+    }
+
+    fn with_vars(mut self, var: impl Fn(&str) -> Option<String>) -> Self {
+        self = self.with_manifest(var(MANIFEST_ENV).filter(|m| !m.is_empty()));
+        if var(DISABLE_ENV).is_some_and(|v| !matches!(v.trim(), "" | "0" | "false")) {
+            self = self.disabled(&format!("{DISABLE_ENV} is set"));
+        }
+        if let Some(seconds) = var(CACHE_TTL_ENV).and_then(|v| v.trim().parse().ok()) {
+            self.cache_ttl = Duration::from_secs(seconds);
+        }
+        self
+    }
+
+    /// Compare `current` with the releases in the manifest, using the cached
+    /// copy while it is fresh.
+    ///
+    /// # Errors
+    /// Returns a message if checks are disabled, no manifest is configured,
+    /// or the manifest cannot be read or parsed.
+    pub fn check(&self, current: &SemVer) -> Result<UpdateReport, String> {
+        let manifest = self.load()?;
+        Ok(UpdateReport {
+            current: current.clone(),
+            releases: manifest
+                .manifest
+                .newer_than(current)
+                .into_iter()
+                .cloned()
+                .collect(),
+            location: manifest.location,
+            checked_at: manifest.checked_at,
+            cached: manifest.cached,
+        })
+    }
+
+    /// Fetch the manifest, or read it from the cache while it is fresh
+    ///
+    /// # Errors
+    /// Same as [`UpdateSettings::check`].
+    pub fn load(&self) -> Result<LoadedManifest, String> {
+        if !self.enabled {
+            return Err(format!(
+                "update checks are disabled ({})",
+                self.disabled_by.as_deref().unwrap_or("by configuration")
+            ));
+        }
+        let location = self.manifest.clone().ok_or_else(|| {
+            format!(
+                "no release manifest configured; set `update-manifest` in \
+                 [package.metadata.sw-cli], {MANIFEST_ENV}, or pass one to --check-update"
+            )
+        })?;
+
+        let now = Utc::now();
+        if let Some((checked_at, text)) = self.read_cache(&location, now) {
+            return Ok(LoadedManifest {
+                manifest: Manifest::parse(&text)?,
+                location,
+                checked_at,
+                cached: true,
+            });
+        }
+        let text = fetch::fetch_text(&location)?;
+        let manifest = Manifest::parse(&text).map_err(|e| format!("{location}: {e}"))?;
+        self.write_cache(&location, now, &text);
+        Ok(LoadedManifest {
+            manifest,
+            location,
+            checked_at: now,
+            cached: false,
+        })
+    }
+
+    fn cache_file(&self) -> Option<PathBuf> {
+        self.cache_dir.as_ref().map(|dir| dir.join(CACHE_FILE))
+    }
+
+    fn read_cache(&self, location: &str, now: DateTime<Utc>) -> Option<(DateTime<Utc>, String)> {
+        let cache = Value::from_json(&fs::read_to_string(self.cache_file()?).ok()?).ok()?;
+        if cache.get("manifest")?.as_str()? != location {
+            return None;
+        }
+        let checked_at = DateTime::from_timestamp(cache.get("checked_at")?.as_i64()?, 0)?;
+        let age = (now - checked_at).to_std().ok()?;
+        if age >= self.cache_ttl {
+            return None;
+        }
+        Some((checked_at, cache.get("body")?.as_str()?.to_string()))
+    }
+
+    /// Best effort: a read-only cache directory only costs a refetch
+    fn write_cache(&self, location: &str, now: DateTime<Utc>, text: &str) {
+        let Some(file) = self.cache_file().filter(|_| !self.cache_ttl.is_zero()) else {
+            return;
+        };
+        let cache = Value::map()
+            .with("manifest", location)
+            .with("checked_at", now.timestamp())
+            .with("body", text);
+        if let Some(dir) = file.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = fs::write(file, cache.to_json());
+    }
+}
+
+/// Per-user cache directory: `$XDG_CACHE_HOME/<app>`, `~/.cache/<app>`, or
+/// `%LOCALAPPDATA%\<app>` on Windows
+#[must_use]
+pub fn default_cache_dir(app_name: &str) -> Option<PathBuf> {
+    let var = |key| std::env::var_os(key).filter(|v| !v.is_empty());
+    let base = var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .or_else(|| var("LOCALAPPDATA").map(PathBuf::from))?;
+    let app = if app_name.is_empty() {
+        "sw-cli"
+    } else {
+        app_name
+    };
+    Some(base.join(app))
+}
+
+/// A manifest together with where and when it was read
+#[derive(Debug, Clone)]
+pub struct LoadedManifest {
+    pub manifest: Manifest,
+    pub location: String,
+    pub checked_at: DateTime<Utc>,
+    /// Whether it came from the cache instead of `location`
+    pub cached: bool,
+}
+
+/// Result of an update check
+#[derive(Debug, Clone)]
+pub struct UpdateReport {
+    pub current: SemVer,
+    /// Releases newer than `current`, newest first
+    pub releases: Vec<Release>,
+    /// Manifest location
+    pub location: String,
+    pub checked_at: DateTime<Utc>,
+    /// Whether the manifest came from the cache
+    pub cached: bool,
+}
+
+impl UpdateReport {
+    /// The newest release newer than the running version
+    #[must_use]
+    pub fn latest(&self) -> Option<&Release> {
+        self.releases.first()
+    }
+
+    #[must_use]
+    pub fn is_update_available(&self) -> bool {
+        !self.releases.is_empty()
+    }
+
+    #[must_use]
+    pub fn to_value(&self) -> Value {
+        Value::map()
+            .with("current", self.current.to_string())
+            .with("latest", self.latest().map(|r| r.version.to_string()))
+            .with("update_available", self.is_update_available())
+            .with("manifest", self.location.as_str())
+            .with(
+                "checked_at",
+                self.checked_at
+                    .to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+            )
+            .with("cached", self.cached)
+            .with(
+                "releases",
+                self.releases
+                    .iter()
+                    .map(Release::to_value)
+                    .collect::<Vec<_>>(),
+            )
+    }
+
+    /// Render as text (the changelog of every newer release) or structured data
+    #[must_use]
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => self.to_string(),
+            format => self.to_value().render(format),
+        }
+    }
+}
+
+impl fmt::Display for UpdateReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(latest) = self.latest() else {
+            return writeln!(f, "Version {} is up to date.", self.current);
+        };
+        writeln!(
+            f,
+            "Update available: {} -> {}",
+            self.current, latest.version
+        )?;
+        for release in &self.releases {
+            writeln!(f)?;
+            match &release.date {
+                Some(date) => writeln!(f, "{} ({date})", release.version)?,
+                None => writeln!(f, "{}", release.version)?,
+            }
+            for entry in &release.changelog {
+                writeln!(f, "  - {entry}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"{"releases": [
+        {"version": "0.1.0"},
+        {"version": "0.2.0", "date": "2025-12-01", "changelog": ["Add --sbom"]},
+        {"version": "0.3.0", "changelog": "Faster startup"}
+    ]}"#;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sw-cli-update-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn settings(dir: &std::path::Path) -> UpdateSettings {
+        let manifest = dir.join("releases.json");
+        fs::write(&manifest, MANIFEST).unwrap();
+        UpdateSettings::new("demo")
+            .with_manifest(Some(manifest.display().to_string()))
+            .with_cache_dir(Some(dir.join("cache")))
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let vars = |pairs: &'static [(&str, &str)]| {
+            move |key: &str| {
+                pairs
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| (*v).to_string())
+            }
+        };
+        let base = UpdateSettings::new("demo").with_manifest(Some("/srv/built-in.json".into()));
+
+        let settings = base.clone().with_vars(vars(&[
+            (MANIFEST_ENV, "http://localhost:8000/releases.json"),
+            (CACHE_TTL_ENV, "90"),
+        ]));
+        assert!(settings.enabled);
+        assert_eq!(
+            settings.manifest.as_deref(),
+            Some("http://localhost:8000/releases.json")
+        );
+        assert_eq!(settings.cache_ttl, Duration::from_secs(90));
+
+        let settings = base.clone().with_vars(vars(&[(DISABLE_ENV, "1")]));
+        assert!(!settings.enabled);
+        assert_eq!(settings.manifest.as_deref(), Some("/srv/built-in.json"));
+        let err = settings.check(&SemVer::new(0, 1, 0)).unwrap_err();
+        assert_eq!(
+            err,
+            "update checks are disabled (SW_CLI_NO_UPDATE_CHECK is set)"
+        );
+
+        assert!(base.with_vars(vars(&[(DISABLE_ENV, "0")])).enabled);
+    }
+
+    #[test]
+    fn test_check_and_cache() {
+        let dir = scratch_dir("check");
+        let settings = settings(&dir);
+
+        let report = settings.check(&SemVer::new(0, 1, 0)).unwrap();
+        assert!(!report.cached);
+        assert_eq!(report.latest().unwrap().version, SemVer::new(0, 3, 0));
+        assert_eq!(
+            report.to_string(),
+            "Update available: 0.1.0 -> 0.3.0\n\n0.3.0\n  - Faster startup\n\n\
+             0.2.0 (2025-12-01)\n  - Add --sbom\n"
+        );
+
+        // A second check within the TTL does not read the manifest again
+        fs::write(dir.join("releases.json"), r#"{"releases": []}"#).unwrap();
+        let report = settings.check(&SemVer::new(0, 3, 0)).unwrap();
+        assert!(report.cached);
+        assert_eq!(report.to_string(), "Version 0.3.0 is up to date.\n");
+        assert_eq!(report.to_value().get("latest"), Some(&Value::Null));
+
+        let report = settings
+            .with_cache_ttl(Duration::ZERO)
+            .check(&SemVer::new(0, 1, 0))
+            .unwrap();
+        assert!(!report.cached);
+        assert!(!report.is_update_available());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_check_errors() {
+        let err = UpdateSettings::new("demo")
+            .check(&SemVer::new(0, 1, 0))
+            .unwrap_err();
+        assert!(err.starts_with("no release manifest configured"), "{err}");
+
+        let dir = scratch_dir("errors");
+        let settings = settings(&dir).with_cache_dir(None);
+        fs::write(dir.join("releases.json"), "not json").unwrap();
+        let err = settings.check(&SemVer::new(0, 1, 0)).unwrap_err();
+        assert!(err.contains("releases.json: invalid JSON"), "{err}");
+        let _ = fs::remove_dir_all(dir);
+    }
+}
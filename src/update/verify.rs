@@ -0,0 +1,201 @@
+//! Integrity checks for downloaded artifacts: the SHA-256 digest from the
+//! manifest and an ed25519 signature made with the release key.
+//!
+//! The manifest itself is not authenticated, so the signature covers
+//! [`release_message`] rather than the bare binary: it binds the artifact's
+//! digest to the tool, version and target the manifest lists it under.
+
+use crate::version::SemVer;
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+
+/// Lowercase hex SHA-256 digest of `data`
+#[must_use]
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// Check `data` against a hex SHA-256 digest.
+///
+/// # Errors
+/// Returns a message with both digests if they differ.
+pub fn verify_checksum(data: &[u8], expected: &str) -> Result<(), String> {
+    let actual = sha256_hex(data);
+    if actual.eq_ignore_ascii_case(expected.trim()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "SHA-256 mismatch: expected {}, got {actual}",
+            expected.trim()
+        ))
+    }
+}
+
+/// The text a release signature covers, one `key: value` line each:
+///
+/// ```text
+/// sw-cli release
+/// name: mycli
+/// version: 0.2.0
+/// target: x86_64-unknown-linux-gnu
+/// sha256: 9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08
+/// ```
+///
+/// A genuinely signed old binary listed under a newer version or another
+/// target no longer matches its signature.
+#[must_use]
+pub fn release_message(name: &str, version: &SemVer, target: &str, sha256: &str) -> String {
+    format!(
+        "sw-cli release\nname: {name}\nversion: {version}\ntarget: {target}\nsha256: {}\n",
+        sha256.trim().to_ascii_lowercase()
+    )
+}
+
+/// Check a base64 ed25519 `signature` of `data` against a base64 public key.
+///
+/// # Errors
+/// Returns a message if the key or signature is malformed or the signature
+/// does not match.
+pub fn verify_signature(data: &[u8], signature: &str, public_key: &str) -> Result<(), String> {
+    let key: [u8; 32] = decode_base64(public_key)?
+        .try_into()
+        .map_err(|_| "update public key must be 32 bytes".to_string())?;
+    let key =
+        VerifyingKey::from_bytes(&key).map_err(|e| format!("invalid update public key: {e}"))?;
+    let signature: [u8; 64] = decode_base64(signature)?
+        .try_into()
+        .map_err(|_| "signature must be 64 bytes".to_string())?;
+    key.verify_strict(data, &Signature::from_bytes(&signature))
+        .map_err(|_| "signature verification failed".to_string())
+}
+
+/// Decode standard or URL-safe base64, with or without padding
+///
+/// # Errors
+/// Returns a message if `text` contains characters outside the alphabet.
+pub fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in text.trim().trim_end_matches('=').bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return Err(format!("invalid base64 character '{}'", char::from(c))),
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits).to_le_bytes()[0]);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    pub(crate) fn encode_base64(data: &[u8]) -> String {
+        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let mut out = String::new();
+        for chunk in data.chunks(3) {
+            let n = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |n, (i, b)| n | u32::from(*b) << (16 - 8 * i));
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(char::from(ALPHABET[(n >> (18 - 6 * i) & 63) as usize]));
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+        out
+    }
+
+    /// Fixed release key for tests: (base64 public key, signing key)
+    pub(crate) fn release_key() -> (String, SigningKey) {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        (encode_base64(key.verifying_key().as_bytes()), key)
+    }
+
+    pub(crate) fn sign(key: &SigningKey, data: &[u8]) -> String {
+        encode_base64(&key.sign(data).to_bytes())
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(
+            verify_checksum(
+                b"abc",
+                "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD"
+            )
+            .is_ok()
+        );
+        let err = verify_checksum(b"abd", "ba7816bf").unwrap_err();
+        assert!(
+            err.starts_with("SHA-256 mismatch: expected ba7816bf, got "),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(decode_base64("aGVsbG8="), Ok(b"hello".to_vec()));
+        assert_eq!(decode_base64("aGVsbG8"), Ok(b"hello".to_vec()));
+        assert_eq!(decode_base64("-_8="), decode_base64("+/8="));
+        assert!(decode_base64("a b").is_err());
+        for data in [&b""[..], b"f", b"fo", b"foo", b"\xff\x00\x10"] {
+            assert_eq!(decode_base64(&encode_base64(data)), Ok(data.to_vec()));
+        }
+    }
+
+    #[test]
+    fn test_release_message() {
+        assert_eq!(
+            release_message(
+                "mycli",
+                &"v0.2.0".parse().unwrap(),
+                "x86_64-unknown-linux-gnu",
+                " 9F86D081 "
+            ),
+            "sw-cli release\n\
+             name: mycli\n\
+             version: 0.2.0\n\
+             target: x86_64-unknown-linux-gnu\n\
+             sha256: 9f86d081\n"
+        );
+    }
+
+    #[test]
+    fn test_signature() {
+        let (public_key, key) = release_key();
+        let signature = sign(&key, b"binary");
+        assert_eq!(verify_signature(b"binary", &signature, &public_key), Ok(()));
+        assert_eq!(
+            verify_signature(b"tampered", &signature, &public_key),
+            Err("signature verification failed".to_string())
+        );
+        let other = encode_base64(SigningKey::from_bytes(&[8; 32]).verifying_key().as_bytes());
+        assert!(verify_signature(b"binary", &signature, &other).is_err());
+        assert!(verify_signature(b"binary", "AAAA", &public_key).is_err());
+        assert!(verify_signature(b"binary", &signature, "AAAA").is_err());
+    }
+}
@@ -0,0 +1,245 @@
+//! HTML presentation of [`Version`] for web footers.
+//!
+//! Plain std string building, so it works with any web framework (or none).
+//! Every value taken from `version_info.rs` is escaped before it is written.
+
+use super::Version;
+use super::license::{find, map_license_ids};
+use std::str::FromStr;
+
+/// CSS class used when `web_footer!()` is called without `class = ...`
+pub const DEFAULT_FOOTER_CLASS: &str = "sw-footer";
+
+/// Footer layout selected with `web_footer!(style = "...")`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FooterStyle {
+    /// Single line of text with a license link
+    #[default]
+    Plain,
+    /// Structured markup with one styled `<span>` per field
+    Rich,
+}
+
+impl FromStr for FooterStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "plain" => Ok(Self::Plain),
+            "rich" => Ok(Self::Rich),
+            _ => Err(format!(
+                "unknown footer style '{s}' (expected 'plain' or 'rich')"
+            )),
+        }
+    }
+}
+
+/// Escape text for use in HTML element content and quoted attribute values
+#[must_use]
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Render a footer in the given style
+#[must_use]
+pub fn format_footer_styled(version: &Version, style: FooterStyle, css_class: &str) -> String {
+    match style {
+        FooterStyle::Plain => format_footer_with_class(version, css_class),
+        FooterStyle::Rich => format_footer_rich(version, css_class),
+    }
+}
+
+/// Single-line footer using [`DEFAULT_FOOTER_CLASS`]
+#[must_use]
+pub fn format_footer(version: &Version) -> String {
+    format_footer_with_class(version, DEFAULT_FOOTER_CLASS)
+}
+
+/// Single-line footer:
+///
+/// ```text
+/// <footer class="sw-footer">Version 0.1.0 | Copyright (c) 2025 ... | <a href="...">MIT License</a> | Build abc123d</footer>
+/// ```
+#[must_use]
+pub fn format_footer_with_class(version: &Version, css_class: &str) -> String {
+    format!(
+        r#"<footer class="{}">Version {} | {} | {} | Build {}</footer>"#,
+        escape(css_class),
+        escape(&version.version.to_string()),
+        copyright_html(&version.copyright),
+        license_link(version, None),
+        commit_link(version),
+    )
+}
+
+/// Structured footer with one element per field, for styling with CSS
+#[must_use]
+pub fn format_footer_rich(version: &Version, css_class: &str) -> String {
+    format!(
+        r#"<footer class="{class}">
+    <div class="version-info">
+        <span class="version">v{version}</span>
+        <span class="copyright">{copyright}</span>
+        {license}
+        <span class="build-info" title="{build}">Build {commit}</span>
+    </div>
+</footer>"#,
+        class = escape(css_class),
+        version = escape(&version.version.to_string()),
+        copyright = copyright_html(&version.copyright),
+        license = license_link(version, Some("license")),
+        build = escape(&version.build_info.to_string()),
+        commit = commit_link(version),
+    )
+}
+
+/// Escaped copyright; multi-line notices keep their line breaks
+fn copyright_html(copyright: &str) -> String {
+    copyright
+        .lines()
+        .map(escape)
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+
+fn license_link(version: &Version, class: Option<&str>) -> String {
+    let label = format!("{} License", escape(&version.license_name));
+    let class_attr = class
+        .map(|c| format!(r#" class="{}""#, escape(c)))
+        .unwrap_or_default();
+    if version.licenses.len() > 1 {
+        // One link per license of the SPDX expression
+        let linked = map_license_ids(&version.license_name, |token| {
+            match find(&version.licenses, token) {
+                Some(license) if !license.url.is_empty() => format!(
+                    r#"<a href="{}" rel="license">{}</a>"#,
+                    escape(&license.url),
+                    escape(token)
+                ),
+                _ => escape(token),
+            }
+        });
+        format!("<span{class_attr}>License: {linked}</span>")
+    } else if version.license_url.is_empty() {
+        format!("<span{class_attr}>{label}</span>")
+    } else {
+        format!(
+            r#"<a{class_attr} href="{}" rel="license">{label}</a>"#,
+            escape(&version.license_url)
+        )
+    }
+}
+
+fn commit_link(version: &Version) -> String {
+    let short_sha = escape(version.build_info.short_sha());
+    match &version.commit_url {
+        Some(url) => format!(r#"<a href="{}">{short_sha}</a>"#, escape(url)),
+        None => short_sha,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::{BuildInfo, License};
+
+    fn sample_version() -> Version {
+        Version::new(
+            "0.1.0".to_string(),
+            "Copyright (c) 2025 <Example> & \"Sons\"".to_string(),
+            "MIT".to_string(),
+            "https://github.com/example/repo/blob/main/LICENSE".to_string(),
+            BuildInfo::new(
+                "builder.local".to_string(),
+                "abc123def456".to_string(),
+                1_700_000_000_000,
+            ),
+        )
+        .with_repository("https://github.com/example/repo".to_string())
+        .with_commit_url(Some(
+            "https://github.com/example/repo/commit/abc123def456".to_string(),
+        ))
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape(r#"<a href="x">'&'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&#39;&amp;&#39;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn test_plain_footer() {
+        assert_eq!(
+            format_footer(&sample_version()),
+            "<footer class=\"sw-footer\">Version 0.1.0 | \
+             Copyright (c) 2025 &lt;Example&gt; &amp; &quot;Sons&quot; | \
+             <a href=\"https://github.com/example/repo/blob/main/LICENSE\" rel=\"license\">MIT License</a> | \
+             Build <a href=\"https://github.com/example/repo/commit/abc123def456\">abc123d</a></footer>"
+        );
+    }
+
+    #[test]
+    fn test_rich_footer() {
+        let html = format_footer_rich(&sample_version(), "app-footer");
+        assert!(html.starts_with("<footer class=\"app-footer\">"));
+        assert!(html.contains("<span class=\"version\">v0.1.0</span>"));
+        assert!(html.contains("&lt;Example&gt; &amp; &quot;Sons&quot;"));
+        assert!(html.contains(
+            "<a class=\"license\" href=\"https://github.com/example/repo/blob/main/LICENSE\""
+        ));
+        assert!(
+            html.contains("title=\"Build: abc123d @ builder.local (2023-11-14T22:13:20+00:00)\"")
+        );
+        assert!(html.ends_with("</footer>"));
+    }
+
+    #[test]
+    fn test_footer_without_repository_or_license_url() {
+        let mut version = sample_version();
+        version.repository = String::new();
+        version.commit_url = None;
+        version.license_url = String::new();
+        let html = format_footer(&version);
+        assert!(html.contains("<span>MIT License</span>"));
+        assert!(html.ends_with("Build abc123d</footer>"));
+    }
+
+    #[test]
+    fn test_footer_links_each_license() {
+        let version = sample_version().with_licenses(vec![
+            License::new("MIT".to_string(), "https://x/LICENSE-MIT".to_string()),
+            License::new(
+                "Apache-2.0".to_string(),
+                "https://x/LICENSE-APACHE".to_string(),
+            ),
+        ]);
+        let version = Version {
+            license_name: "MIT OR Apache-2.0".to_string(),
+            ..version
+        };
+        assert!(format_footer(&version).contains(
+            "<span>License: <a href=\"https://x/LICENSE-MIT\" rel=\"license\">MIT</a> OR \
+             <a href=\"https://x/LICENSE-APACHE\" rel=\"license\">Apache-2.0</a></span>"
+        ));
+    }
+
+    #[test]
+    fn test_multiline_copyright() {
+        let mut version = sample_version();
+        version.copyright = "Copyright (c) 2024 A\nCopyright (c) 2025 B".to_string();
+        assert!(format_footer(&version).contains("2024 A<br>Copyright (c) 2025 B"));
+    }
+}
@@ -0,0 +1,203 @@
+//! Build-time metadata as `'static` data.
+//!
+//! `build_info!()` expands to a `VersionInfo` built from the caller's
+//! `version_info.rs` constants. Every field is a `&'static` reference or a
+//! plain value, so it can initialise a `const` or `static` and be read
+//! without allocating; `to_version()` converts it into an owned `Version`
+//! when formatting is needed.
+
+use super::{BuildInfo, Dependency, License, SemVer, Version};
+use chrono::{DateTime, Utc};
+use std::fmt;
+
+/// (SPDX id, link, embedded text) of a license
+pub type LicenseRecord = (&'static str, &'static str, Option<&'static str>);
+
+/// (name, version, license, source, checksum) of a dependency from Cargo.lock
+pub type DependencyRecord = (
+    &'static str,
+    &'static str,
+    &'static str,
+    Option<&'static str>,
+    Option<&'static str>,
+);
+
+/// Version and build information of a binary, without allocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersionInfo {
+    /// Package name from Cargo.toml
+    pub name: &'static str,
+    pub version: &'static str,
+    pub copyright: &'static str,
+    /// License expression (e.g. "MIT OR Apache-2.0")
+    pub license_name: &'static str,
+    pub license_url: &'static str,
+    pub licenses: &'static [LicenseRecord],
+    pub repository: &'static str,
+    pub commit_url: Option<&'static str>,
+    pub build_host: &'static str,
+    pub commit_sha: &'static str,
+    /// Build time in milliseconds since the Unix epoch
+    pub build_timestamp_ms: i64,
+    pub git_branch: Option<&'static str>,
+    pub git_tag: Option<&'static str>,
+    pub git_describe: Option<&'static str>,
+    pub dirty: bool,
+    pub rustc_version: &'static str,
+    pub target: &'static str,
+    pub profile: &'static str,
+    pub opt_level: &'static str,
+    pub features: &'static [&'static str],
+    pub dependencies: &'static [DependencyRecord],
+    /// Release manifest for `--check-update` and `--self-update`
+    pub update_manifest: Option<&'static str>,
+    /// `false` if `update-check = false` was set at build time
+    pub update_check: bool,
+    /// Base64 ed25519 key self-updates must be signed with
+    pub update_public_key: Option<&'static str>,
+}
+
+impl VersionInfo {
+    /// Commit SHA shortened to 7 characters
+    #[must_use]
+    pub fn short_sha(&self) -> &'static str {
+        self.commit_sha.get(..7).unwrap_or(self.commit_sha)
+    }
+
+    /// Build timestamp as a UTC datetime
+    #[must_use]
+    pub fn build_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_timestamp_millis(self.build_timestamp_ms)
+            .unwrap_or(DateTime::<Utc>::UNIX_EPOCH)
+    }
+
+    /// The version number parsed as a semantic version
+    #[must_use]
+    pub fn semver(&self) -> Option<SemVer> {
+        self.version.parse().ok()
+    }
+
+    /// Owned `Version` with the same data, for formatting and rendering
+    #[must_use]
+    pub fn to_version(&self) -> Version {
+        let build_info = BuildInfo::new(
+            self.build_host.to_string(),
+            self.commit_sha.to_string(),
+            self.build_timestamp_ms,
+        )
+        .with_git(
+            self.git_branch.map(str::to_string),
+            self.git_tag.map(str::to_string),
+            self.git_describe.map(str::to_string),
+            self.dirty,
+        )
+        .with_toolchain(
+            self.rustc_version.to_string(),
+            self.target.to_string(),
+            self.profile.to_string(),
+            self.opt_level.to_string(),
+            self.features.iter().map(ToString::to_string).collect(),
+        )
+        .with_dependencies(
+            self.dependencies
+                .iter()
+                .map(|(name, version, license, source, checksum)| {
+                    Dependency::new(
+                        (*name).to_string(),
+                        (*version).to_string(),
+                        (*license).to_string(),
+                    )
+                    .with_source(source.map(str::to_string), checksum.map(str::to_string))
+                })
+                .collect(),
+        );
+
+        Version::new(
+            self.version.to_string(),
+            self.copyright.to_string(),
+            self.license_name.to_string(),
+            self.license_url.to_string(),
+            build_info,
+        )
+        .with_name(self.name.to_string())
+        .with_repository(self.repository.to_string())
+        .with_commit_url(self.commit_url.map(str::to_string))
+        .with_licenses(
+            self.licenses
+                .iter()
+                .map(|(id, url, text)| {
+                    License::new((*id).to_string(), (*url).to_string())
+                        .with_text(text.map(str::to_string))
+                })
+                .collect(),
+        )
+    }
+}
+
+impl From<&VersionInfo> for Version {
+    fn from(info: &VersionInfo) -> Self {
+        info.to_version()
+    }
+}
+
+/// Same output as `Version`: the 4-line block, or the verbose one with `{:#}`
+impl fmt::Display for VersionInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_version(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INFO: VersionInfo = VersionInfo {
+        name: "demo",
+        version: "0.2.0-rc.1",
+        copyright: "Copyright (c) 2025 Example",
+        license_name: "MIT",
+        license_url: "https://example.com/LICENSE",
+        licenses: &[("MIT", "https://example.com/LICENSE", None)],
+        repository: "https://example.com/repo",
+        commit_url: Some("https://example.com/repo/commit/abc123def456"),
+        build_host: "builder",
+        commit_sha: "abc123def456",
+        build_timestamp_ms: 1_700_000_000_000,
+        git_branch: Some("main"),
+        git_tag: None,
+        git_describe: None,
+        dirty: false,
+        rustc_version: "rustc 1.91.0",
+        target: "x86_64-unknown-linux-gnu",
+        profile: "release",
+        opt_level: "3",
+        features: &["default"],
+        dependencies: &[("clap", "4.5.0", "MIT OR Apache-2.0", None, None)],
+        update_manifest: None,
+        update_check: true,
+        update_public_key: None,
+    };
+
+    #[test]
+    fn test_const_fields() {
+        static STATIC_INFO: VersionInfo = INFO;
+        assert_eq!(STATIC_INFO.short_sha(), "abc123d");
+        assert_eq!(INFO.semver(), Some("0.2.0-rc.1".parse().unwrap()));
+        assert_eq!(INFO.build_datetime().timestamp(), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_to_version() {
+        let version = INFO.to_version();
+        assert_eq!(version.name, "demo");
+        assert_eq!(version.build_info.short_sha(), "abc123d");
+        assert_eq!(version.build_info.features, ["default"]);
+        assert_eq!(version.build_info.dependencies[0].name, "clap");
+        assert_eq!(version.licenses[0].id, "MIT");
+        assert_eq!(INFO.to_string(), version.to_string());
+        assert!(
+            INFO.to_string()
+                .starts_with("Version: 0.2.0-rc.1 (pre-release)\n")
+        );
+    }
+}
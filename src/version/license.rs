@@ -0,0 +1,216 @@
+//! Licenses named in the package's SPDX license expression.
+
+use crate::output::Value;
+
+/// One license of an SPDX expression such as `MIT OR Apache-2.0`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct License {
+    /// SPDX identifier (e.g. "MIT", "Apache-2.0")
+    pub id: String,
+    /// Link to the license text (repository file or spdx.org)
+    pub url: String,
+    /// Full license text, if embedded with `define_build_info!(embed_licenses)`
+    pub text: Option<String>,
+}
+
+impl License {
+    #[must_use]
+    pub fn new(id: String, url: String) -> Self {
+        Self {
+            id,
+            url,
+            text: None,
+        }
+    }
+
+    /// Attach the embedded license text
+    #[must_use]
+    pub fn with_text(mut self, text: Option<String>) -> Self {
+        self.text = text;
+        self
+    }
+
+    /// Structured representation; the text is only included when asked for
+    #[must_use]
+    pub fn to_value(&self, include_text: bool) -> Value {
+        let value = Value::map()
+            .with("id", self.id.as_str())
+            .with("url", self.url.as_str());
+        if include_text {
+            value.with("text", self.text.as_deref())
+        } else {
+            value
+        }
+    }
+}
+
+/// A third-party crate compiled into the binary, from Cargo.lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub name: String,
+    pub version: String,
+    /// SPDX expression from the crate's manifest, or `unknown`
+    pub license: String,
+    /// Cargo.lock source (`registry+...`, `git+...`); `None` for path crates
+    pub source: Option<String>,
+    /// SHA-256 of the `.crate` file from Cargo.lock (registry crates only)
+    pub checksum: Option<String>,
+}
+
+impl Dependency {
+    #[must_use]
+    pub fn new(name: String, version: String, license: String) -> Self {
+        Self {
+            name,
+            version,
+            license,
+            source: None,
+            checksum: None,
+        }
+    }
+
+    /// Attach the Cargo.lock source and checksum
+    #[must_use]
+    pub fn with_source(mut self, source: Option<String>, checksum: Option<String>) -> Self {
+        self.source = source;
+        self.checksum = checksum;
+        self
+    }
+
+    /// Package URL, e.g. `pkg:cargo/clap@4.5.0`
+    #[must_use]
+    pub fn purl(&self) -> String {
+        format!("pkg:cargo/{}@{}", self.name, self.version)
+    }
+
+    #[must_use]
+    pub fn to_value(&self) -> Value {
+        Value::map()
+            .with("name", self.name.as_str())
+            .with("version", self.version.as_str())
+            .with("license", self.license.as_str())
+    }
+}
+
+/// Dependency notice table with aligned name, version and license columns
+#[must_use]
+pub fn format_notices(dependencies: &[Dependency]) -> String {
+    let name_width = dependencies.iter().map(|d| d.name.len()).max().unwrap_or(0);
+    let version_width = dependencies
+        .iter()
+        .map(|d| d.version.len())
+        .max()
+        .unwrap_or(0);
+    dependencies
+        .iter()
+        .map(|d| {
+            format!(
+                "{:name_width$}  {:version_width$}  {}",
+                d.name, d.version, d.license
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The expression with each license followed by its link:
+/// `MIT (https://...) OR Apache-2.0 (https://...)`
+#[must_use]
+pub fn linked_expression(expression: &str, licenses: &[License]) -> String {
+    map_license_ids(expression, |token| match find(licenses, token) {
+        Some(license) if !license.url.is_empty() => format!("{token} ({})", license.url),
+        _ => token.to_string(),
+    })
+}
+
+/// License for an expression token; `GPL-2.0+` is found as `GPL-2.0`
+pub(crate) fn find<'a>(licenses: &'a [License], token: &str) -> Option<&'a License> {
+    let id = token.trim_end_matches('+');
+    licenses.iter().find(|l| l.id == id)
+}
+
+/// Rewrite every license token in an SPDX expression, keeping operators,
+/// parentheses and `WITH` exceptions as they are
+pub(crate) fn map_license_ids(expression: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut out = String::with_capacity(expression.len());
+    let mut after_with = false;
+    for (i, word) in expression.split(' ').enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        let open = word.len() - word.trim_start_matches('(').len();
+        let close = word.len() - word.trim_end_matches(')').len();
+        let core = &word[open..word.len().saturating_sub(close).max(open)];
+        out.push_str(&word[..open]);
+        if core.is_empty() || after_with || ["AND", "OR", "WITH"].contains(&core) {
+            out.push_str(core);
+        } else {
+            out.push_str(&f(core));
+        }
+        out.push_str(&word[word.len() - close..]);
+        after_with = core == "WITH";
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn licenses() -> Vec<License> {
+        vec![
+            License::new("MIT".to_string(), "https://x/LICENSE-MIT".to_string()),
+            License::new(
+                "Apache-2.0".to_string(),
+                "https://x/LICENSE-APACHE".to_string(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_linked_expression() {
+        assert_eq!(
+            linked_expression("MIT OR Apache-2.0", &licenses()),
+            "MIT (https://x/LICENSE-MIT) OR Apache-2.0 (https://x/LICENSE-APACHE)"
+        );
+        assert_eq!(
+            linked_expression("(MIT OR Apache-2.0+) AND Zlib", &licenses()),
+            "(MIT (https://x/LICENSE-MIT) OR Apache-2.0+ (https://x/LICENSE-APACHE)) AND Zlib"
+        );
+    }
+
+    #[test]
+    fn test_format_notices() {
+        let dependencies = vec![
+            Dependency::new(
+                "clap".to_string(),
+                "4.5.0".to_string(),
+                "MIT OR Apache-2.0".to_string(),
+            ),
+            Dependency::new(
+                "unicode-ident".to_string(),
+                "1.0.12".to_string(),
+                "unknown".to_string(),
+            ),
+        ];
+        assert_eq!(
+            format_notices(&dependencies),
+            "clap           4.5.0   MIT OR Apache-2.0\n\
+             unicode-ident  1.0.12  unknown"
+        );
+        assert_eq!(format_notices(&[]), "");
+    }
+
+    #[test]
+    fn test_exceptions_are_not_linked() {
+        let licenses = vec![License::new("Apache-2.0".to_string(), "u".to_string())];
+        assert_eq!(
+            map_license_ids("Apache-2.0 WITH LLVM-exception", |id| format!("<{id}>")),
+            "<Apache-2.0> WITH LLVM-exception"
+        );
+        assert_eq!(
+            linked_expression("Apache-2.0 WITH LLVM-exception", &licenses),
+            "Apache-2.0 (u) WITH LLVM-exception"
+        );
+    }
+}
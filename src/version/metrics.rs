@@ -0,0 +1,134 @@
+//! Prometheus exposition of [`Version`] as a `build_info` gauge.
+//!
+//! The output is `OpenMetrics` 1.0 text ending in `# EOF`. The Prometheus
+//! 0.0.4 text format treats that line as a comment, so the same body can be
+//! served with either content type.
+
+use super::Version;
+use std::fmt::Write as _;
+
+/// Content type of [`render`] output for scrapers that ask for `OpenMetrics`
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Content type of [`render`] output for scrapers that only accept the
+/// Prometheus text format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Metric name used by [`render`]
+pub const DEFAULT_METRIC: &str = "build_info";
+
+/// Escape a label value: backslash, double quote and line feed
+#[must_use]
+pub fn escape_label(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Labels of the gauge: `version`, `commit`, `host` and `rustc`
+#[must_use]
+pub fn labels(version: &Version) -> Vec<(&'static str, String)> {
+    let info = &version.build_info;
+    // "rustc 1.91.0 (f8297e351 2025-10-28)" -> "1.91.0"
+    let rustc = info
+        .rustc_version
+        .strip_prefix("rustc ")
+        .unwrap_or(&info.rustc_version)
+        .split_whitespace()
+        .next()
+        .unwrap_or_default();
+    vec![
+        ("version", version.version.to_string()),
+        ("commit", info.commit_sha.clone()),
+        ("host", info.build_host.clone()),
+        ("rustc", rustc.to_string()),
+    ]
+}
+
+/// `OpenMetrics` text with a `build_info` gauge whose value is always 1
+#[must_use]
+pub fn render(version: &Version) -> String {
+    render_named(version, DEFAULT_METRIC)
+}
+
+/// Like [`render`] with a custom metric name, e.g. `myapp_build_info`
+/// when several binaries share a job
+#[must_use]
+pub fn render_named(version: &Version, metric: &str) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# TYPE {metric} gauge");
+    let _ = writeln!(
+        out,
+        "# HELP {metric} Build information of the running binary."
+    );
+    let labels = labels(version)
+        .iter()
+        .map(|(name, value)| format!("{name}=\"{}\"", escape_label(value)))
+        .collect::<Vec<_>>()
+        .join(",");
+    let _ = writeln!(out, "{metric}{{{labels}}} 1");
+    out.push_str("# EOF\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::BuildInfo;
+
+    fn sample_version() -> Version {
+        Version::new(
+            "0.3.0".to_string(),
+            "Copyright (c) 2025 Example Corp".to_string(),
+            "MIT".to_string(),
+            String::new(),
+            BuildInfo::new(
+                "builder".to_string(),
+                "abc123def456".to_string(),
+                1_700_000_000_000,
+            )
+            .with_toolchain(
+                "rustc 1.91.0 (f8297e351 2025-10-28)".to_string(),
+                "x86_64-unknown-linux-gnu".to_string(),
+                "release".to_string(),
+                "3".to_string(),
+                Vec::new(),
+            ),
+        )
+        .with_name("example".to_string())
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render(&sample_version()),
+            "# TYPE build_info gauge\n\
+             # HELP build_info Build information of the running binary.\n\
+             build_info{version=\"0.3.0\",commit=\"abc123def456\",host=\"builder\",rustc=\"1.91.0\"} 1\n\
+             # EOF\n"
+        );
+        assert!(
+            render_named(&sample_version(), "example_build_info")
+                .contains("\nexample_build_info{version=\"0.3.0\",")
+        );
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label(r#"a\b"c"#), r#"a\\b\"c"#);
+        assert_eq!(escape_label("line\nbreak"), "line\\nbreak");
+
+        let mut version = sample_version();
+        version.build_info.build_host = "odd \"host\"".to_string();
+        version.build_info.rustc_version = String::new();
+        assert!(render(&version).contains(r#"host="odd \"host\"",rustc=""}"#));
+    }
+}
@@ -0,0 +1,898 @@
+pub mod html;
+pub mod info;
+pub mod license;
+pub mod metrics;
+pub mod sbom;
+pub mod semver;
+#[cfg(feature = "metrics-server")]
+pub mod server;
+
+pub use info::VersionInfo;
+pub use license::{Dependency, License};
+pub use semver::{SemVer, VersionReq};
+
+use crate::output::{OutputFormat, Value};
+use chrono::{DateTime, Utc};
+use std::env;
+use std::fmt;
+use std::fmt::Write as _;
+
+/// Build information captured at compile time
+#[derive(Debug, Clone)]
+pub struct BuildInfo {
+    /// Hostname where the binary was built
+    pub build_host: String,
+    /// Git commit SHA of the build
+    pub commit_sha: String,
+    /// Build timestamp in milliseconds since epoch
+    pub build_timestamp_ms: i64,
+    /// Branch checked out at build time (`None` for a detached HEAD)
+    pub git_branch: Option<String>,
+    /// Nearest tag reachable from the commit
+    pub git_tag: Option<String>,
+    /// Output of `git describe --always --dirty`
+    pub git_describe: Option<String>,
+    /// Whether tracked files had uncommitted changes at build time
+    pub dirty: bool,
+    /// Output of `rustc --version` for the compiler that built the binary
+    pub rustc_version: String,
+    /// Target triple (e.g. `x86_64-unknown-linux-gnu`)
+    pub target: String,
+    /// Cargo profile (`debug` or `release`)
+    pub profile: String,
+    /// Optimization level (`0`-`3`, `s` or `z`)
+    pub opt_level: String,
+    /// Cargo features enabled for the package
+    pub features: Vec<String>,
+    /// Crates from Cargo.lock compiled into the binary (for `--licenses` and `--sbom`)
+    pub dependencies: Vec<Dependency>,
+}
+
+impl BuildInfo {
+    /// Create a new `BuildInfo` instance
+    #[must_use]
+    pub fn new(build_host: String, commit_sha: String, build_timestamp_ms: i64) -> Self {
+        Self {
+            build_host,
+            commit_sha,
+            build_timestamp_ms,
+            git_branch: None,
+            git_tag: None,
+            git_describe: None,
+            dirty: false,
+            rustc_version: String::new(),
+            target: String::new(),
+            profile: String::new(),
+            opt_level: String::new(),
+            features: Vec::new(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Attach branch, tag, `git describe` output and dirty flag
+    #[must_use]
+    pub fn with_git(
+        mut self,
+        branch: Option<String>,
+        tag: Option<String>,
+        describe: Option<String>,
+        dirty: bool,
+    ) -> Self {
+        self.git_branch = branch;
+        self.git_tag = tag;
+        self.git_describe = describe;
+        self.dirty = dirty;
+        self
+    }
+
+    /// Attach compiler version, target triple, profile, opt-level and features
+    #[must_use]
+    pub fn with_toolchain(
+        mut self,
+        rustc_version: String,
+        target: String,
+        profile: String,
+        opt_level: String,
+        features: Vec<String>,
+    ) -> Self {
+        self.rustc_version = rustc_version;
+        self.target = target;
+        self.profile = profile;
+        self.opt_level = opt_level;
+        self.features = features;
+        self
+    }
+
+    /// Attach the dependencies recorded from Cargo.lock
+    #[must_use]
+    pub fn with_dependencies(mut self, dependencies: Vec<Dependency>) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    /// Commit SHA shortened to 7 characters
+    #[must_use]
+    pub fn short_sha(&self) -> &str {
+        self.commit_sha.get(..7).unwrap_or(&self.commit_sha)
+    }
+
+    /// Build timestamp as a UTC datetime
+    #[must_use]
+    pub fn build_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_timestamp_millis(self.build_timestamp_ms)
+            .unwrap_or(DateTime::<Utc>::UNIX_EPOCH)
+    }
+
+    /// Structured representation used by the machine-readable formats.
+    /// Dependencies are left out; `--licenses` and `--sbom` report them.
+    #[must_use]
+    pub fn to_value(&self) -> Value {
+        Value::map()
+            .with(
+                "host",
+                (!self.build_host.is_empty()).then_some(self.build_host.as_str()),
+            )
+            .with("commit_sha", self.commit_sha.as_str())
+            .with("commit_sha_short", self.short_sha())
+            .with("timestamp", self.build_datetime().to_rfc3339())
+            .with("timestamp_epoch", self.build_timestamp_ms.div_euclid(1000))
+            .with("timestamp_ms", self.build_timestamp_ms)
+            .with("branch", self.git_branch.clone())
+            .with("tag", self.git_tag.clone())
+            .with("describe", self.git_describe.clone())
+            .with("dirty", self.dirty)
+            .with("rustc_version", self.rustc_version.as_str())
+            .with("target", self.target.as_str())
+            .with("profile", self.profile.as_str())
+            .with("opt_level", self.opt_level.as_str())
+            .with("features", self.features.clone())
+    }
+
+    /// Detail lines shown by `-V -v` after the standard build line
+    fn fmt_details(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Commit: {}", self.commit_sha)?;
+        let refs: Vec<String> = [
+            self.git_branch.as_ref().map(|b| format!("branch {b}")),
+            self.git_describe.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !refs.is_empty() {
+            write!(f, " ({})", refs.join(", "))?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Rustc: {}", self.rustc_version)?;
+        writeln!(f, "Target: {}", self.target)?;
+        writeln!(
+            f,
+            "Profile: {} (opt-level {})",
+            self.profile, self.opt_level
+        )?;
+        if self.features.is_empty() {
+            write!(f, "Features: (none)")
+        } else {
+            write!(f, "Features: {}", self.features.join(", "))
+        }
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Build: {}{}",
+            self.short_sha(),
+            if self.dirty { "-dirty" } else { "" }
+        )?;
+        // Reproducible builds may omit the host
+        if !self.build_host.is_empty() {
+            write!(f, " @ {}", self.build_host)?;
+        }
+        write!(f, " ({})", self.build_datetime().to_rfc3339())
+    }
+}
+
+/// A crate compiled into the binary with its own build information, listed
+/// in the component versions table of `-V -v`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    pub name: String,
+    pub version: String,
+    pub commit_sha: String,
+    pub dirty: bool,
+}
+
+impl Component {
+    #[must_use]
+    pub fn new(name: String, version: String, commit_sha: String, dirty: bool) -> Self {
+        Self {
+            name,
+            version,
+            commit_sha,
+            dirty,
+        }
+    }
+
+    /// Commit SHA shortened to 7 characters, with `-dirty` for modified trees
+    #[must_use]
+    pub fn short_sha(&self) -> String {
+        let sha = self.commit_sha.get(..7).unwrap_or(&self.commit_sha);
+        if self.dirty {
+            format!("{sha}-dirty")
+        } else {
+            sha.to_string()
+        }
+    }
+
+    #[must_use]
+    pub fn to_value(&self) -> Value {
+        Value::map()
+            .with("name", self.name.as_str())
+            .with("version", self.version.as_str())
+            .with("commit_sha", self.commit_sha.as_str())
+            .with("dirty", self.dirty)
+    }
+}
+
+impl From<&VersionInfo> for Component {
+    fn from(info: &VersionInfo) -> Self {
+        Self::new(
+            info.name.to_string(),
+            info.version.to_string(),
+            info.commit_sha.to_string(),
+            info.dirty,
+        )
+    }
+}
+
+impl From<&Version> for Component {
+    fn from(version: &Version) -> Self {
+        Self::new(
+            version.name.clone(),
+            version.version.to_string(),
+            version.build_info.commit_sha.clone(),
+            version.build_info.dirty,
+        )
+    }
+}
+
+/// Version information for the CLI application
+#[derive(Debug, Clone)]
+pub struct Version {
+    /// Package name from Cargo.toml (empty if unknown)
+    pub name: String,
+    /// Semantic version number (e.g., "0.1.0"), parsed when the `Version`
+    /// is created
+    pub version: SemVer,
+    /// Copyright notice
+    pub copyright: String,
+    /// License name (e.g., "MIT", "Apache-2.0")
+    pub license_name: String,
+    /// URL to the LICENSE file in the repository (empty if unknown)
+    pub license_url: String,
+    /// Each license in `license_name` with its own link
+    pub licenses: Vec<License>,
+    /// Repository URL from Cargo.toml (empty if unknown)
+    pub repository: String,
+    /// Link to the build's commit in the repository's web UI, if known
+    pub commit_url: Option<String>,
+    /// Build information
+    pub build_info: BuildInfo,
+    /// Libraries and plugins reporting their own build information
+    pub components: Vec<Component>,
+}
+
+impl Version {
+    /// Create a new Version instance. A version number that is not a
+    /// semantic version, such as `dev`, is kept as a pre-release of `0.0.0`
+    /// (`0.0.0-dev`), so it sorts before every release; use
+    /// [`Version::try_new`] to reject it instead.
+    // `version` stays a `String` so existing callers keep compiling
+    #[allow(clippy::needless_pass_by_value)]
+    #[must_use]
+    pub fn new(
+        version: String,
+        copyright: String,
+        license_name: String,
+        license_url: String,
+        build_info: BuildInfo,
+    ) -> Self {
+        let semver = version.parse().unwrap_or_else(|_| SemVer {
+            pre: prerelease_of(&version),
+            ..SemVer::new(0, 0, 0)
+        });
+        Self {
+            name: String::new(),
+            version: semver,
+            copyright,
+            license_name,
+            license_url,
+            licenses: Vec::new(),
+            repository: String::new(),
+            commit_url: None,
+            build_info,
+            components: Vec::new(),
+        }
+    }
+
+    /// Create a new Version instance from a version number that may not be
+    /// a semantic version
+    ///
+    /// # Errors
+    /// Returns a message if `version` is not a semantic version.
+    pub fn try_new(
+        version: &str,
+        copyright: String,
+        license_name: String,
+        license_url: String,
+        build_info: BuildInfo,
+    ) -> Result<Self, String> {
+        let semver = version.parse()?;
+        Ok(Self {
+            version: semver,
+            ..Self::new(
+                version.to_string(),
+                copyright,
+                license_name,
+                license_url,
+                build_info,
+            )
+        })
+    }
+
+    /// Set the package name
+    #[must_use]
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// Set the repository URL
+    #[must_use]
+    pub fn with_repository(mut self, repository: String) -> Self {
+        self.repository = repository;
+        self
+    }
+
+    /// Set the licenses of the SPDX expression in `license_name`
+    #[must_use]
+    pub fn with_licenses(mut self, licenses: Vec<License>) -> Self {
+        self.licenses = licenses;
+        self
+    }
+
+    /// Whether this is a pre-release build (e.g. `0.4.0-beta.1`)
+    #[must_use]
+    pub fn is_prerelease(&self) -> bool {
+        self.version.is_prerelease()
+    }
+
+    /// Check the version against a requirement such as `>=0.3`, as used by
+    /// `--min-version` guards and `requires = "..."` in config files.
+    ///
+    /// # Errors
+    /// Returns a message for the user if the requirement is invalid or not
+    /// met.
+    pub fn require(&self, requirement: &str) -> Result<(), String> {
+        let req: VersionReq = requirement.parse()?;
+        let version = &self.version;
+        if req.matches(version) {
+            Ok(())
+        } else {
+            Err(format!(
+                "version {version} does not satisfy the requirement {req}"
+            ))
+        }
+    }
+
+    /// License line of the text output. A single license keeps the classic
+    /// `MIT License: <url>`; expressions link every license they name.
+    #[must_use]
+    pub fn license_line(&self) -> String {
+        if self.licenses.len() > 1 {
+            format!(
+                "License: {}",
+                license::linked_expression(&self.license_name, &self.licenses)
+            )
+        } else {
+            format!("{} License: {}", self.license_name, self.license_url)
+        }
+    }
+
+    /// Set the commit link; the build script generates it in the
+    /// repository forge's URL layout
+    #[must_use]
+    pub fn with_commit_url(mut self, commit_url: Option<String>) -> Self {
+        self.commit_url = commit_url;
+        self
+    }
+
+    /// Attach the component versions table
+    #[must_use]
+    pub fn with_components(mut self, components: Vec<Component>) -> Self {
+        self.components = components;
+        self
+    }
+
+    /// Component versions table: the binary itself, then each component
+    #[must_use]
+    pub fn components_table(&self) -> String {
+        let rows: Vec<Component> = std::iter::once(Component::from(self))
+            .chain(self.components.iter().cloned())
+            .collect();
+        let name_width = rows.iter().map(|c| c.name.len()).max().unwrap_or(0);
+        let version_width = rows.iter().map(|c| c.version.len()).max().unwrap_or(0);
+        rows.iter().fold(String::new(), |mut table, c| {
+            let _ = writeln!(
+                table,
+                "  {:name_width$}  {:version_width$}  @ {}",
+                c.name,
+                c.version,
+                c.short_sha()
+            );
+            table
+        })
+    }
+
+    /// Structured representation used by the machine-readable formats.
+    ///
+    /// Field names are part of the public interface; scripts rely on them.
+    /// `components` is only present when components were attached.
+    #[must_use]
+    pub fn to_value(&self) -> Value {
+        let value = Value::map()
+            .with("version", self.version.to_string())
+            .with("pre_release", self.is_prerelease())
+            .with("copyright", self.copyright.as_str())
+            .with("license_name", self.license_name.as_str())
+            .with("license_url", self.license_url.as_str())
+            .with(
+                "licenses",
+                self.licenses
+                    .iter()
+                    .map(|l| l.to_value(false))
+                    .collect::<Vec<_>>(),
+            )
+            .with("repository", self.repository.as_str())
+            .with("commit_url", self.commit_url.as_deref())
+            .with("build", self.build_info.to_value());
+        if self.components.is_empty() {
+            value
+        } else {
+            value.with(
+                "components",
+                self.components
+                    .iter()
+                    .map(Component::to_value)
+                    .collect::<Vec<_>>(),
+            )
+        }
+    }
+
+    /// Render the version in the given format.
+    ///
+    /// `Text` produces the standard 4-line block from the `Display` impl.
+    #[must_use]
+    pub fn render(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => self.to_string(),
+            format => self.to_value().render(format),
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.render(OutputFormat::Json)
+    }
+
+    #[must_use]
+    pub fn to_toml(&self) -> String {
+        self.render(OutputFormat::Toml)
+    }
+
+    #[must_use]
+    pub fn to_yaml(&self) -> String {
+        self.render(OutputFormat::Yaml)
+    }
+}
+
+/// The alternate form (`{:#}`) appends commit, toolchain and target details
+/// and any component versions after the standard 4 lines; it is what
+/// `-V -v` prints.
+/// Pre-release identifiers spelling `text`: characters outside
+/// `[0-9A-Za-z-]` become `-`, empty identifiers are dropped and numeric ones
+/// lose their leading zeros
+fn prerelease_of(text: &str) -> String {
+    let identifiers: Vec<String> = text
+        .trim()
+        .split('.')
+        .filter(|identifier| !identifier.is_empty())
+        .map(|identifier| {
+            if identifier.bytes().all(|b| b.is_ascii_digit()) {
+                let digits = identifier.trim_start_matches('0');
+                return if digits.is_empty() { "0" } else { digits }.to_string();
+            }
+            identifier
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+                .collect()
+        })
+        .collect();
+    if identifiers.is_empty() {
+        "unknown".to_string()
+    } else {
+        identifiers.join(".")
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_prerelease() {
+            writeln!(f, "Version: {} (pre-release)", self.version)?;
+        } else {
+            writeln!(f, "Version: {}", self.version)?;
+        }
+        writeln!(f, "{}", self.copyright)?;
+        writeln!(f, "{}", self.license_line())?;
+        write!(f, "{}", self.build_info)?;
+        if f.alternate() {
+            writeln!(f)?;
+            self.build_info.fmt_details(f)?;
+            if !self.components.is_empty() {
+                write!(f, "\nComponents:\n{}", self.components_table().trim_end())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check command line arguments for version flags (-V or --version).
+/// Returns true if a version flag was found in any position.
+///
+/// This function checks all command line arguments (not just the first one)
+/// to handle cases where flags are combined in different orders, e.g.:
+/// - `mycli -V`
+/// - `mycli -V -v -n`
+/// - `mycli -v -V -n`
+/// - `mycli --version`
+///
+/// # Examples
+///
+/// ```no_run
+/// use sw_cli::version::check_version_flag;
+///
+/// fn main() {
+///     if check_version_flag() {
+///         println!("{}", sw_cli::version!());
+///         return;
+///     }
+///     // ... rest of CLI logic
+/// }
+/// ```
+#[must_use]
+pub fn check_version_flag() -> bool {
+    let args: Vec<String> = env::args().collect();
+    args.iter()
+        .skip(1)
+        .any(|arg| arg == "-V" || arg == "--version")
+}
+
+#[cfg(test)]
+// The original display tests are kept as written
+#[allow(clippy::unreadable_literal, clippy::uninlined_format_args)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info_display() {
+        let build_info = BuildInfo::new(
+            "builder.local".to_string(),
+            "abc123def456".to_string(),
+            1700000000000,
+        );
+
+        let output = format!("{}", build_info);
+        // 1700000000000ms = 2023-11-14T22:13:20Z, SHA shortened to 7 chars
+        assert_eq!(
+            output,
+            "Build: abc123d @ builder.local (2023-11-14T22:13:20+00:00)"
+        );
+    }
+
+    #[test]
+    fn test_build_info_display_dirty() {
+        let build_info = BuildInfo::new(
+            "builder.local".to_string(),
+            "abc123def456".to_string(),
+            1_700_000_000_000,
+        )
+        .with_git(
+            Some("main".to_string()),
+            Some("v0.1.0".to_string()),
+            Some("v0.1.0-3-gabc123d-dirty".to_string()),
+            true,
+        );
+
+        assert_eq!(
+            build_info.to_string(),
+            "Build: abc123d-dirty @ builder.local (2023-11-14T22:13:20+00:00)"
+        );
+
+        let json = build_info.to_value().to_json();
+        assert!(json.contains(r#""branch": "main""#));
+        assert!(json.contains(r#""tag": "v0.1.0""#));
+        assert!(json.contains(r#""describe": "v0.1.0-3-gabc123d-dirty""#));
+        assert!(json.contains(r#""dirty": true"#));
+    }
+
+    #[test]
+    fn test_build_info_display_without_host() {
+        let build_info = BuildInfo::new(String::new(), "abc123def456".to_string(), 0);
+        assert_eq!(
+            build_info.to_string(),
+            "Build: abc123d (1970-01-01T00:00:00+00:00)"
+        );
+        assert!(build_info.to_value().to_json().contains(r#""host": null"#));
+    }
+
+    #[test]
+    fn test_version_display() {
+        let build_info = BuildInfo::new(
+            "builder.local".to_string(),
+            "abc123def456".to_string(),
+            1700000000000,
+        );
+
+        let version = Version::new(
+            "0.1.0".to_string(),
+            "Copyright (c) 2025 Example Corp".to_string(),
+            "MIT".to_string(),
+            "https://github.com/example/repo/blob/main/LICENSE".to_string(),
+            build_info,
+        );
+
+        let output = format!("{}", version);
+        assert!(output.contains("Version: 0.1.0"));
+        assert!(output.contains("Copyright (c) 2025 Example Corp"));
+        assert!(output.contains("MIT License: https://github.com/example/repo"));
+        assert!(output.contains("Build: abc123d @ builder.local"));
+    }
+
+    fn sample_version() -> Version {
+        Version::new(
+            "0.1.0".to_string(),
+            "Copyright (c) 2025 Example Corp".to_string(),
+            "MIT".to_string(),
+            "https://github.com/example/repo/blob/main/LICENSE".to_string(),
+            BuildInfo::new(
+                "builder.local".to_string(),
+                "abc123def456".to_string(),
+                1_700_000_000_000,
+            ),
+        )
+    }
+
+    #[test]
+    fn test_version_json() {
+        let json = sample_version().to_json();
+        assert!(json.contains(r#""version": "0.1.0""#));
+        assert!(json.contains(r#""commit_sha": "abc123def456""#));
+        assert!(json.contains(r#""commit_sha_short": "abc123d""#));
+        assert!(json.contains(r#""timestamp": "2023-11-14T22:13:20+00:00""#));
+        assert!(json.contains(r#""timestamp_epoch": 1700000000"#));
+        assert!(json.contains(r#""timestamp_ms": 1700000000000"#));
+    }
+
+    #[test]
+    fn test_version_toml_and_yaml() {
+        let version = sample_version();
+        let toml = version.to_toml();
+        assert!(toml.starts_with("version = \"0.1.0\"\n"));
+        assert!(toml.contains("\n[build]\nhost = \"builder.local\"\n"));
+
+        let yaml = version.to_yaml();
+        assert!(yaml.contains("license_name: \"MIT\"\n"));
+        assert!(yaml.contains("build:\n  host: \"builder.local\"\n"));
+    }
+
+    #[test]
+    fn test_version_verbose_display() {
+        let mut version = sample_version();
+        version.build_info = version
+            .build_info
+            .with_git(
+                Some("main".to_string()),
+                None,
+                Some("abc123d".to_string()),
+                false,
+            )
+            .with_toolchain(
+                "rustc 1.91.0 (f8297e351 2025-10-28)".to_string(),
+                "x86_64-unknown-linux-gnu".to_string(),
+                "release".to_string(),
+                "3".to_string(),
+                vec!["default".to_string()],
+            );
+
+        // Default output stays at 4 lines
+        assert_eq!(version.to_string().lines().count(), 4);
+
+        let verbose = format!("{version:#}");
+        assert!(verbose.starts_with(&version.to_string()));
+        assert!(verbose.ends_with(
+            "\nCommit: abc123def456 (branch main, abc123d)\n\
+             Rustc: rustc 1.91.0 (f8297e351 2025-10-28)\n\
+             Target: x86_64-unknown-linux-gnu\n\
+             Profile: release (opt-level 3)\n\
+             Features: default"
+        ));
+    }
+
+    #[test]
+    fn test_components_table() {
+        let version = sample_version()
+            .with_name("mytool".to_string())
+            .with_components(vec![Component::new(
+                "mytool-core".to_string(),
+                "0.9.1".to_string(),
+                "def4567890ab".to_string(),
+                true,
+            )]);
+        assert!(format!("{version:#}").ends_with(
+            "\nComponents:\n  \
+             mytool       0.1.0  @ abc123d\n  \
+             mytool-core  0.9.1  @ def4567-dirty"
+        ));
+        // The standard output is unchanged
+        assert_eq!(version.to_string().lines().count(), 4);
+
+        let json = version.to_json();
+        assert!(json.contains("\"components\": [\n    {\n      \"name\": \"mytool-core\""));
+        assert!(!sample_version().to_json().contains("components"));
+    }
+
+    #[test]
+    fn test_commit_url() {
+        let version = sample_version();
+        assert_eq!(version.commit_url, None);
+        assert!(version.to_json().contains("\"commit_url\": null"));
+
+        let version = version.with_commit_url(Some(
+            "https://gitlab.com/example/repo/-/commit/abc123def456".to_string(),
+        ));
+        assert!(
+            version.to_json().contains(
+                "\"commit_url\": \"https://gitlab.com/example/repo/-/commit/abc123def456\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_license_expression_links_each_license() {
+        let mut version = sample_version().with_licenses(vec![
+            License::new("MIT".to_string(), "https://x/LICENSE-MIT".to_string()),
+            License::new(
+                "Apache-2.0".to_string(),
+                "https://x/LICENSE-APACHE".to_string(),
+            ),
+        ]);
+        version.license_name = "MIT OR Apache-2.0".to_string();
+
+        let text = version.to_string();
+        assert_eq!(text.lines().count(), 4);
+        assert_eq!(
+            text.lines().nth(2),
+            Some("License: MIT (https://x/LICENSE-MIT) OR Apache-2.0 (https://x/LICENSE-APACHE)")
+        );
+        assert!(version.to_json().contains(r#""id": "Apache-2.0","#));
+    }
+
+    #[test]
+    fn test_single_license_line_is_unchanged() {
+        let version = sample_version().with_licenses(vec![License::new(
+            "MIT".to_string(),
+            "https://github.com/example/repo/blob/main/LICENSE".to_string(),
+        )]);
+        assert_eq!(
+            version.license_line(),
+            "MIT License: https://github.com/example/repo/blob/main/LICENSE"
+        );
+    }
+
+    #[test]
+    fn test_dependencies_are_not_in_version_output() {
+        let mut version = sample_version();
+        version.build_info = version.build_info.with_dependencies(vec![Dependency::new(
+            "clap".to_string(),
+            "4.5.0".to_string(),
+            "MIT OR Apache-2.0".to_string(),
+        )]);
+        assert!(!version.to_json().contains("clap"));
+    }
+
+    #[test]
+    fn test_prerelease_display() {
+        let mut version = sample_version();
+        assert!(!version.is_prerelease());
+        version.version = "0.4.0-beta.1+ci.7".parse().unwrap();
+        assert!(version.is_prerelease());
+        assert!(
+            version
+                .to_string()
+                .starts_with("Version: 0.4.0-beta.1+ci.7 (pre-release)\n")
+        );
+        assert!(version.to_json().contains(r#""pre_release": true"#));
+    }
+
+    #[test]
+    fn test_require() {
+        let version = sample_version();
+        assert_eq!(version.version, SemVer::new(0, 1, 0));
+        assert!(version.require(">=0.1").is_ok());
+        assert_eq!(
+            version.require(">=0.3"),
+            Err("version 0.1.0 does not satisfy the requirement >=0.3".to_string())
+        );
+        assert!(version.require("at least 0.3").is_err());
+    }
+
+    #[test]
+    fn test_version_is_parsed_once() {
+        let build_info = || BuildInfo::new(String::new(), String::new(), 0);
+        let version = Version::try_new(
+            "v1.2.3-rc.1",
+            String::new(),
+            "MIT".to_string(),
+            String::new(),
+            build_info(),
+        )
+        .unwrap();
+        assert_eq!(version.version.to_string(), "1.2.3-rc.1");
+
+        let err = Version::try_new(
+            "dev",
+            String::new(),
+            "MIT".to_string(),
+            String::new(),
+            build_info(),
+        )
+        .unwrap_err();
+        assert_eq!(err, "invalid version 'dev': expected MAJOR.MINOR.PATCH");
+
+        // `new` keeps other version numbers as pre-releases of 0.0.0
+        let version = Version::new(
+            "dev".to_string(),
+            String::new(),
+            "MIT".to_string(),
+            String::new(),
+            build_info(),
+        );
+        assert_eq!(version.version.to_string(), "0.0.0-dev");
+        assert!(version.is_prerelease());
+        assert!(version.version < SemVer::new(0, 0, 1));
+        let version = Version::new(
+            "nightly 2025/01".to_string(),
+            String::new(),
+            "MIT".to_string(),
+            String::new(),
+            build_info(),
+        );
+        assert_eq!(version.version.to_string(), "0.0.0-nightly-2025-01");
+        assert!(version.version.to_string().parse::<SemVer>().is_ok());
+        let version = Version::new(
+            "2025.01..".to_string(),
+            String::new(),
+            "MIT".to_string(),
+            String::new(),
+            build_info(),
+        );
+        assert_eq!(version.version.to_string(), "0.0.0-2025.1");
+    }
+
+    #[test]
+    fn test_version_render_text_matches_display() {
+        let version = sample_version();
+        assert_eq!(version.render(OutputFormat::Text), version.to_string());
+    }
+}
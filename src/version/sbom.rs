@@ -0,0 +1,438 @@
+//! Software bill of materials for the binary, built from the `version_info.rs`
+//! data and the dependencies recorded from Cargo.lock.
+//!
+//! Both formats are JSON: `CycloneDX` 1.5 and SPDX 2.3. Output is derived only
+//! from build-time data, so the same binary always produces the same document.
+
+use super::{Dependency, Version};
+use crate::output::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// SBOM format selected with `--sbom <FORMAT>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum SbomFormat {
+    #[default]
+    CycloneDx,
+    Spdx,
+}
+
+impl SbomFormat {
+    /// Names accepted by `--sbom`
+    pub const NAMES: [&'static str; 2] = ["cyclonedx", "spdx"];
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CycloneDx => "cyclonedx",
+            Self::Spdx => "spdx",
+        }
+    }
+}
+
+impl FromStr for SbomFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "cyclonedx" | "cdx" => Ok(Self::CycloneDx),
+            "spdx" => Ok(Self::Spdx),
+            _ => Err(format!(
+                "unknown SBOM format '{s}' (expected one of: {})",
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SbomFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Render the SBOM as a JSON document
+#[must_use]
+pub fn render(version: &Version, format: SbomFormat) -> String {
+    match format {
+        SbomFormat::CycloneDx => cyclonedx(version).to_json(),
+        SbomFormat::Spdx => spdx(version).to_json(),
+    }
+}
+
+/// `CycloneDX` 1.5 document
+#[must_use]
+pub fn cyclonedx(version: &Version) -> Value {
+    let build = &version.build_info;
+    let root_purl = purl(&version.name, &version.version.to_string());
+
+    let mut component = Value::map()
+        .with("type", "application")
+        .with("bom-ref", root_purl.as_str())
+        .with("name", version.name.as_str())
+        .with("version", version.version.to_string())
+        .with("copyright", version.copyright.as_str())
+        .with("purl", root_purl.as_str())
+        .with("licenses", cyclonedx_licenses(&version.license_name));
+    if !version.repository.is_empty() {
+        component = component.with(
+            "externalReferences",
+            vec![
+                Value::map()
+                    .with("type", "vcs")
+                    .with("url", version.repository.as_str()),
+            ],
+        );
+    }
+
+    let properties: Vec<Value> = [
+        ("sw-cli:commit", Some(build.commit_sha.clone())),
+        ("sw-cli:describe", build.git_describe.clone()),
+        ("sw-cli:dirty", Some(build.dirty.to_string())),
+        ("sw-cli:rustc", Some(build.rustc_version.clone())),
+        ("sw-cli:target", Some(build.target.clone())),
+        ("sw-cli:profile", Some(build.profile.clone())),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| {
+        value
+            .filter(|v| !v.is_empty())
+            .map(|v| Value::map().with("name", name).with("value", v))
+    })
+    .collect();
+
+    let components: Vec<Value> = build
+        .dependencies
+        .iter()
+        .map(|dep| {
+            let mut value = Value::map()
+                .with("type", "library")
+                .with("bom-ref", dep.purl())
+                .with("name", dep.name.as_str())
+                .with("version", dep.version.as_str())
+                .with("purl", dep.purl())
+                .with("licenses", cyclonedx_licenses(&dep.license));
+            if let Some(checksum) = &dep.checksum {
+                value = value.with(
+                    "hashes",
+                    vec![
+                        Value::map()
+                            .with("alg", "SHA-256")
+                            .with("content", checksum.as_str()),
+                    ],
+                );
+            }
+            value
+        })
+        .collect();
+
+    Value::map()
+        .with("bomFormat", "CycloneDX")
+        .with("specVersion", "1.5")
+        .with("version", 1)
+        .with(
+            "metadata",
+            Value::map()
+                .with("timestamp", timestamp(version))
+                .with("tools", vec![tool()])
+                .with("component", component)
+                .with("properties", properties),
+        )
+        .with("components", components)
+        .with(
+            "dependencies",
+            vec![
+                Value::map().with("ref", root_purl.as_str()).with(
+                    "dependsOn",
+                    build
+                        .dependencies
+                        .iter()
+                        .map(Dependency::purl)
+                        .collect::<Vec<_>>(),
+                ),
+            ],
+        )
+}
+
+/// SPDX 2.3 document
+#[must_use]
+pub fn spdx(version: &Version) -> Value {
+    let build = &version.build_info;
+    let root_id = spdx_id(&version.name, &version.version.to_string());
+    let download = if version.repository.is_empty() {
+        "NOASSERTION".to_string()
+    } else {
+        format!("git+{}", version.repository)
+    };
+
+    let mut packages = vec![
+        spdx_package(
+            &root_id,
+            &version.name,
+            &version.version.to_string(),
+            &version.license_name,
+            &download,
+            None,
+        )
+        .with("copyrightText", version.copyright.as_str()),
+    ];
+    let mut relationships = vec![
+        Value::map()
+            .with("spdxElementId", "SPDXRef-DOCUMENT")
+            .with("relationshipType", "DESCRIBES")
+            .with("relatedSpdxElement", root_id.as_str()),
+    ];
+    for dep in &build.dependencies {
+        let id = spdx_id(&dep.name, &dep.version);
+        packages.push(spdx_package(
+            &id,
+            &dep.name,
+            &dep.version,
+            &dep.license,
+            &dependency_download(dep),
+            dep.checksum.as_deref(),
+        ));
+        relationships.push(
+            Value::map()
+                .with("spdxElementId", root_id.as_str())
+                .with("relationshipType", "DEPENDS_ON")
+                .with("relatedSpdxElement", id),
+        );
+    }
+
+    // Unique per build: the same name and version can be rebuilt from other commits
+    let namespace = format!(
+        "https://spdx.org/spdxdocs/{}-{}-{}-{}",
+        version.name, version.version, build.commit_sha, build.build_timestamp_ms
+    );
+
+    Value::map()
+        .with("spdxVersion", "SPDX-2.3")
+        .with("dataLicense", "CC0-1.0")
+        .with("SPDXID", "SPDXRef-DOCUMENT")
+        .with("name", format!("{}-{}", version.name, version.version))
+        .with("documentNamespace", namespace)
+        .with(
+            "creationInfo",
+            Value::map().with("created", timestamp(version)).with(
+                "creators",
+                vec![format!("Tool: sw-cli-{}", env!("CARGO_PKG_VERSION"))],
+            ),
+        )
+        .with("packages", packages)
+        .with("relationships", relationships)
+}
+
+fn tool() -> Value {
+    Value::map()
+        .with("name", "sw-cli")
+        .with("version", env!("CARGO_PKG_VERSION"))
+}
+
+/// Build time in the `YYYY-MM-DDThh:mm:ssZ` form both formats accept
+fn timestamp(version: &Version) -> String {
+    version
+        .build_info
+        .build_datetime()
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+fn purl(name: &str, version: &str) -> String {
+    format!("pkg:cargo/{name}@{version}")
+}
+
+/// Whether a license string is an SPDX expression rather than a placeholder
+fn is_expression(license: &str) -> bool {
+    !license.is_empty()
+        && !matches!(license, "unknown" | "Unknown")
+        && !license.starts_with("Custom")
+}
+
+fn cyclonedx_licenses(license: &str) -> Vec<Value> {
+    if is_expression(license) {
+        vec![Value::map().with("expression", license)]
+    } else if license.starts_with("Custom") {
+        vec![Value::map().with("license", Value::map().with("name", license))]
+    } else {
+        Vec::new()
+    }
+}
+
+/// `SPDXRef-<name>-<version>`, limited to the characters SPDX ids allow
+fn spdx_id(name: &str, version: &str) -> String {
+    let id: String = format!("{name}-{version}")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("SPDXRef-Package-{id}")
+}
+
+fn spdx_package(
+    id: &str,
+    name: &str,
+    version: &str,
+    license: &str,
+    download: &str,
+    checksum: Option<&str>,
+) -> Value {
+    let declared = if is_expression(license) {
+        license
+    } else {
+        "NOASSERTION"
+    };
+    let mut package = Value::map()
+        .with("SPDXID", id)
+        .with("name", name)
+        .with("versionInfo", version)
+        .with("downloadLocation", download)
+        .with("filesAnalyzed", false)
+        .with("licenseConcluded", "NOASSERTION")
+        .with("licenseDeclared", declared)
+        .with(
+            "externalRefs",
+            vec![
+                Value::map()
+                    .with("referenceCategory", "PACKAGE-MANAGER")
+                    .with("referenceType", "purl")
+                    .with("referenceLocator", purl(name, version)),
+            ],
+        );
+    if let Some(checksum) = checksum {
+        package = package.with(
+            "checksums",
+            vec![
+                Value::map()
+                    .with("algorithm", "SHA256")
+                    .with("checksumValue", checksum),
+            ],
+        );
+    }
+    package
+}
+
+/// Where a dependency can be downloaded from, in SPDX `downloadLocation` form
+fn dependency_download(dep: &Dependency) -> String {
+    match dep.source.as_deref() {
+        Some(
+            "registry+https://github.com/rust-lang/crates.io-index"
+            | "sparse+https://index.crates.io/",
+        ) => {
+            format!(
+                "https://crates.io/api/v1/crates/{}/{}/download",
+                dep.name, dep.version
+            )
+        }
+        // git+https://host/repo?branch=main#<commit> -> git+https://host/repo@<commit>
+        Some(source) if source.starts_with("git+") => {
+            let (url, commit) = source.split_once('#').unwrap_or((source, ""));
+            let url = url.split('?').next().unwrap_or(url);
+            if commit.is_empty() {
+                url.to_string()
+            } else {
+                format!("{url}@{commit}")
+            }
+        }
+        _ => "NOASSERTION".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::BuildInfo;
+
+    fn sample_version() -> Version {
+        Version::new(
+            "0.1.0".to_string(),
+            "Copyright (c) 2025 Example Corp".to_string(),
+            "MIT OR Apache-2.0".to_string(),
+            String::new(),
+            BuildInfo::new(String::new(), "abc123def456".to_string(), 1_700_000_000_000)
+                .with_dependencies(vec![
+                    Dependency::new(
+                        "clap".to_string(),
+                        "4.5.0".to_string(),
+                        "MIT OR Apache-2.0".to_string(),
+                    )
+                    .with_source(
+                        Some("registry+https://github.com/rust-lang/crates.io-index".to_string()),
+                        Some("0123abcd".to_string()),
+                    ),
+                    Dependency::new(
+                        "local_dep".to_string(),
+                        "0.2.0".to_string(),
+                        "unknown".to_string(),
+                    ),
+                ]),
+        )
+        .with_name("example".to_string())
+        .with_repository("https://github.com/example/repo".to_string())
+    }
+
+    #[test]
+    fn test_sbom_format_from_str() {
+        assert_eq!("cyclonedx".parse(), Ok(SbomFormat::CycloneDx));
+        assert_eq!("SPDX".parse(), Ok(SbomFormat::Spdx));
+        assert!("xml".parse::<SbomFormat>().is_err());
+    }
+
+    #[test]
+    fn test_cyclonedx() {
+        let json = render(&sample_version(), SbomFormat::CycloneDx);
+        assert!(json.contains(r#""bomFormat": "CycloneDX""#));
+        assert!(json.contains(r#""specVersion": "1.5""#));
+        assert!(json.contains(r#""timestamp": "2023-11-14T22:13:20Z""#));
+        assert!(json.contains(r#""bom-ref": "pkg:cargo/example@0.1.0""#));
+        assert!(json.contains(r#""purl": "pkg:cargo/clap@4.5.0""#));
+        assert!(json.contains(r#""expression": "MIT OR Apache-2.0""#));
+        assert!(json.contains(r#""alg": "SHA-256""#));
+        assert!(json.contains(r#""content": "0123abcd""#));
+        assert!(json.contains(r#""value": "abc123def456""#));
+    }
+
+    #[test]
+    fn test_spdx() {
+        let json = render(&sample_version(), SbomFormat::Spdx);
+        assert!(json.contains(r#""spdxVersion": "SPDX-2.3""#));
+        assert!(json.contains(r#""created": "2023-11-14T22:13:20Z""#));
+        assert!(json.contains(
+            r#""documentNamespace": "https://spdx.org/spdxdocs/example-0.1.0-abc123def456-1700000000000""#
+        ));
+        assert!(json.contains(r#""SPDXID": "SPDXRef-Package-local-dep-0.2.0""#));
+        assert!(json.contains(r#""downloadLocation": "git+https://github.com/example/repo""#));
+        assert!(json.contains(
+            r#""downloadLocation": "https://crates.io/api/v1/crates/clap/4.5.0/download""#
+        ));
+        assert!(json.contains(r#""checksumValue": "0123abcd""#));
+        assert!(json.contains(r#""licenseDeclared": "NOASSERTION""#));
+        assert_eq!(
+            json.matches(r#""relationshipType": "DEPENDS_ON""#).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_git_download_location() {
+        let dep = Dependency::new("a".to_string(), "1.0.0".to_string(), "MIT".to_string())
+            .with_source(
+                Some("git+https://github.com/a/a?branch=main#0123abcd".to_string()),
+                None,
+            );
+        assert_eq!(
+            dependency_download(&dep),
+            "git+https://github.com/a/a@0123abcd"
+        );
+    }
+}
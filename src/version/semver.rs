@@ -0,0 +1,475 @@
+//! Semantic versions (<https://semver.org>) and Cargo-style version requirements.
+//!
+//! `SemVer` orders versions by precedence: pre-releases sort before the
+//! release (`1.0.0-rc.1 < 1.0.0`) and build metadata only breaks ties.
+//! `VersionReq` understands the requirement syntax of Cargo.toml
+//! (`>=0.3`, `^1.2`, `~1.2.3`, `=1.0.0`, `1.*`, comma-separated lists).
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// A parsed `MAJOR.MINOR.PATCH[-PRE][+BUILD]` version
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SemVer {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    /// Pre-release identifiers without the leading `-` (empty for releases)
+    pub pre: String,
+    /// Build metadata without the leading `+` (empty if none)
+    pub build: String,
+}
+
+impl SemVer {
+    #[must_use]
+    pub const fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            pre: String::new(),
+            build: String::new(),
+        }
+    }
+
+    /// Whether this is a pre-release such as `1.0.0-beta.2`
+    #[must_use]
+    pub fn is_prerelease(&self) -> bool {
+        !self.pre.is_empty()
+    }
+
+    /// Compare by semver precedence, ignoring build metadata
+    #[must_use]
+    pub fn cmp_precedence(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| cmp_pre(&self.pre, &other.pre))
+    }
+
+    /// Whether `self` can stand in for `other` under Cargo's caret rules:
+    /// same major version (same minor for `0.x`, same patch for `0.0.x`)
+    /// and not older.
+    #[must_use]
+    pub fn is_compatible_with(&self, other: &Self) -> bool {
+        let same_series = match (other.major, other.minor) {
+            (0, 0) => self.major == 0 && self.minor == 0 && self.patch == other.patch,
+            (0, minor) => self.major == 0 && self.minor == minor,
+            (major, _) => self.major == major,
+        };
+        same_series && self.cmp_precedence(other) != Ordering::Less
+    }
+}
+
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_precedence(other)
+            .then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for SemVer {
+    type Err = String;
+
+    /// Parse a version; a leading `v` (as in git tags) is accepted
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let text = text.strip_prefix('v').unwrap_or(text);
+        let (rest, build) = match text.split_once('+') {
+            Some((rest, build)) => (rest, build),
+            None => (text, ""),
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, pre),
+            None => (rest, ""),
+        };
+
+        let parts: Vec<&str> = core.split('.').collect();
+        let [major, minor, patch] = parts.as_slice() else {
+            return Err(format!("invalid version '{s}': expected MAJOR.MINOR.PATCH"));
+        };
+        let version = Self {
+            major: numeric(major).ok_or_else(|| format!("invalid major version in '{s}'"))?,
+            minor: numeric(minor).ok_or_else(|| format!("invalid minor version in '{s}'"))?,
+            patch: numeric(patch).ok_or_else(|| format!("invalid patch version in '{s}'"))?,
+            pre: pre.to_string(),
+            build: build.to_string(),
+        };
+
+        if rest.contains('-') && !valid_identifiers(pre, true) {
+            return Err(format!("invalid pre-release '{pre}' in '{s}'"));
+        }
+        if text.contains('+') && !valid_identifiers(build, false) {
+            return Err(format!("invalid build metadata '{build}' in '{s}'"));
+        }
+        Ok(version)
+    }
+}
+
+impl fmt::Display for SemVer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.pre.is_empty() {
+            write!(f, "-{}", self.pre)?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build)?;
+        }
+        Ok(())
+    }
+}
+
+/// Numeric identifier without leading zeros
+fn numeric(s: &str) -> Option<u64> {
+    let valid =
+        !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) && (s == "0" || !s.starts_with('0'));
+    if valid { s.parse().ok() } else { None }
+}
+
+/// Dot-separated, non-empty `[0-9A-Za-z-]` identifiers; numeric
+/// pre-release identifiers may not have leading zeros
+fn valid_identifiers(s: &str, pre_release: bool) -> bool {
+    s.split('.').all(|id| {
+        !id.is_empty()
+            && id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+            && (!pre_release || !id.bytes().all(|b| b.is_ascii_digit()) || numeric(id).is_some())
+    })
+}
+
+/// Pre-release precedence: a release sorts after its pre-releases; numeric
+/// identifiers compare numerically and sort before alphanumeric ones.
+fn cmp_pre(a: &str, b: &str) -> Ordering {
+    match (a.is_empty(), b.is_empty()) {
+        (true, true) => return Ordering::Equal,
+        (true, false) => return Ordering::Greater,
+        (false, true) => return Ordering::Less,
+        (false, false) => {}
+    }
+    let mut left = a.split('.');
+    let mut right = b.split('.');
+    loop {
+        let ordering = match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => x.cmp(y),
+            },
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+}
+
+/// One comparator of a requirement; missing parts match anything
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+    op: Op,
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: String,
+}
+
+impl Comparator {
+    fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+        let (op, rest) = [
+            (">=", Op::GreaterEq),
+            ("<=", Op::LessEq),
+            (">", Op::Greater),
+            ("<", Op::Less),
+            ("=", Op::Exact),
+            ("~", Op::Tilde),
+            ("^", Op::Caret),
+        ]
+        .iter()
+        .find_map(|(prefix, op)| s.strip_prefix(prefix).map(|rest| (*op, rest)))
+        .unwrap_or((Op::Caret, s));
+        let rest = rest.trim();
+        let rest = rest.strip_prefix('v').unwrap_or(rest);
+
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) if valid_identifiers(pre, true) => (core, pre),
+            Some(_) => return Err(format!("invalid pre-release in requirement '{s}'")),
+            None => (rest, ""),
+        };
+        let mut parts = core.split('.');
+        let mut part = |required: bool| -> Result<Option<u64>, String> {
+            match parts.next() {
+                None | Some("*" | "x" | "X") if !required => Ok(None),
+                Some(p) => numeric(p)
+                    .map(Some)
+                    .ok_or_else(|| format!("invalid version requirement '{s}'")),
+                None => Err(format!("invalid version requirement '{s}'")),
+            }
+        };
+        let major = part(true)?.unwrap_or_default();
+        let minor = part(false)?;
+        let patch = if minor.is_some() { part(false)? } else { None };
+        if parts.next().is_some() || (!pre.is_empty() && patch.is_none()) {
+            return Err(format!("invalid version requirement '{s}'"));
+        }
+
+        // `1.*` and `1.2.*` pin the given parts, whatever the operator
+        let op = if core.ends_with('*') || core.ends_with(['x', 'X']) {
+            Op::Exact
+        } else {
+            op
+        };
+        Ok(Self {
+            op,
+            major,
+            minor,
+            patch,
+            pre: pre.to_string(),
+        })
+    }
+
+    /// Compare on the parts the comparator specifies
+    fn cmp_partial(&self, version: &SemVer) -> Ordering {
+        version
+            .major
+            .cmp(&self.major)
+            .then_with(|| {
+                self.minor
+                    .map_or(Ordering::Equal, |m| version.minor.cmp(&m))
+            })
+            .then_with(|| match self.patch {
+                Some(p) => version
+                    .patch
+                    .cmp(&p)
+                    .then_with(|| cmp_pre(&version.pre, &self.pre)),
+                None => Ordering::Equal,
+            })
+    }
+
+    /// At least the comparator's version and below `upper`
+    fn in_range(&self, version: &SemVer, upper: &SemVer) -> bool {
+        self.cmp_partial(version) != Ordering::Less
+            && version.cmp_precedence(upper) == Ordering::Less
+    }
+
+    fn matches(&self, version: &SemVer) -> bool {
+        let ordering = self.cmp_partial(version);
+        let minor = self.minor.unwrap_or(0);
+        let patch = self.patch.unwrap_or(0);
+        match self.op {
+            Op::Exact => ordering == Ordering::Equal,
+            Op::Greater => ordering == Ordering::Greater,
+            Op::GreaterEq => ordering != Ordering::Less,
+            Op::Less => ordering == Ordering::Less,
+            Op::LessEq => ordering != Ordering::Greater,
+            Op::Tilde => {
+                let upper = match self.minor {
+                    Some(minor) => SemVer::new(self.major, minor + 1, 0),
+                    None => SemVer::new(self.major + 1, 0, 0),
+                };
+                self.in_range(version, &upper)
+            }
+            Op::Caret => {
+                let upper = if self.major > 0 || self.minor.is_none() {
+                    SemVer::new(self.major + 1, 0, 0)
+                } else if minor > 0 || self.patch.is_none() {
+                    SemVer::new(0, minor + 1, 0)
+                } else {
+                    SemVer::new(0, 0, patch + 1)
+                };
+                self.in_range(version, &upper)
+            }
+        }
+    }
+}
+
+/// A version requirement such as `>=0.3` or `>=1.2, <2`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    text: String,
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    /// Whether `version` satisfies every comparator.
+    ///
+    /// As in Cargo, a pre-release only matches when a comparator names a
+    /// pre-release of the same `MAJOR.MINOR.PATCH` (`>=1.0.0-rc.1`).
+    #[must_use]
+    pub fn matches(&self, version: &SemVer) -> bool {
+        let pre_allowed = !version.is_prerelease()
+            || self.comparators.iter().any(|c| {
+                !c.pre.is_empty()
+                    && c.major == version.major
+                    && c.minor == Some(version.minor)
+                    && c.patch == Some(version.patch)
+            });
+        pre_allowed && self.comparators.iter().all(|c| c.matches(version))
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let comparators = if text.is_empty() || text == "*" {
+            Vec::new()
+        } else {
+            text.split(',')
+                .map(Comparator::parse)
+                .collect::<Result<_, _>>()?
+        };
+        Ok(Self {
+            text: text.to_string(),
+            comparators,
+        })
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.text.is_empty() {
+            "*"
+        } else {
+            &self.text
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(s: &str) -> SemVer {
+        s.parse().unwrap()
+    }
+
+    fn req(s: &str) -> VersionReq {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let version = v("1.2.3-beta.1+build.5");
+        assert_eq!((version.major, version.minor, version.patch), (1, 2, 3));
+        assert_eq!(version.pre, "beta.1");
+        assert_eq!(version.build, "build.5");
+        assert_eq!(version.to_string(), "1.2.3-beta.1+build.5");
+        assert_eq!(v("v0.3.0"), SemVer::new(0, 3, 0));
+
+        for invalid in [
+            "1.2",
+            "1.2.3.4",
+            "01.2.3",
+            "1.2.x",
+            "1.2.3-",
+            "1.2.3-01",
+            "1.2.3+",
+            "1.2.3-a..b",
+        ] {
+            assert!(invalid.parse::<SemVer>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_precedence() {
+        // Example from the semver specification
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.1.0",
+            "2.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        assert_eq!(v("1.0.0+a").cmp_precedence(&v("1.0.0+b")), Ordering::Equal);
+        assert_ne!(v("1.0.0+a"), v("1.0.0+b"));
+    }
+
+    #[test]
+    fn test_compatibility() {
+        assert!(v("1.4.0").is_compatible_with(&v("1.2.0")));
+        assert!(!v("1.1.0").is_compatible_with(&v("1.2.0")));
+        assert!(!v("2.0.0").is_compatible_with(&v("1.2.0")));
+        assert!(v("0.3.5").is_compatible_with(&v("0.3.1")));
+        assert!(!v("0.4.0").is_compatible_with(&v("0.3.1")));
+        assert!(!v("0.0.4").is_compatible_with(&v("0.0.3")));
+    }
+
+    #[test]
+    fn test_requirements() {
+        let cases = [
+            (">=0.3", "0.3.0", true),
+            (">=0.3", "0.2.9", false),
+            (">=0.3", "1.0.0", true),
+            (">0.3", "0.3.9", false),
+            (">0.3", "0.4.0", true),
+            ("<2", "1.9.9", true),
+            ("<2", "2.0.0", false),
+            ("<=1.2", "1.2.7", true),
+            ("=1.2.3", "1.2.3", true),
+            ("=1.2", "1.2.9", true),
+            ("1.2.*", "1.2.9", true),
+            ("1.*", "2.0.0", false),
+            ("*", "3.1.4", true),
+            ("~1.2.3", "1.2.9", true),
+            ("~1.2.3", "1.3.0", false),
+            ("~1", "1.9.0", true),
+            ("^1.2", "1.9.0", true),
+            ("1.2", "2.0.0", false),
+            ("^0.2.3", "0.2.9", true),
+            ("^0.2.3", "0.3.0", false),
+            ("^0.0.3", "0.0.4", false),
+            ("^0", "0.9.0", true),
+            (">=1.2, <1.5", "1.4.0", true),
+            (">=1.2, <1.5", "1.5.0", false),
+            // Pre-releases only match requirements that name one
+            (">=0.3", "0.4.0-beta.1", false),
+            (">=0.4.0-beta.1", "0.4.0-beta.2", true),
+            (">=0.4.0-beta.1", "0.4.0", true),
+        ];
+        for (requirement, version, expected) in cases {
+            assert_eq!(
+                req(requirement).matches(&v(version)),
+                expected,
+                "{requirement} vs {version}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_requirements() {
+        for invalid in [">=", "abc", ">=1.2.3.4", "^1.x.3-pre", ">=1.2, "] {
+            assert!(invalid.parse::<VersionReq>().is_err(), "{invalid}");
+        }
+        assert_eq!(req(" >=0.3 ").to_string(), ">=0.3");
+    }
+}
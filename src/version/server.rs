@@ -0,0 +1,253 @@
+//! Minimal HTTP responder for `/metrics` and `/version`, for daemons that
+//! are scraped by Prometheus but have no web framework of their own.
+//!
+//! std only: requests are answered one at a time on a single thread and
+//! every connection is closed after the response. Only `GET` and `HEAD` are
+//! supported; anything else gets `405`, unknown paths `404`.
+
+use super::{Version, metrics};
+use crate::output::OutputFormat;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Largest request head that is read before answering `400`
+const MAX_REQUEST: usize = 8 * 1024;
+
+/// How long a client may take to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Serves the `build_info` gauge on `/metrics` and the version as JSON on
+/// `/version`
+pub struct MetricsServer {
+    listener: TcpListener,
+    version: Version,
+    metric: String,
+}
+
+impl MetricsServer {
+    /// Listen on `addr`, e.g. `"127.0.0.1:9100"` (port 0 picks a free port)
+    ///
+    /// # Errors
+    /// Returns a message if the address cannot be bound.
+    pub fn bind(addr: impl ToSocketAddrs, version: &Version) -> Result<Self, String> {
+        let listener = TcpListener::bind(addr).map_err(|e| format!("cannot listen: {e}"))?;
+        Ok(Self {
+            listener,
+            version: version.clone(),
+            metric: metrics::DEFAULT_METRIC.to_string(),
+        })
+    }
+
+    /// Publish the gauge under another name, e.g. `myapp_build_info`
+    #[must_use]
+    pub fn with_metric_name(mut self, metric: &str) -> Self {
+        self.metric = metric.to_string();
+        self
+    }
+
+    /// The address actually bound, useful after binding port 0
+    ///
+    /// # Errors
+    /// Returns a message if the socket address cannot be read.
+    pub fn local_addr(&self) -> Result<SocketAddr, String> {
+        self.listener
+            .local_addr()
+            .map_err(|e| format!("cannot read listening address: {e}"))
+    }
+
+    /// Answer requests forever on the current thread. Failed connections are
+    /// dropped without stopping the server.
+    pub fn run(&self) {
+        for stream in self.listener.incoming().flatten() {
+            let _ = self.handle(stream);
+        }
+    }
+
+    /// Answer requests on a background thread
+    ///
+    /// # Errors
+    /// Returns a message if the thread cannot be started.
+    pub fn spawn(self) -> Result<JoinHandle<()>, String> {
+        thread::Builder::new()
+            .name("sw-cli-metrics".to_string())
+            .spawn(move || self.run())
+            .map_err(|e| format!("cannot start metrics server: {e}"))
+    }
+
+    fn handle(&self, mut stream: TcpStream) -> Result<(), String> {
+        stream
+            .set_read_timeout(Some(REQUEST_TIMEOUT))
+            .and_then(|()| stream.set_write_timeout(Some(REQUEST_TIMEOUT)))
+            .map_err(|e| e.to_string())?;
+        let head = read_head(&mut stream)?;
+        let response = self.respond(head.as_deref());
+        stream
+            .write_all(&response)
+            .and_then(|()| stream.flush())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Full HTTP response for a request head (`None` if it was unreadable)
+    fn respond(&self, head: Option<&str>) -> Vec<u8> {
+        let Some((method, path, accept)) = head.and_then(parse_head) else {
+            return response("400 Bad Request", "text/plain", "bad request\n", true);
+        };
+        let with_body = method == "GET";
+        if !with_body && method != "HEAD" {
+            return response(
+                "405 Method Not Allowed",
+                "text/plain",
+                "method not allowed\n",
+                true,
+            );
+        }
+        match path {
+            "/metrics" => {
+                let content_type = if accept.contains("application/openmetrics-text") {
+                    metrics::OPENMETRICS_CONTENT_TYPE
+                } else {
+                    metrics::PROMETHEUS_CONTENT_TYPE
+                };
+                let body = metrics::render_named(&self.version, &self.metric);
+                response("200 OK", content_type, &body, with_body)
+            }
+            "/version" => {
+                let body = self.version.render(OutputFormat::Json);
+                response("200 OK", "application/json", &body, with_body)
+            }
+            _ => response("404 Not Found", "text/plain", "not found\n", with_body),
+        }
+    }
+}
+
+/// Read up to the blank line ending the request head
+fn read_head(stream: &mut TcpStream) -> Result<Option<String>, String> {
+    let mut head = Vec::new();
+    let mut buf = [0; 1024];
+    while !head.ends_with(b"\r\n\r\n") && !head.ends_with(b"\n\n") {
+        if head.len() > MAX_REQUEST {
+            return Ok(None);
+        }
+        let n = stream.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    Ok(String::from_utf8(head).ok())
+}
+
+/// (method, path without query, Accept header) of a request head
+fn parse_head(head: &str) -> Option<(&str, &str, &str)> {
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?;
+    let target = request_line.next()?;
+    if !request_line.next()?.starts_with("HTTP/") {
+        return None;
+    }
+    let path = target.split(['?', '#']).next().unwrap_or(target);
+    let accept = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("accept"))
+        .map_or("", |(_, value)| value.trim());
+    Some((method, path, accept))
+}
+
+fn response(status: &str, content_type: &str, body: &str, with_body: bool) -> Vec<u8> {
+    let mut out = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n",
+        body.len()
+    );
+    if status.starts_with("405") {
+        out.push_str("Allow: GET, HEAD\r\n");
+    }
+    out.push_str("Connection: close\r\n\r\n");
+    if with_body {
+        out.push_str(body);
+    }
+    out.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::update::fetch::fetch_text;
+    use crate::version::BuildInfo;
+
+    fn start() -> SocketAddr {
+        let version = Version::new(
+            "0.3.0".to_string(),
+            "Copyright (c) 2025 Example Corp".to_string(),
+            "MIT".to_string(),
+            String::new(),
+            BuildInfo::new(
+                "builder".to_string(),
+                "abc123def456".to_string(),
+                1_700_000_000_000,
+            ),
+        )
+        .with_name("example".to_string());
+        let server = MetricsServer::bind("127.0.0.1:0", &version).unwrap();
+        let addr = server.local_addr().unwrap();
+        server.spawn().unwrap();
+        addr
+    }
+
+    fn raw_request(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_metrics_and_version() {
+        let addr = start();
+        let metrics = fetch_text(&format!("http://{addr}/metrics")).unwrap();
+        assert!(metrics.contains("\nbuild_info{version=\"0.3.0\",commit=\"abc123def456\","));
+        assert!(metrics.ends_with("# EOF\n"));
+
+        let version = fetch_text(&format!("http://{addr}/version?pretty")).unwrap();
+        assert!(version.contains("\"version\": \"0.3.0\""), "{version}");
+    }
+
+    #[test]
+    fn test_content_negotiation() {
+        let addr = start();
+        let response = raw_request(
+            addr,
+            "GET /metrics HTTP/1.1\r\nAccept: application/openmetrics-text; version=1.0.0\r\n\r\n",
+        );
+        assert!(response.contains(&format!(
+            "Content-Type: {}\r\n",
+            metrics::OPENMETRICS_CONTENT_TYPE
+        )));
+        let response = raw_request(addr, "GET /metrics HTTP/1.0\r\n\r\n");
+        assert!(response.contains(&format!(
+            "Content-Type: {}\r\n",
+            metrics::PROMETHEUS_CONTENT_TYPE
+        )));
+    }
+
+    #[test]
+    fn test_errors() {
+        let addr = start();
+        let response = raw_request(addr, "HEAD /metrics HTTP/1.0\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("\r\n\r\n"));
+
+        let response = raw_request(addr, "GET /other HTTP/1.0\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let response = raw_request(addr, "POST /metrics HTTP/1.0\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+        assert!(response.contains("Allow: GET, HEAD\r\n"));
+
+        let response = raw_request(addr, "garbage\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    }
+}
@@ -0,0 +1,2063 @@
+//! Build-script side of `define_build_info!()`.
+//!
+//! The macro splices this file verbatim into the caller's build.rs, so it may
+//! only depend on `std`. It is also compiled as a module of `sw-cli-macros`
+//! under `cfg(test)`, which lets the generator be unit tested directly.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Everything written to `version_info.rs`
+pub struct Metadata {
+    pub name: String,
+    pub version: String,
+    pub copyright: String,
+    pub license_name: String,
+    pub license_url: String,
+    /// One entry per license in the SPDX expression
+    pub licenses: Vec<LicenseEntry>,
+    /// Third-party crates from Cargo.lock
+    pub dependencies: Vec<DependencyNotice>,
+    pub lockfile: Option<PathBuf>,
+    pub repository: String,
+    pub commit_url: Option<String>,
+    /// Release manifest read by `--check-update`
+    pub update_manifest: Option<String>,
+    /// `update-check = false` disables update checks
+    pub update_check: bool,
+    /// Base64 ed25519 public key verifying self-update artifacts
+    pub update_public_key: Option<String>,
+    pub build_host: String,
+    pub git: GitState,
+    pub toolchain: Toolchain,
+    pub timestamp_ms: i64,
+}
+
+/// Repository state at build time
+pub struct GitState {
+    pub commit_sha: String,
+    pub branch: Option<String>,
+    pub tag: Option<String>,
+    pub describe: Option<String>,
+    pub dirty: bool,
+}
+
+/// Compiler and target settings of the build
+pub struct Toolchain {
+    pub rustc_version: String,
+    pub target: String,
+    pub profile: String,
+    pub opt_level: String,
+    pub features: Vec<String>,
+}
+
+/// A license named in the package's SPDX expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseEntry {
+    pub id: String,
+    pub url: String,
+    /// File the link and embedded text come from, relative to Cargo.toml
+    pub file: Option<String>,
+    pub text: Option<String>,
+}
+
+/// Options passed as `define_build_info!(option, ...)`
+pub struct Options {
+    /// Derive the timestamp from `SOURCE_DATE_EPOCH` or the commit, omit the
+    /// host and only rerun when git state changes
+    pub reproducible: bool,
+    /// Fail the build instead of warning when release metadata is missing
+    pub strict: bool,
+    /// Embed the full text of each license for `--license`
+    pub embed_licenses: bool,
+}
+
+/// Release metadata that is missing and had to be substituted
+pub struct Issue {
+    pub problem: String,
+    pub fix: String,
+}
+
+impl Issue {
+    fn new(problem: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            problem: problem.into(),
+            fix: fix.into(),
+        }
+    }
+}
+
+/// Overrides the recorded build host; set it empty to omit the host
+pub const BUILD_HOST_ENV: &str = "SW_CLI_BUILD_HOST";
+/// Enables reproducible mode without changing build.rs (any value but `0`)
+pub const REPRODUCIBLE_ENV: &str = "SW_CLI_REPRODUCIBLE";
+/// Enables strict mode without changing build.rs (any value but `0`)
+pub const STRICT_ENV: &str = "SW_CLI_STRICT";
+/// Commit SHA to record when building outside a git checkout
+pub const COMMIT_SHA_ENV: &str = "SW_CLI_COMMIT_SHA";
+/// Base64 ed25519 key that self-updates must be signed with, overriding
+/// `update-public-key` in `[package.metadata.sw-cli]`
+pub const UPDATE_PUBLIC_KEY_ENV: &str = "SW_CLI_UPDATE_PUBLIC_KEY";
+
+/// Collect metadata for the package being built and write `$OUT_DIR/version_info.rs`.
+///
+/// Missing metadata is reported as `cargo:warning` lines and replaced by a
+/// fallback; in strict mode the build fails with the list of problems instead.
+pub fn generate(options: &Options) {
+    let reproducible = options.reproducible || env_flag(REPRODUCIBLE_ENV);
+    let strict = options.strict || env_flag(STRICT_ENV);
+
+    let mut issues = Vec::new();
+    let meta = collect(reproducible, options.embed_licenses, &mut issues);
+
+    if strict && !issues.is_empty() {
+        eprintln!("{}", strict_failure_message(&issues));
+        std::process::exit(1);
+    }
+    for issue in &issues {
+        println!("cargo:warning={} ({})", issue.problem, issue.fix);
+    }
+
+    let Some(out_dir) = env("OUT_DIR") else {
+        eprintln!("error: OUT_DIR is not set; define_build_info!() must be called from build.rs");
+        std::process::exit(1);
+    };
+    let dest_path = Path::new(&out_dir).join("version_info.rs");
+    if let Err(err) = fs::write(&dest_path, render(&meta)) {
+        eprintln!("error: failed to write {}: {err}", dest_path.display());
+        std::process::exit(1);
+    }
+
+    if reproducible {
+        emit_git_rerun_directives();
+        println!("cargo:rerun-if-changed=COPYRIGHT");
+        for file in meta.licenses.iter().filter_map(|l| l.file.as_ref()) {
+            println!("cargo:rerun-if-changed={file}");
+        }
+        if let Some(lockfile) = &meta.lockfile {
+            println!("cargo:rerun-if-changed={}", lockfile.display());
+        }
+        println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+        println!("cargo:rerun-if-env-changed={BUILD_HOST_ENV}");
+        println!("cargo:rerun-if-env-changed={REPRODUCIBLE_ENV}");
+        println!("cargo:rerun-if-env-changed={STRICT_ENV}");
+        println!("cargo:rerun-if-env-changed={COMMIT_SHA_ENV}");
+        println!("cargo:rerun-if-env-changed={UPDATE_PUBLIC_KEY_ENV}");
+    }
+    // Otherwise print no rerun-if-changed lines, so Cargo reruns the script
+    // whenever anything in the package changes and each build gets a fresh timestamp
+}
+
+/// Non-empty environment variable
+pub fn env(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|v| !v.trim().is_empty())
+}
+
+fn env_flag(key: &str) -> bool {
+    std::env::var(key).is_ok_and(|v| v != "0")
+}
+
+/// Error printed when strict mode finds incomplete release metadata
+pub fn strict_failure_message(issues: &[Issue]) -> String {
+    let mut message =
+        String::from("error: define_build_info!(strict): release metadata is incomplete\n");
+    for issue in issues {
+        message.push_str("  - ");
+        message.push_str(&issue.problem);
+        message.push_str("\n    fix: ");
+        message.push_str(&issue.fix);
+        message.push('\n');
+    }
+    message.push_str("  (drop `strict` or unset SW_CLI_STRICT to build with fallbacks)");
+    message
+}
+
+/// Gather metadata from the COPYRIGHT file, Cargo environment, host and git
+pub fn collect(reproducible: bool, embed_licenses: bool, issues: &mut Vec<Issue>) -> Metadata {
+    let build_host = build_host(std::env::var(BUILD_HOST_ENV).ok(), reproducible);
+    let timestamp_ms = build_timestamp(std::env::var("SOURCE_DATE_EPOCH").ok(), reproducible);
+
+    let copyright = copyright(
+        fs::read_to_string("COPYRIGHT").ok(),
+        env("CARGO_PKG_AUTHORS").as_deref(),
+        year_of(timestamp_ms),
+        issues,
+    );
+
+    let repository = env("CARGO_PKG_REPOSITORY").unwrap_or_default();
+    if repository.is_empty() {
+        issues.push(Issue::new(
+            "Cargo.toml has no `repository`; commit and license links are unavailable",
+            "add `repository = \"https://...\"` to [package]",
+        ));
+    }
+
+    let settings = manifest_metadata(&read_manifest());
+    let git = git_state(issues);
+    let links = Links::new(&repository, &settings, &git, issues);
+
+    let (license_name, license_file) = license(
+        env("CARGO_PKG_LICENSE").as_deref(),
+        env("CARGO_PKG_LICENSE_FILE").as_deref(),
+        issues,
+    );
+    let license_file = setting(&settings, "license-file").or(license_file);
+    let licenses = licenses(
+        &license_name,
+        license_file.as_deref(),
+        &package_files(),
+        links.as_ref(),
+        embed_licenses,
+        issues,
+    );
+    let (dependencies, lockfile) = dependency_notices(issues);
+    let license_url = match licenses.as_slice() {
+        [single] => single.url.clone(),
+        _ => license_file
+            .and_then(|file| links.as_ref().map(|links| links.file_url(&file)))
+            .or_else(|| licenses.first().map(|l| l.url.clone()))
+            .unwrap_or_default(),
+    };
+
+    Metadata {
+        name: env("CARGO_PKG_NAME").unwrap_or_default(),
+        version: env("CARGO_PKG_VERSION").unwrap_or_else(|| "0.0.0".to_string()),
+        copyright,
+        license_name,
+        license_url,
+        licenses,
+        dependencies,
+        lockfile,
+        commit_url: links.as_ref().and_then(Links::commit_url),
+        update_manifest: setting(&settings, "update-manifest"),
+        update_check: setting(&settings, "update-check").is_none_or(|v| v != "false"),
+        update_public_key: env(UPDATE_PUBLIC_KEY_ENV)
+            .or_else(|| setting(&settings, "update-public-key")),
+        repository,
+        build_host,
+        git,
+        toolchain: toolchain(),
+        timestamp_ms,
+    }
+}
+
+/// Copyright notice from the COPYRIGHT file, falling back to
+/// `Copyright (c) <year> <authors>` built from `CARGO_PKG_AUTHORS`.
+pub fn copyright(
+    file: Option<String>,
+    authors: Option<&str>,
+    year: i64,
+    issues: &mut Vec<Issue>,
+) -> String {
+    if let Some(text) = file.map(|t| t.trim().to_string()).filter(|t| !t.is_empty()) {
+        return text;
+    }
+    let authors = authors.map(author_names).filter(|a| !a.is_empty());
+    let fix = "create a COPYRIGHT file next to Cargo.toml, e.g. \"Copyright (c) 2025 Your Name\"";
+    if let Some(authors) = authors {
+        issues.push(Issue::new(
+            "COPYRIGHT file not found; using `authors` from Cargo.toml",
+            fix,
+        ));
+        format!("Copyright (c) {year} {authors}")
+    } else {
+        issues.push(Issue::new(
+            "COPYRIGHT file not found and Cargo.toml has no `authors`",
+            fix,
+        ));
+        "Copyright unknown".to_string()
+    }
+}
+
+/// `"Jane Doe <jane@example.com>:John"` -> `"Jane Doe, John"`
+pub fn author_names(authors: &str) -> String {
+    authors
+        .split(':')
+        .map(|author| author.split('<').next().unwrap_or_default().trim())
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// License name and the file to link to. Without `license`, a
+/// `license-file` is linked instead and reported as a custom license.
+pub fn license(
+    license: Option<&str>,
+    license_file: Option<&str>,
+    issues: &mut Vec<Issue>,
+) -> (String, Option<String>) {
+    match (license, license_file) {
+        // Old crates.io style `MIT/Apache-2.0`
+        (Some(name), file) if name.contains('/') => (
+            name.split('/')
+                .map(str::trim)
+                .collect::<Vec<_>>()
+                .join(" OR "),
+            Some(file.unwrap_or("LICENSE").to_string()),
+        ),
+        (Some(name), file) => (
+            name.to_string(),
+            Some(file.unwrap_or("LICENSE").to_string()),
+        ),
+        (None, Some(file)) => ("Custom".to_string(), Some(file.to_string())),
+        (None, None) => {
+            issues.push(Issue::new(
+                "Cargo.toml has neither `license` nor `license-file`",
+                "add an SPDX expression, e.g. `license = \"MIT\"`, to [package]",
+            ));
+            ("Unknown".to_string(), None)
+        }
+    }
+}
+
+/// License ids named in an SPDX expression, in order of appearance.
+///
+/// Supports `AND`, `OR`, `WITH <exception>`, `+` and parentheses;
+/// exceptions are not licenses of their own and are not returned.
+pub fn spdx_license_ids(expression: &str) -> Result<Vec<String>, String> {
+    let spaced = expression.replace('(', " ( ").replace(')', " ) ");
+    let mut parser = SpdxParser {
+        tokens: spaced.split_whitespace().collect(),
+        pos: 0,
+        ids: Vec::new(),
+    };
+    parser.or_expression()?;
+    match parser.tokens.get(parser.pos) {
+        None => Ok(parser.ids),
+        Some(token) => Err(format!("unexpected `{token}`")),
+    }
+}
+
+/// Recursive-descent parser for SPDX license expressions
+struct SpdxParser<'a> {
+    tokens: Vec<&'a str>,
+    pos: usize,
+    ids: Vec<String>,
+}
+
+impl SpdxParser<'_> {
+    fn or_expression(&mut self) -> Result<(), String> {
+        self.and_expression()?;
+        while self.operator("OR") {
+            self.and_expression()?;
+        }
+        Ok(())
+    }
+
+    fn and_expression(&mut self) -> Result<(), String> {
+        self.term()?;
+        while self.operator("AND") {
+            self.term()?;
+        }
+        Ok(())
+    }
+
+    fn term(&mut self) -> Result<(), String> {
+        let Some(token) = self.tokens.get(self.pos).copied() else {
+            return Err("expression ends where a license was expected".to_string());
+        };
+        self.pos += 1;
+        if token == "(" {
+            self.or_expression()?;
+            if self.tokens.get(self.pos) != Some(&")") {
+                return Err("missing `)`".to_string());
+            }
+            self.pos += 1;
+            return Ok(());
+        }
+        let id = Self::identifier(token)?.trim_end_matches('+').to_string();
+        if !self.ids.contains(&id) {
+            self.ids.push(id);
+        }
+        if self.operator("WITH") {
+            let exception = self.tokens.get(self.pos).copied().unwrap_or_default();
+            Self::identifier(exception)?;
+            self.pos += 1;
+        }
+        Ok(())
+    }
+
+    fn operator(&mut self, name: &str) -> bool {
+        let found = self
+            .tokens
+            .get(self.pos)
+            .is_some_and(|t| t.eq_ignore_ascii_case(name));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn identifier(token: &str) -> Result<&str, String> {
+        let is_operator = ["AND", "OR", "WITH"]
+            .iter()
+            .any(|op| token.eq_ignore_ascii_case(op));
+        let valid = !token.is_empty()
+            && !is_operator
+            && token
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '+' | ':'));
+        if valid {
+            Ok(token)
+        } else if token.is_empty() {
+            Err("expression ends where a license was expected".to_string())
+        } else {
+            Err(format!("`{token}` is not a license identifier"))
+        }
+    }
+}
+
+/// Files next to Cargo.toml, plus those in a REUSE-style `LICENSES/` directory
+pub fn package_files() -> Vec<String> {
+    let names = |dir: &str, prefix: &str| -> Vec<String> {
+        fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+                    .filter_map(|e| e.file_name().into_string().ok())
+                    .map(|name| format!("{prefix}{name}"))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut files = names(".", "");
+    files.extend(names("LICENSES", "LICENSES/"));
+    sorted(files)
+}
+
+/// File holding the text of one license among several, e.g. `LICENSE-MIT`,
+/// `LICENSE-APACHE` for `Apache-2.0`, or `LICENSES/MIT.txt`
+pub fn license_file_for(id: &str, files: &[String]) -> Option<String> {
+    let id = id.to_lowercase();
+    let family = id.split('-').next().unwrap_or_default();
+    let wanted = [
+        format!("licenses/{id}"),
+        format!("license-{id}"),
+        format!("license-{family}"),
+    ];
+    wanted.iter().find_map(|want| {
+        files
+            .iter()
+            .find(|file| stem(file).eq_ignore_ascii_case(want))
+            .cloned()
+    })
+}
+
+/// The package's only license file: `LICENSE`, `LICENSE.md`, `COPYING`, ...
+fn generic_license_file(files: &[String]) -> Option<String> {
+    ["license", "licence", "copying"].iter().find_map(|want| {
+        files
+            .iter()
+            .find(|file| stem(file).eq_ignore_ascii_case(want))
+            .cloned()
+    })
+}
+
+/// File name without a `.txt` or `.md` extension
+fn stem(file: &str) -> &str {
+    file.strip_suffix(".txt")
+        .or_else(|| file.strip_suffix(".md"))
+        .unwrap_or(file)
+}
+
+/// Canonical text of a license on spdx.org; empty for non-SPDX ids
+pub fn spdx_url(id: &str) -> String {
+    if id.starts_with("LicenseRef-") || matches!(id, "Custom" | "Unknown") {
+        String::new()
+    } else {
+        format!("https://spdx.org/licenses/{id}.html")
+    }
+}
+
+/// One entry per license of the expression, each with its own link.
+///
+/// A single license links to `license-file` or the package's license file
+/// (`LICENSE` if there is none); several licenses each link to their own
+/// `LICENSE-<ID>` file, falling back to the text on spdx.org.
+pub fn licenses(
+    expression: &str,
+    license_file: Option<&str>,
+    files: &[String],
+    links: Option<&Links>,
+    embed_texts: bool,
+    issues: &mut Vec<Issue>,
+) -> Vec<LicenseEntry> {
+    let ids = match expression {
+        "Unknown" => return Vec::new(),
+        "Custom" => vec![expression.to_string()],
+        _ => spdx_license_ids(expression).unwrap_or_else(|err| {
+            issues.push(Issue::new(
+                format!("`license = \"{expression}\"` is not a valid SPDX expression: {err}"),
+                "use SPDX ids joined by AND/OR, e.g. `license = \"MIT OR Apache-2.0\"`",
+            ));
+            vec![expression.to_string()]
+        }),
+    };
+
+    let single = ids.len() == 1;
+    ids.into_iter()
+        .map(|id| {
+            let file = if single {
+                license_file
+                    .map(str::to_string)
+                    .or_else(|| license_file_for(&id, files))
+                    .or_else(|| generic_license_file(files))
+            } else {
+                license_file_for(&id, files)
+            };
+            // A lone license keeps linking to LICENSE even before the file exists
+            let link_file = file
+                .clone()
+                .or_else(|| single.then(|| "LICENSE".to_string()));
+            let url = match (link_file, links) {
+                (Some(link_file), Some(links)) => links.file_url(&link_file),
+                _ => spdx_url(&id),
+            };
+
+            let text = if embed_texts {
+                let text = file.as_ref().and_then(|f| fs::read_to_string(f).ok());
+                if text.is_none() {
+                    let family = id.split('-').next().unwrap_or_default().to_uppercase();
+                    issues.push(Issue::new(
+                        format!("no license text found for {id}; it will not be embedded"),
+                        format!("add LICENSE-{family} (or LICENSES/{id}.txt) next to Cargo.toml"),
+                    ));
+                }
+                text
+            } else {
+                None
+            };
+
+            LicenseEntry {
+                id,
+                url,
+                file,
+                text,
+            }
+        })
+        .collect()
+}
+
+/// Contents of the package's Cargo.toml (empty if unreadable)
+pub fn read_manifest() -> String {
+    let dir = env("CARGO_MANIFEST_DIR").unwrap_or_else(|| ".".to_string());
+    fs::read_to_string(Path::new(&dir).join("Cargo.toml")).unwrap_or_default()
+}
+
+/// Key/value pairs of the `[package.metadata.sw-cli]` table
+pub fn manifest_metadata(manifest: &str) -> Vec<(String, String)> {
+    toml_table(
+        manifest,
+        &["package.metadata.sw-cli", "package.metadata.\"sw-cli\""],
+    )
+}
+
+/// Key/value pairs of the first table whose header is one of `names`.
+///
+/// Only the subset of TOML found in manifests is understood: one
+/// `key = value` per line with string, boolean or integer values.
+pub fn toml_table(manifest: &str, names: &[&str]) -> Vec<(String, String)> {
+    let mut in_table = false;
+    let mut entries = Vec::new();
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            if in_table {
+                break;
+            }
+            let header = line.trim_matches(|c| c == '[' || c == ']').trim();
+            in_table = names.contains(&header);
+            continue;
+        }
+        if !in_table || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim().trim_matches('"').to_string();
+            entries.push((key, toml_scalar(value)));
+        }
+    }
+    entries
+}
+
+/// Value of a `key = value` TOML line: quotes removed for basic and literal
+/// strings, trailing comments dropped for everything else
+pub fn toml_scalar(value: &str) -> String {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(rest) = value.strip_prefix(quote) {
+            let end = rest.find(quote).unwrap_or(rest.len());
+            let text = &rest[..end];
+            return if quote == '"' {
+                text.replace("\\\\", "\\")
+            } else {
+                text.to_string()
+            };
+        }
+    }
+    value
+        .split('#')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+/// Look up a `[package.metadata.sw-cli]` setting
+pub fn setting(settings: &[(String, String)], key: &str) -> Option<String> {
+    settings
+        .iter()
+        .rev()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.clone())
+        .filter(|v| !v.is_empty())
+}
+
+/// URL layouts of the code hosting services the link builder knows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+    GitHub,
+    GitLab,
+    Gitea,
+    SourceHut,
+}
+
+impl Forge {
+    /// Guess the forge from the repository host; unknown hosts get GitHub's layout
+    pub fn detect(repository: &str) -> Self {
+        let host = repository
+            .split("://")
+            .nth(1)
+            .unwrap_or(repository)
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if host.contains("gitlab") {
+            Self::GitLab
+        } else if host == "codeberg.org" || host.contains("gitea") || host.contains("forgejo") {
+            Self::Gitea
+        } else if host.ends_with("sr.ht") {
+            Self::SourceHut
+        } else {
+            Self::GitHub
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "github" => Some(Self::GitHub),
+            "gitlab" => Some(Self::GitLab),
+            "gitea" | "forgejo" | "codeberg" => Some(Self::Gitea),
+            "sourcehut" | "srht" => Some(Self::SourceHut),
+            _ => None,
+        }
+    }
+}
+
+/// Builds file and commit links for the repository
+pub struct Links {
+    pub forge: Forge,
+    pub repository: String,
+    /// Branch (or commit SHA when `commit_ref` is set) that file links point at
+    pub reference: String,
+    pub commit_ref: bool,
+    pub commit_sha: Option<String>,
+}
+
+impl Links {
+    /// Link settings from `[package.metadata.sw-cli]`:
+    /// `forge`, `branch` and `link-to = "commit"`. `None` without a repository.
+    ///
+    /// Without a `branch` setting, links use the `origin` remote's default
+    /// branch, then the commit of the build, then `main`.
+    pub fn new(
+        repository: &str,
+        settings: &[(String, String)],
+        git: &GitState,
+        issues: &mut Vec<Issue>,
+    ) -> Option<Self> {
+        Self::with_default_branch(repository, settings, git, default_branch, issues)
+    }
+
+    /// [`Links::new`] with the lookup of the remote's default branch passed in
+    fn with_default_branch(
+        repository: &str,
+        settings: &[(String, String)],
+        git: &GitState,
+        default_branch: impl FnOnce() -> Option<String>,
+        issues: &mut Vec<Issue>,
+    ) -> Option<Self> {
+        let repository = repository.trim_end_matches('/').trim_end_matches(".git");
+        if repository.is_empty() {
+            return None;
+        }
+
+        let forge = match setting(settings, "forge") {
+            Some(name) => Forge::parse(&name).unwrap_or_else(|| {
+                issues.push(Issue::new(
+                    format!("unknown forge \"{name}\" in [package.metadata.sw-cli]"),
+                    "use one of: github, gitlab, gitea, sourcehut",
+                ));
+                Forge::detect(repository)
+            }),
+            None => Forge::detect(repository),
+        };
+
+        let commit_sha =
+            Some(git.commit_sha.clone()).filter(|sha| !sha.is_empty() && sha != "unknown");
+        let link_to_commit = setting(settings, "link-to").is_some_and(|v| v == "commit");
+        // A feature branch can be deleted after the release, so links fall
+        // back to the commit rather than the checked-out branch
+        let branch = if link_to_commit {
+            None
+        } else {
+            setting(settings, "branch").or_else(default_branch)
+        };
+        let (reference, commit_ref) = match (branch, &commit_sha) {
+            (Some(branch), _) => (branch, false),
+            (None, Some(sha)) => (sha.clone(), true),
+            (None, None) => ("main".to_string(), false),
+        };
+
+        Some(Self {
+            forge,
+            repository: repository.to_string(),
+            reference,
+            commit_ref,
+            commit_sha,
+        })
+    }
+
+    /// Link to a file at the configured branch or commit
+    pub fn file_url(&self, file: &str) -> String {
+        let repo = &self.repository;
+        let reference = &self.reference;
+        let file = file.trim_start_matches("./");
+        match self.forge {
+            Forge::GitHub => format!("{repo}/blob/{reference}/{file}"),
+            Forge::GitLab => format!("{repo}/-/blob/{reference}/{file}"),
+            Forge::Gitea if self.commit_ref => format!("{repo}/src/commit/{reference}/{file}"),
+            Forge::Gitea => format!("{repo}/src/branch/{reference}/{file}"),
+            Forge::SourceHut => format!("{repo}/tree/{reference}/item/{file}"),
+        }
+    }
+
+    /// Link to the build's commit, if the commit is known
+    pub fn commit_url(&self) -> Option<String> {
+        let repo = &self.repository;
+        let sha = self.commit_sha.as_ref()?;
+        Some(match self.forge {
+            Forge::GitLab => format!("{repo}/-/commit/{sha}"),
+            Forge::GitHub | Forge::Gitea | Forge::SourceHut => format!("{repo}/commit/{sha}"),
+        })
+    }
+}
+
+/// Default branch of the `origin` remote, as recorded by `git clone`
+pub fn default_branch() -> Option<String> {
+    git(&["symbolic-ref", "--short", "refs/remotes/origin/HEAD"])
+        .map(|r| r.trim_start_matches("origin/").to_string())
+}
+
+/// A `[[package]]` entry of Cargo.lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    /// `registry+...` or `git+...`; `None` for workspace and path packages
+    pub source: Option<String>,
+    /// SHA-256 of the downloaded `.crate` file (registry packages only)
+    pub checksum: Option<String>,
+    /// Entries of `dependencies = [...]`: `name`, `name version` or
+    /// `name version (source)`
+    pub dependencies: Vec<String>,
+}
+
+/// A dependency compiled into the binary and the license it is under
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyNotice {
+    pub name: String,
+    pub version: String,
+    pub license: String,
+    pub source: Option<String>,
+    pub checksum: Option<String>,
+}
+
+/// Nearest Cargo.lock at or above the package directory
+pub fn find_lockfile() -> Option<PathBuf> {
+    let dir = PathBuf::from(env("CARGO_MANIFEST_DIR").unwrap_or_else(|| ".".to_string()));
+    dir.ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.is_file())
+}
+
+/// Packages listed in a Cargo.lock file
+pub fn parse_lockfile(lock: &str) -> Vec<LockedPackage> {
+    let mut packages = Vec::new();
+    let mut current: Option<LockedPackage> = None;
+    let mut in_dependencies = false;
+    for line in lock.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            packages.extend(current.take());
+            in_dependencies = false;
+            if line == "[[package]]" {
+                current = Some(LockedPackage {
+                    name: String::new(),
+                    version: String::new(),
+                    source: None,
+                    checksum: None,
+                    dependencies: Vec::new(),
+                });
+            }
+            continue;
+        }
+        let Some(package) = current.as_mut() else {
+            continue;
+        };
+        if in_dependencies {
+            if line.starts_with(']') {
+                in_dependencies = false;
+            } else if !line.is_empty() {
+                package
+                    .dependencies
+                    .push(toml_scalar(line.trim_end_matches(',')));
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "name" => package.name = toml_scalar(value),
+            "version" => package.version = toml_scalar(value),
+            "source" => package.source = Some(toml_scalar(value)),
+            "checksum" => package.checksum = Some(toml_scalar(value)),
+            "dependencies" => {
+                let value = value.trim();
+                if let Some(list) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                    package.dependencies.extend(
+                        list.split(',')
+                            .map(toml_scalar)
+                            .filter(|dep| !dep.is_empty()),
+                    );
+                } else {
+                    in_dependencies = true;
+                }
+            }
+            _ => {}
+        }
+    }
+    packages.extend(current);
+    packages
+}
+
+/// Every package reachable from `root` through the lock file's dependency
+/// lists, sorted by name and version.
+///
+/// Cargo.lock does not separate dev- and build-dependencies or
+/// platform-specific ones, so the list errs on the side of including them.
+pub fn dependency_closure<'a>(
+    packages: &'a [LockedPackage],
+    root_name: &str,
+    root_version: &str,
+) -> Vec<&'a LockedPackage> {
+    let Some(root) = packages
+        .iter()
+        .position(|p| p.name == root_name && p.version == root_version)
+        .or_else(|| packages.iter().position(|p| p.name == root_name))
+    else {
+        return Vec::new();
+    };
+
+    let mut seen = vec![false; packages.len()];
+    seen[root] = true;
+    let mut queue = vec![root];
+    while let Some(index) = queue.pop() {
+        for spec in &packages[index].dependencies {
+            let mut parts = spec.split_whitespace();
+            let name = parts.next().unwrap_or_default();
+            let version = parts.next();
+            let found = packages
+                .iter()
+                .position(|p| p.name == name && version.is_none_or(|v| p.version == v));
+            if let Some(found) = found.filter(|&i| !seen[i]) {
+                seen[found] = true;
+                queue.push(found);
+            }
+        }
+    }
+
+    let mut closure: Vec<_> = packages
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| seen[i] && i != root)
+        .map(|(_, p)| p)
+        .collect();
+    closure.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    closure
+}
+
+/// License of a package manifest: `license`, or `license-file` as a custom license
+pub fn manifest_license(manifest: &str) -> Option<String> {
+    let package = toml_table(manifest, &["package"]);
+    setting(&package, "license")
+        .map(|license| license.replace('/', " OR "))
+        .or_else(|| setting(&package, "license-file").map(|file| format!("Custom ({file})")))
+}
+
+/// Directories that may hold unpacked crate sources: vendor directories
+/// (`vendor/` and `directory = ...` source replacements in `.cargo/config.toml`)
+/// next to Cargo.lock, then every registry under `$CARGO_HOME/registry/src`
+pub fn crate_source_dirs(workspace: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![workspace.join("vendor")];
+    for config in [".cargo/config.toml", ".cargo/config"] {
+        let text = fs::read_to_string(workspace.join(config)).unwrap_or_default();
+        for line in text.lines() {
+            if let Some((key, value)) = line.split_once('=')
+                && key.trim() == "directory"
+            {
+                dirs.push(workspace.join(toml_scalar(value)));
+            }
+        }
+    }
+
+    if let Some(registry) = cargo_home().map(|home| home.join("registry").join("src"))
+        && let Ok(entries) = fs::read_dir(registry)
+    {
+        let mut registries: Vec<_> = entries.filter_map(Result::ok).map(|e| e.path()).collect();
+        registries.sort();
+        dirs.extend(registries);
+    }
+    dirs.into_iter().filter(|dir| dir.is_dir()).collect()
+}
+
+/// Manifest of a locked package: `<dir>/<name>-<version>/Cargo.toml` in a
+/// source directory or `<dir>/<name>/Cargo.toml` in a vendor directory. Path
+/// and git packages are also looked for in the workspace or the git checkout,
+/// at the root and up to two directory levels below it.
+pub fn find_manifest(
+    package: &LockedPackage,
+    workspace: &Path,
+    source_dirs: &[PathBuf],
+) -> Option<String> {
+    let versioned = format!("{}-{}", package.name, package.version);
+    let is_match = |manifest: &str| {
+        let table = toml_table(manifest, &["package"]);
+        setting(&table, "name").as_deref() == Some(package.name.as_str())
+            && setting(&table, "version").is_none_or(|v| v == package.version)
+    };
+    let read = |dir: &Path| {
+        fs::read_to_string(dir.join("Cargo.toml"))
+            .ok()
+            .filter(|m| is_match(m))
+    };
+
+    let in_tree = |root: &Path| {
+        let mut dirs = vec![root.to_path_buf()];
+        for _ in 0..=2 {
+            if let Some(manifest) = dirs.iter().find_map(|dir| read(dir)) {
+                return Some(manifest);
+            }
+            dirs = dirs.iter().flat_map(|dir| subdirs(dir)).collect();
+        }
+        None
+    };
+
+    let vendored = source_dirs
+        .iter()
+        .find_map(|dir| read(&dir.join(&versioned)).or_else(|| read(&dir.join(&package.name))));
+    match package.source.as_deref() {
+        _ if vendored.is_some() => vendored,
+        None => in_tree(workspace),
+        // git+https://host/repo?branch=main#<commit>, checked out by Cargo
+        // under $CARGO_HOME/git/checkouts/<repo>-<hash>/<short commit>
+        Some(source) if source.starts_with("git+") => {
+            let commit = source.rsplit_once('#')?.1;
+            let checkouts = cargo_home()?.join("git").join("checkouts");
+            subdirs(&checkouts)
+                .iter()
+                .flat_map(|repo| subdirs(repo))
+                .filter(|checkout| {
+                    checkout
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| commit.starts_with(n))
+                })
+                .find_map(|checkout| in_tree(&checkout))
+        }
+        Some(_) => None,
+    }
+}
+
+/// `$CARGO_HOME`, defaulting to `~/.cargo`
+fn cargo_home() -> Option<PathBuf> {
+    env("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env("HOME").map(|home| Path::new(&home).join(".cargo")))
+}
+
+/// Subdirectories that may hold workspace members (not hidden, `target` or `vendor`)
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<_> = entries
+        .filter_map(Result::ok)
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| !n.starts_with('.') && n != "target" && n != "vendor")
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// Name, version and license of every dependency in Cargo.lock that was
+/// built. Licenses that cannot be determined are recorded as `unknown` and reported.
+pub fn dependency_notices(issues: &mut Vec<Issue>) -> (Vec<DependencyNotice>, Option<PathBuf>) {
+    let Some(lockfile) = find_lockfile() else {
+        issues.push(Issue::new(
+            "Cargo.lock not found; dependency notices are unavailable",
+            "run `cargo generate-lockfile`",
+        ));
+        return (Vec::new(), None);
+    };
+    let packages = parse_lockfile(&fs::read_to_string(&lockfile).unwrap_or_default());
+    let workspace = lockfile.parent().unwrap_or(Path::new(".")).to_path_buf();
+    let source_dirs = crate_source_dirs(&workspace);
+
+    let name = env("CARGO_PKG_NAME").unwrap_or_default();
+    let version = env("CARGO_PKG_VERSION").unwrap_or_default();
+    let mut unknown = Vec::new();
+    let mut notices = Vec::new();
+    for package in dependency_closure(&packages, &name, &version) {
+        let manifest = find_manifest(package, &workspace, &source_dirs);
+        // Cargo only downloads the crates a build needs, so a registry crate
+        // without sources was not compiled for this target
+        let is_registry = package
+            .source
+            .as_deref()
+            .is_some_and(|s| s.starts_with("registry+") || s.starts_with("sparse+"));
+        if manifest.is_none() && is_registry {
+            continue;
+        }
+        let license = manifest
+            .and_then(|manifest| manifest_license(&manifest))
+            .unwrap_or_else(|| {
+                unknown.push(format!("{} {}", package.name, package.version));
+                "unknown".to_string()
+            });
+        notices.push(DependencyNotice {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            license,
+            source: package.source.clone(),
+            checksum: package.checksum.clone(),
+        });
+    }
+
+    if !unknown.is_empty() {
+        issues.push(Issue::new(
+            format!(
+                "license unknown for {} dependencies: {}",
+                unknown.len(),
+                unknown.join(", ")
+            ),
+            "add `license` to their Cargo.toml, or vendor them with `cargo vendor`",
+        ));
+    }
+    (notices, Some(lockfile))
+}
+
+/// Calendar year (UTC) of a timestamp in milliseconds
+pub fn year_of(timestamp_ms: i64) -> i64 {
+    // Civil-from-days conversion (Howard Hinnant), valid for the proleptic Gregorian calendar
+    let days = timestamp_ms.div_euclid(86_400_000) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    year_of_era + era * 400 + i64::from(month_index >= 10)
+}
+
+/// Host to record: the override if set (empty omits it), nothing in
+/// reproducible mode, otherwise the output of `hostname`.
+pub fn build_host(host_override: Option<String>, reproducible: bool) -> String {
+    if let Some(host) = host_override {
+        return host.trim().to_string();
+    }
+    if reproducible {
+        return String::new();
+    }
+    match Command::new("hostname").output() {
+        Ok(output) if output.status.success() => decode_output(&output.stdout),
+        _ => {
+            println!(
+                "cargo:warning=could not run `hostname`; recording \"unknown\" (set {BUILD_HOST_ENV} to choose a value)"
+            );
+            "unknown".to_string()
+        }
+    }
+}
+
+/// Build timestamp in milliseconds.
+///
+/// `SOURCE_DATE_EPOCH` always wins. Without it, reproducible builds use the
+/// commit time (or the epoch outside a repository) and normal builds use now.
+pub fn build_timestamp(source_date_epoch: Option<String>, reproducible: bool) -> i64 {
+    if let Some(value) = source_date_epoch {
+        match parse_source_date_epoch(&value) {
+            Some(ms) => return ms,
+            None => println!(
+                "cargo:warning=ignoring SOURCE_DATE_EPOCH={value:?}: expected seconds since the Unix epoch"
+            ),
+        }
+    }
+    if reproducible {
+        return git(&["log", "-1", "--format=%ct"])
+            .and_then(|secs| parse_source_date_epoch(&secs))
+            .unwrap_or(0);
+    }
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX))
+}
+
+/// Parse a `SOURCE_DATE_EPOCH` value (whole seconds) into milliseconds
+pub fn parse_source_date_epoch(value: &str) -> Option<i64> {
+    value.trim().parse::<i64>().ok()?.checked_mul(1000)
+}
+
+/// Rerun the build script only when HEAD, refs, tags or the index change.
+///
+/// The index covers staged changes; edits to tracked files are picked up the
+/// next time git refreshes it (e.g. `git status`).
+pub fn emit_git_rerun_directives() {
+    let Some(git_dir) = git(&["rev-parse", "--git-dir"]) else {
+        return;
+    };
+    let common_dir = git(&["rev-parse", "--git-common-dir"]).unwrap_or_else(|| git_dir.clone());
+    let git_dir = Path::new(&git_dir);
+    let common_dir = Path::new(&common_dir);
+
+    let mut paths = vec![
+        git_dir.join("HEAD"),
+        git_dir.join("index"),
+        common_dir.join("packed-refs"),
+        common_dir.join("refs").join("tags"),
+    ];
+    if let Some(head_ref) = git(&["symbolic-ref", "-q", "HEAD"]) {
+        paths.push(common_dir.join(head_ref));
+    }
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+/// Trimmed command output; invalid UTF-8 (e.g. a Latin-1 hostname) is
+/// replaced with U+FFFD rather than failing the build.
+pub fn decode_output(stdout: &[u8]) -> String {
+    String::from_utf8_lossy(stdout).trim().to_string()
+}
+
+/// Run git and return its trimmed stdout, or `None` if git is missing,
+/// fails (e.g. no tags, not a repository) or prints nothing.
+pub fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = decode_output(&output.stdout);
+    (!stdout.is_empty()).then_some(stdout)
+}
+
+/// Query commit, branch, nearest tag, `git describe` and working tree state.
+///
+/// Untracked files do not make a build dirty, matching `git describe --dirty`.
+/// Outside a git checkout (e.g. a source tarball) the commit comes from
+/// `SW_CLI_COMMIT_SHA` or the `.cargo_vcs_info.json` of a published crate.
+pub fn git_state(issues: &mut Vec<Issue>) -> GitState {
+    let commit_sha = env(COMMIT_SHA_ENV)
+        .or_else(|| git(&["rev-parse", "HEAD"]))
+        .or_else(|| {
+            fs::read_to_string(".cargo_vcs_info.json")
+                .ok()
+                .and_then(|json| vcs_info_sha(&json))
+        });
+    let commit_sha = commit_sha.unwrap_or_else(|| {
+        issues.push(Issue::new(
+            "no git repository or .cargo_vcs_info.json found; commit recorded as \"unknown\"",
+            format!("build from a git checkout or set {COMMIT_SHA_ENV}"),
+        ));
+        "unknown".to_string()
+    });
+
+    GitState {
+        commit_sha,
+        // A detached HEAD reports "HEAD" rather than a branch name
+        branch: git(&["rev-parse", "--abbrev-ref", "HEAD"]).filter(|b| b != "HEAD"),
+        tag: git(&["describe", "--tags", "--abbrev=0"]),
+        describe: git(&["describe", "--always", "--dirty"]),
+        dirty: git(&["status", "--porcelain", "--untracked-files=no"]).is_some(),
+    }
+}
+
+/// Extract `git.sha1` from `.cargo_vcs_info.json`, which `cargo package`
+/// writes into every published crate.
+pub fn vcs_info_sha(json: &str) -> Option<String> {
+    let after_key = &json[json.find("\"sha1\"")? + "\"sha1\"".len()..];
+    let value = after_key.trim_start().strip_prefix(':')?.trim_start();
+    let value = value.strip_prefix('"')?;
+    let sha = &value[..value.find('"')?];
+    (!sha.is_empty() && sha.chars().all(|c| c.is_ascii_hexdigit())).then(|| sha.to_string())
+}
+
+/// Read compiler and target settings from the build-script environment
+pub fn toolchain() -> Toolchain {
+    let env = |key: &str| std::env::var(key).unwrap_or_else(|_| "unknown".to_string());
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map_or_else(
+            || "unknown".to_string(),
+            |output| decode_output(&output.stdout),
+        );
+
+    let features = std::env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(feature_name))
+        .collect();
+
+    Toolchain {
+        rustc_version,
+        target: env("TARGET"),
+        profile: env("PROFILE"),
+        opt_level: env("OPT_LEVEL"),
+        features: sorted(features),
+    }
+}
+
+/// Cargo exposes feature `serde-json` as `CARGO_FEATURE_SERDE_JSON`
+pub fn feature_name(env_suffix: &str) -> String {
+    env_suffix.to_lowercase().replace('_', "-")
+}
+
+fn sorted(mut items: Vec<String>) -> Vec<String> {
+    items.sort();
+    items
+}
+
+/// Generated source of `version_info.rs`.
+///
+/// Every string is written with `{:?}`, which produces an escaped Rust
+/// literal, so quotes, backslashes, newlines and control characters in the
+/// inputs cannot break the generated code.
+pub fn render(meta: &Metadata) -> String {
+    format!(
+        r"// Generated by sw_cli::define_build_info!()
+// Do not edit manually
+
+pub const PACKAGE_NAME: &str = {:?};
+pub const VERSION: &str = {:?};
+pub const COPYRIGHT: &str = {:?};
+pub const LICENSE_NAME: &str = {:?};
+pub const LICENSE_URL: &str = {:?};
+pub const REPOSITORY: &str = {:?};
+pub const COMMIT_URL: Option<&str> = {:?};
+#[allow(dead_code)]
+pub const UPDATE_MANIFEST: Option<&str> = {:?};
+#[allow(dead_code)]
+pub const UPDATE_CHECK: bool = {};
+#[allow(dead_code)]
+pub const UPDATE_PUBLIC_KEY: Option<&str> = {:?};
+pub const BUILD_HOST: &str = {:?};
+pub const GIT_COMMIT_SHA: &str = {:?};
+pub const GIT_BRANCH: Option<&str> = {:?};
+pub const GIT_TAG: Option<&str> = {:?};
+pub const GIT_DESCRIBE: Option<&str> = {:?};
+pub const GIT_DIRTY: bool = {};
+pub const RUSTC_VERSION: &str = {:?};
+pub const BUILD_TARGET: &str = {:?};
+pub const BUILD_PROFILE: &str = {:?};
+pub const OPT_LEVEL: &str = {:?};
+pub const CARGO_FEATURES: &[&str] = &{:?};
+#[allow(clippy::unreadable_literal)]
+pub const BUILD_TIMESTAMP: i64 = {};
+/// (SPDX id, link, embedded text) for each license in `LICENSE_NAME`
+pub const LICENSES: &[(&str, &str, Option<&str>)] = &[{}];
+/// (name, version, license, source, checksum) for each crate in Cargo.lock
+/// the package depends on
+#[allow(dead_code, clippy::type_complexity)]
+pub const DEPENDENCIES: &[(&str, &str, &str, Option<&str>, Option<&str>)] = &[{}];
+",
+        meta.name,
+        meta.version,
+        meta.copyright,
+        meta.license_name,
+        meta.license_url,
+        meta.repository,
+        meta.commit_url,
+        meta.update_manifest,
+        meta.update_check,
+        meta.update_public_key,
+        meta.build_host,
+        meta.git.commit_sha,
+        meta.git.branch,
+        meta.git.tag,
+        meta.git.describe,
+        meta.git.dirty,
+        meta.toolchain.rustc_version,
+        meta.toolchain.target,
+        meta.toolchain.profile,
+        meta.toolchain.opt_level,
+        meta.toolchain.features,
+        meta.timestamp_ms,
+        meta.licenses
+            .iter()
+            .map(|l| format!("({:?}, {:?}, {:?})", l.id, l.url, l.text))
+            .collect::<Vec<_>>()
+            .join(", "),
+        meta.dependencies
+            .iter()
+            .map(|d| {
+                format!(
+                    "({:?}, {:?}, {:?}, {:?}, {:?})",
+                    d.name, d.version, d.license, d.source, d.checksum
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Metadata {
+        Metadata {
+            name: "example".to_string(),
+            version: "0.1.0".to_string(),
+            copyright: "Copyright (c) 2025 Example Corp".to_string(),
+            license_name: "MIT".to_string(),
+            license_url: "https://github.com/example/repo/blob/main/LICENSE".to_string(),
+            licenses: vec![LicenseEntry {
+                id: "MIT".to_string(),
+                url: "https://github.com/example/repo/blob/main/LICENSE".to_string(),
+                file: Some("LICENSE".to_string()),
+                text: None,
+            }],
+            dependencies: vec![DependencyNotice {
+                name: "clap".to_string(),
+                version: "4.5.0".to_string(),
+                license: "MIT OR Apache-2.0".to_string(),
+                source: Some("registry+https://github.com/rust-lang/crates.io-index".to_string()),
+                checksum: Some("abc".to_string()),
+            }],
+            lockfile: None,
+            repository: "https://github.com/example/repo".to_string(),
+            commit_url: Some("https://github.com/example/repo/commit/abc123def456".to_string()),
+            update_manifest: Some("file:///srv/releases/example.json".to_string()),
+            update_check: true,
+            update_public_key: None,
+            build_host: "builder.local".to_string(),
+            git: GitState {
+                commit_sha: "abc123def456".to_string(),
+                branch: Some("main".to_string()),
+                tag: None,
+                describe: Some("abc123d-dirty".to_string()),
+                dirty: true,
+            },
+            toolchain: Toolchain {
+                rustc_version: "rustc 1.91.0 (f8297e351 2025-10-28)".to_string(),
+                target: "x86_64-unknown-linux-gnu".to_string(),
+                profile: "release".to_string(),
+                opt_level: "3".to_string(),
+                features: vec!["default".to_string(), "serde-json".to_string()],
+            },
+            timestamp_ms: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn test_render_git_state() {
+        let code = render(&sample());
+        assert!(code.contains("pub const GIT_COMMIT_SHA: &str = \"abc123def456\";\n"));
+        assert!(code.contains(
+            "= &[(\"clap\", \"4.5.0\", \"MIT OR Apache-2.0\", \
+             Some(\"registry+https://github.com/rust-lang/crates.io-index\"), Some(\"abc\"))];\n"
+        ));
+        assert!(code.contains(
+            "pub const LICENSES: &[(&str, &str, Option<&str>)] = &[(\"MIT\", \"https://github.com/example/repo/blob/main/LICENSE\", None)];\n"
+        ));
+        assert!(code.contains(
+            "pub const COMMIT_URL: Option<&str> = Some(\"https://github.com/example/repo/commit/abc123def456\");\n"
+        ));
+        assert!(code.contains(
+            "pub const UPDATE_MANIFEST: Option<&str> = Some(\"file:///srv/releases/example.json\");\n"
+        ));
+        assert!(code.contains("pub const UPDATE_CHECK: bool = true;\n"));
+        assert!(code.contains("pub const UPDATE_PUBLIC_KEY: Option<&str> = None;\n"));
+        assert!(code.contains("pub const GIT_BRANCH: Option<&str> = Some(\"main\");\n"));
+        assert!(code.contains("pub const GIT_TAG: Option<&str> = None;\n"));
+        assert!(code.contains("pub const GIT_DESCRIBE: Option<&str> = Some(\"abc123d-dirty\");\n"));
+        assert!(code.contains("pub const GIT_DIRTY: bool = true;\n"));
+        assert!(code.contains("pub const BUILD_TIMESTAMP: i64 = 1700000000000;\n"));
+    }
+
+    #[test]
+    fn test_render_toolchain() {
+        let code = render(&sample());
+        assert!(code.contains(
+            "pub const RUSTC_VERSION: &str = \"rustc 1.91.0 (f8297e351 2025-10-28)\";\n"
+        ));
+        assert!(code.contains("pub const BUILD_TARGET: &str = \"x86_64-unknown-linux-gnu\";\n"));
+        assert!(code.contains("pub const BUILD_PROFILE: &str = \"release\";\n"));
+        assert!(code.contains("pub const OPT_LEVEL: &str = \"3\";\n"));
+        assert!(
+            code.contains("pub const CARGO_FEATURES: &[&str] = &[\"default\", \"serde-json\"];\n")
+        );
+    }
+
+    #[test]
+    fn test_feature_name() {
+        assert_eq!(feature_name("SERDE_JSON"), "serde-json");
+        assert_eq!(feature_name("DEFAULT"), "default");
+    }
+
+    #[test]
+    fn test_source_date_epoch() {
+        assert_eq!(
+            parse_source_date_epoch("1700000000"),
+            Some(1_700_000_000_000)
+        );
+        assert_eq!(parse_source_date_epoch(" 0\n"), Some(0));
+        assert_eq!(parse_source_date_epoch("yesterday"), None);
+        assert_eq!(parse_source_date_epoch("9223372036854775807"), None);
+        assert_eq!(
+            build_timestamp(Some("1700000000".to_string()), false),
+            1_700_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_build_host_override() {
+        assert_eq!(
+            build_host(Some("ci-runner".to_string()), false),
+            "ci-runner"
+        );
+        assert_eq!(build_host(Some(String::new()), false), "");
+        assert_eq!(build_host(None, true), "");
+    }
+
+    #[test]
+    fn test_copyright_fallbacks() {
+        let mut issues = Vec::new();
+        assert_eq!(
+            copyright(
+                Some("Copyright (c) 2025 A\n".to_string()),
+                None,
+                2025,
+                &mut issues
+            ),
+            "Copyright (c) 2025 A"
+        );
+        assert!(issues.is_empty());
+
+        let authors = "Jane Doe <jane@example.com>:John";
+        assert_eq!(
+            copyright(None, Some(authors), 2025, &mut issues),
+            "Copyright (c) 2025 Jane Doe, John"
+        );
+        assert_eq!(issues.len(), 1);
+
+        assert_eq!(
+            copyright(Some("  ".to_string()), None, 2025, &mut issues),
+            "Copyright unknown"
+        );
+        assert_eq!(issues.len(), 2);
+    }
+
+    #[test]
+    fn test_license_fallbacks() {
+        let mut issues = Vec::new();
+        assert_eq!(
+            license(Some("MIT"), None, &mut issues),
+            ("MIT".to_string(), Some("LICENSE".to_string()))
+        );
+        assert_eq!(
+            license(None, Some("LICENSE.txt"), &mut issues),
+            ("Custom".to_string(), Some("LICENSE.txt".to_string()))
+        );
+        assert!(issues.is_empty());
+
+        assert_eq!(
+            license(None, None, &mut issues),
+            ("Unknown".to_string(), None)
+        );
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[test]
+    fn test_legacy_slash_license_is_normalized() {
+        let mut issues = Vec::new();
+        assert_eq!(
+            license(Some("MIT/Apache-2.0"), None, &mut issues).0,
+            "MIT OR Apache-2.0"
+        );
+    }
+
+    #[test]
+    fn test_spdx_license_ids() {
+        assert_eq!(spdx_license_ids("MIT").unwrap(), ["MIT"]);
+        assert_eq!(
+            spdx_license_ids("MIT OR Apache-2.0").unwrap(),
+            ["MIT", "Apache-2.0"]
+        );
+        assert_eq!(
+            spdx_license_ids("(MIT OR Apache-2.0) AND Unicode-3.0").unwrap(),
+            ["MIT", "Apache-2.0", "Unicode-3.0"]
+        );
+        assert_eq!(
+            spdx_license_ids("Apache-2.0 WITH LLVM-exception OR MIT OR Apache-2.0").unwrap(),
+            ["Apache-2.0", "MIT"]
+        );
+        assert_eq!(spdx_license_ids("GPL-2.0+").unwrap(), ["GPL-2.0"]);
+        assert_eq!(
+            spdx_license_ids("LicenseRef-Proprietary").unwrap(),
+            ["LicenseRef-Proprietary"]
+        );
+
+        assert!(spdx_license_ids("").is_err());
+        assert!(spdx_license_ids("MIT OR").is_err());
+        assert!(spdx_license_ids("(MIT OR Apache-2.0").is_err());
+        assert!(spdx_license_ids("MIT Apache-2.0").is_err());
+        assert!(spdx_license_ids("MIT, Apache-2.0").is_err());
+    }
+
+    #[test]
+    fn test_license_file_for() {
+        let files: Vec<String> = [
+            "COPYRIGHT",
+            "LICENSE-APACHE",
+            "LICENSE-MIT.txt",
+            "LICENSES/Zlib.txt",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        assert_eq!(
+            license_file_for("MIT", &files).as_deref(),
+            Some("LICENSE-MIT.txt")
+        );
+        assert_eq!(
+            license_file_for("Apache-2.0", &files).as_deref(),
+            Some("LICENSE-APACHE")
+        );
+        assert_eq!(
+            license_file_for("Zlib", &files).as_deref(),
+            Some("LICENSES/Zlib.txt")
+        );
+        assert_eq!(license_file_for("BSD-3-Clause", &files), None);
+    }
+
+    #[test]
+    fn test_one_link_per_license() {
+        let files = vec!["LICENSE-APACHE".to_string(), "LICENSE-MIT".to_string()];
+        let github = links("https://github.com/a/b", &[("branch", "main")]);
+        let mut issues = Vec::new();
+        let entries = licenses(
+            "MIT OR Apache-2.0 OR BSD-3-Clause",
+            None,
+            &files,
+            Some(&github),
+            false,
+            &mut issues,
+        );
+        let urls: Vec<_> = entries
+            .iter()
+            .map(|l| (l.id.as_str(), l.url.as_str()))
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                ("MIT", "https://github.com/a/b/blob/main/LICENSE-MIT"),
+                (
+                    "Apache-2.0",
+                    "https://github.com/a/b/blob/main/LICENSE-APACHE"
+                ),
+                (
+                    "BSD-3-Clause",
+                    "https://spdx.org/licenses/BSD-3-Clause.html"
+                ),
+            ]
+        );
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_single_license_links() {
+        let github = links("https://github.com/a/b", &[("branch", "main")]);
+        let mut issues = Vec::new();
+
+        // No license file yet: keep linking to LICENSE
+        let entries = licenses("MIT", None, &[], Some(&github), false, &mut issues);
+        assert_eq!(entries[0].url, "https://github.com/a/b/blob/main/LICENSE");
+
+        let files = vec!["COPYING".to_string()];
+        let entries = licenses(
+            "GPL-3.0-only",
+            None,
+            &files,
+            Some(&github),
+            false,
+            &mut issues,
+        );
+        assert_eq!(entries[0].url, "https://github.com/a/b/blob/main/COPYING");
+
+        // Without a repository, link to the canonical text
+        let entries = licenses("MIT", None, &[], None, false, &mut issues);
+        assert_eq!(entries[0].url, "https://spdx.org/licenses/MIT.html");
+
+        assert!(licenses("Unknown", None, &[], None, false, &mut issues).is_empty());
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_invalid_spdx_expression_is_reported() {
+        let mut issues = Vec::new();
+        let entries = licenses("MIT, Apache-2.0", None, &[], None, false, &mut issues);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, "MIT, Apache-2.0");
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[test]
+    fn test_missing_license_text_is_reported() {
+        let mut issues = Vec::new();
+        let files = vec!["LICENSE-NOT-ON-DISK".to_string()];
+        let entries = licenses("MIT OR Apache-2.0", None, &files, None, true, &mut issues);
+        assert!(entries.iter().all(|l| l.text.is_none()));
+        assert_eq!(issues.len(), 2);
+        assert!(issues[1].fix.contains("LICENSE-APACHE"));
+    }
+
+    #[test]
+    fn test_manifest_metadata() {
+        let manifest = r#"
+[package]
+name = "demo"
+license = "MIT"
+
+[package.metadata.sw-cli]
+branch = "master"   # default branch
+license-file = 'COPYING'
+forge = "gitea"
+reproducible = true
+
+[dependencies]
+branch = "ignored"
+"#;
+        let settings = manifest_metadata(manifest);
+        assert_eq!(setting(&settings, "branch").as_deref(), Some("master"));
+        assert_eq!(
+            setting(&settings, "license-file").as_deref(),
+            Some("COPYING")
+        );
+        assert_eq!(setting(&settings, "forge").as_deref(), Some("gitea"));
+        assert_eq!(setting(&settings, "reproducible").as_deref(), Some("true"));
+        assert_eq!(settings.len(), 4);
+    }
+
+    const LOCK: &str = r#"
+version = 4
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "clap",
+ "serde 1.0.200",
+]
+
+[[package]]
+name = "clap"
+version = "4.5.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "abc"
+dependencies = ["clap_lex"]
+
+[[package]]
+name = "clap_lex"
+version = "0.7.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde"
+version = "0.9.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "unrelated-member"
+version = "0.1.0"
+dependencies = [
+ "serde 0.9.0",
+]
+"#;
+
+    #[test]
+    fn test_parse_lockfile() {
+        let packages = parse_lockfile(LOCK);
+        assert_eq!(packages.len(), 6);
+        assert_eq!(packages[0].name, "app");
+        assert_eq!(packages[0].source, None);
+        assert_eq!(packages[0].dependencies, ["clap", "serde 1.0.200"]);
+        assert_eq!(packages[1].dependencies, ["clap_lex"]);
+        assert_eq!(packages[1].checksum.as_deref(), Some("abc"));
+        assert_eq!(
+            packages[1].source.as_deref(),
+            Some("registry+https://github.com/rust-lang/crates.io-index")
+        );
+    }
+
+    #[test]
+    fn test_dependency_closure() {
+        let packages = parse_lockfile(LOCK);
+        let closure: Vec<_> = dependency_closure(&packages, "app", "0.1.0")
+            .iter()
+            .map(|p| format!("{} {}", p.name, p.version))
+            .collect();
+        // Other workspace members and their dependencies are not included
+        assert_eq!(closure, ["clap 4.5.0", "clap_lex 0.7.0", "serde 1.0.200"]);
+        assert!(dependency_closure(&packages, "missing", "0.1.0").is_empty());
+    }
+
+    #[test]
+    fn test_manifest_license() {
+        let manifest = "[package]\nname = \"a\"\nlicense = \"MIT/Apache-2.0\"\n\n[dependencies]\nlicense = \"x\"\n";
+        assert_eq!(
+            manifest_license(manifest).as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+        let manifest = "[package]\nname = \"a\"\nlicense-file = \"LICENSE.txt\"\n";
+        assert_eq!(
+            manifest_license(manifest).as_deref(),
+            Some("Custom (LICENSE.txt)")
+        );
+        assert_eq!(manifest_license("[package]\nname = \"a\"\n"), None);
+    }
+
+    #[test]
+    fn test_find_manifest_in_vendor_dir() {
+        let dir = std::env::temp_dir().join(format!("sw-cli-vendor-{}", std::process::id()));
+        let crate_dir = dir.join("vendor").join("clap");
+        fs::create_dir_all(&crate_dir).unwrap();
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"clap\"\nversion = \"4.5.0\"\nlicense = \"MIT OR Apache-2.0\"\n",
+        )
+        .unwrap();
+
+        let packages = parse_lockfile(LOCK);
+        let dirs = crate_source_dirs(&dir);
+        let manifest = find_manifest(&packages[1], &dir, &dirs);
+        let other_version = find_manifest(&packages[3], &dir, &dirs);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            manifest.as_deref().and_then(manifest_license).as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+        assert_eq!(other_version, None);
+    }
+
+    #[test]
+    fn test_forge_detection() {
+        assert_eq!(Forge::detect("https://github.com/a/b"), Forge::GitHub);
+        assert_eq!(Forge::detect("https://gitlab.com/a/b"), Forge::GitLab);
+        assert_eq!(
+            Forge::detect("https://gitlab.example.org/a/b"),
+            Forge::GitLab
+        );
+        assert_eq!(Forge::detect("https://codeberg.org/a/b"), Forge::Gitea);
+        assert_eq!(Forge::detect("https://git.sr.ht/~a/b"), Forge::SourceHut);
+        assert_eq!(Forge::detect("https://git.example.com/a/b"), Forge::GitHub);
+        assert_eq!(Forge::parse("SourceHut"), Some(Forge::SourceHut));
+        assert_eq!(Forge::parse("bitbucket"), None);
+    }
+
+    fn links(repository: &str, settings: &[(&str, &str)]) -> Links {
+        let settings: Vec<(String, String)> = settings
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect();
+        let mut issues = Vec::new();
+        let links = Links::new(repository, &settings, &sample().git, &mut issues).unwrap();
+        assert!(issues.is_empty());
+        links
+    }
+
+    #[test]
+    fn test_forge_links() {
+        let github = links("https://github.com/a/b.git", &[("branch", "master")]);
+        assert_eq!(
+            github.file_url("LICENSE"),
+            "https://github.com/a/b/blob/master/LICENSE"
+        );
+        assert_eq!(
+            github.commit_url().as_deref(),
+            Some("https://github.com/a/b/commit/abc123def456")
+        );
+
+        let gitlab = links("https://gitlab.com/a/b/", &[("branch", "main")]);
+        assert_eq!(
+            gitlab.file_url("LICENSE"),
+            "https://gitlab.com/a/b/-/blob/main/LICENSE"
+        );
+        assert_eq!(
+            gitlab.commit_url().as_deref(),
+            Some("https://gitlab.com/a/b/-/commit/abc123def456")
+        );
+
+        let gitea = links("https://codeberg.org/a/b", &[("branch", "main")]);
+        assert_eq!(
+            gitea.file_url("COPYING"),
+            "https://codeberg.org/a/b/src/branch/main/COPYING"
+        );
+
+        let srht = links("https://git.sr.ht/~a/b", &[("branch", "trunk")]);
+        assert_eq!(
+            srht.file_url("LICENSE"),
+            "https://git.sr.ht/~a/b/tree/trunk/item/LICENSE"
+        );
+        assert_eq!(
+            srht.commit_url().as_deref(),
+            Some("https://git.sr.ht/~a/b/commit/abc123def456")
+        );
+    }
+
+    #[test]
+    fn test_links_fall_back_to_the_commit() {
+        let mut git = sample().git;
+        git.branch = Some("feature/x".to_string());
+        let links_with = |git: &GitState, default: Option<&str>| {
+            Links::with_default_branch(
+                "https://github.com/a/b",
+                &[],
+                git,
+                || default.map(str::to_string),
+                &mut Vec::new(),
+            )
+            .unwrap()
+        };
+
+        let links = links_with(&git, Some("trunk"));
+        assert_eq!(
+            links.file_url("LICENSE"),
+            "https://github.com/a/b/blob/trunk/LICENSE"
+        );
+        // Not the checked-out branch, which may be gone after the release
+        let links = links_with(&git, None);
+        assert_eq!(
+            links.file_url("LICENSE"),
+            "https://github.com/a/b/blob/abc123def456/LICENSE"
+        );
+        git.commit_sha = "unknown".to_string();
+        let links = links_with(&git, None);
+        assert_eq!(
+            links.file_url("LICENSE"),
+            "https://github.com/a/b/blob/main/LICENSE"
+        );
+    }
+
+    #[test]
+    fn test_links_to_commit() {
+        let github = links("https://github.com/a/b", &[("link-to", "commit")]);
+        assert_eq!(
+            github.file_url("LICENSE-MIT"),
+            "https://github.com/a/b/blob/abc123def456/LICENSE-MIT"
+        );
+
+        let gitea = links(
+            "https://git.example.com/a/b",
+            &[("link-to", "commit"), ("forge", "gitea")],
+        );
+        assert_eq!(
+            gitea.file_url("LICENSE"),
+            "https://git.example.com/a/b/src/commit/abc123def456/LICENSE"
+        );
+    }
+
+    #[test]
+    fn test_unknown_forge_is_reported() {
+        let settings = vec![("forge".to_string(), "bitbucket".to_string())];
+        let mut issues = Vec::new();
+        let links = Links::new(
+            "https://github.com/a/b",
+            &settings,
+            &sample().git,
+            &mut issues,
+        );
+        assert_eq!(links.unwrap().forge, Forge::GitHub);
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[test]
+    fn test_year_of() {
+        assert_eq!(year_of(0), 1970);
+        assert_eq!(year_of(1_700_000_000_000), 2023);
+        assert_eq!(year_of(1_735_689_599_999), 2024); // 2024-12-31T23:59:59.999Z
+        assert_eq!(year_of(1_735_689_600_000), 2025);
+        assert_eq!(year_of(-1), 1969);
+    }
+
+    #[test]
+    fn test_vcs_info_sha() {
+        let json = r#"{
+  "git": {
+    "sha1": "abc123def4567890abc123def4567890abc123de"
+  },
+  "path_in_vcs": ""
+}"#;
+        assert_eq!(
+            vcs_info_sha(json).as_deref(),
+            Some("abc123def4567890abc123def4567890abc123de")
+        );
+        assert_eq!(vcs_info_sha(r#"{"git": {"sha1": "not hex"}}"#), None);
+        assert_eq!(vcs_info_sha("{}"), None);
+    }
+
+    #[test]
+    fn test_strict_failure_message() {
+        let issues = vec![Issue::new("COPYRIGHT file not found", "create it")];
+        let message = strict_failure_message(&issues);
+        assert!(message.starts_with("error: define_build_info!(strict)"));
+        assert!(message.contains("  - COPYRIGHT file not found\n    fix: create it\n"));
+    }
+
+    #[test]
+    fn test_rendered_code_parses() {
+        syn::parse_file(&render(&sample())).expect("generated code must be valid Rust");
+    }
+
+    /// Parse the generated file and return the value of a `&str` constant
+    fn const_str(code: &str, name: &str) -> String {
+        let file = syn::parse_file(code).expect("generated code must be valid Rust");
+        for item in file.items {
+            if let syn::Item::Const(item) = item
+                && item.ident == name
+                && let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(lit),
+                    ..
+                }) = *item.expr
+            {
+                return lit.value();
+            }
+        }
+        panic!("constant {name} not found");
+    }
+
+    const HOSTILE: &[&str] = &[
+        r#"Copyright (c) 2025 "Quoted" Corp"#,
+        r"C:\path\with\backslashes\",
+        "Copyright (c) 2024 First Holder\nCopyright (c) 2025 Second Holder\r\n",
+        "escape \u{1b}[31m and bell \u{7} and nul \0",
+        r"unicode escape text \u{41} and raw \x41",
+        "\"; pub const INJECTED: &str = \"",
+        r###""# and "## look like raw-string delimiters"###,
+        "braces {} {{}} and {:?}",
+        "non-ASCII: © Ünïcödé 日本 🦀",
+    ];
+
+    #[test]
+    fn test_hostile_strings_round_trip() {
+        for input in HOSTILE {
+            let mut meta = sample();
+            meta.version = (*input).to_string();
+            meta.copyright = (*input).to_string();
+            meta.license_name = (*input).to_string();
+            meta.license_url = (*input).to_string();
+            meta.repository = (*input).to_string();
+            meta.build_host = (*input).to_string();
+            meta.git.commit_sha = (*input).to_string();
+            meta.toolchain.rustc_version = (*input).to_string();
+
+            let code = render(&meta);
+            for name in [
+                "VERSION",
+                "COPYRIGHT",
+                "LICENSE_NAME",
+                "LICENSE_URL",
+                "REPOSITORY",
+                "BUILD_HOST",
+                "GIT_COMMIT_SHA",
+                "RUSTC_VERSION",
+            ] {
+                assert_eq!(const_str(&code, name), *input, "{name} for {input:?}");
+            }
+            let file = syn::parse_file(&code).expect("generated code must be valid Rust");
+            assert_eq!(
+                file.items.len(),
+                render(&sample()).matches("pub const").count()
+            );
+        }
+    }
+
+    #[test]
+    fn test_hostile_optional_strings_and_features() {
+        let mut meta = sample();
+        meta.git.branch = Some("feature/\"quotes\"\\and\\slashes".to_string());
+        meta.toolchain.features = vec!["a\"b".to_string(), "c\nd".to_string()];
+        let code = render(&meta);
+        syn::parse_file(&code).expect("generated code must be valid Rust");
+        assert!(code.contains(
+            r#"pub const GIT_BRANCH: Option<&str> = Some("feature/\"quotes\"\\and\\slashes");"#
+        ));
+        assert!(code.contains(r#"pub const CARGO_FEATURES: &[&str] = &["a\"b", "c\nd"];"#));
+    }
+
+    #[test]
+    fn test_multiline_copyright_preserved() {
+        let mut issues = Vec::new();
+        let text = copyright(
+            Some("Copyright (c) 2024 First Holder\nCopyright (c) 2025 Second Holder\n".to_string()),
+            None,
+            2025,
+            &mut issues,
+        );
+        let mut meta = sample();
+        meta.copyright = text;
+        assert_eq!(
+            const_str(&render(&meta), "COPYRIGHT"),
+            "Copyright (c) 2024 First Holder\nCopyright (c) 2025 Second Holder"
+        );
+    }
+
+    #[test]
+    fn test_non_utf8_hostname() {
+        // "h\xF6st" in Latin-1, followed by the newline `hostname` prints
+        let host = decode_output(b"h\xF6st\n");
+        assert_eq!(host, "h\u{FFFD}st");
+
+        let mut meta = sample();
+        meta.build_host = host;
+        assert_eq!(const_str(&render(&meta), "BUILD_HOST"), "h\u{FFFD}st");
+    }
+}
@@ -1,6 +1,17 @@
+// Spliced into callers' build scripts by `define_build_info!()`; compiled here
+// only so its pure parts can be unit tested.
+#[cfg(test)]
+#[allow(dead_code)]
+mod build_script;
+
 use proc_macro::TokenStream;
-use quote::{quote, format_ident};
-use syn::{Expr, parse_macro_input, Ident, Type, LitStr, LitChar, Token, parse::{Parse, ParseStream}, punctuated::Punctuated};
+use quote::{format_ident, quote};
+use syn::{
+    Expr, Ident, LitBool, LitChar, LitStr, Token, Type,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+};
 
 /// Returns a formatted version string with build information.
 ///
@@ -26,33 +37,87 @@ use syn::{Expr, parse_macro_input, Ident, Type, LitStr, LitChar, Token, parse::{
 /// Requires `sw_cli::define_build_info!()` to be called in build.rs.
 #[proc_macro]
 pub fn version(_input: TokenStream) -> TokenStream {
+    let version = version_expr();
     let expanded = quote! {
+        {
+            let version_obj = #version;
+            format!("{}", version_obj)
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Returns the build information as a `::sw_cli::version::VersionInfo`.
+///
+/// # Usage
+///
+/// ```ignore
+/// static BUILD: sw_cli::version::VersionInfo = sw_cli::build_info!();
+///
+/// fn main() {
+///     let info = sw_cli::build_info!();
+///     println!("{} built from {} at {}", info.version, info.short_sha(), info.build_datetime());
+/// }
+/// ```
+///
+/// Every field is `'static` data read from the generated constants, so the
+/// expression is valid in `const` and `static` initializers and reading it
+/// does not allocate. Use `.to_version()` for an owned `Version`, e.g. to
+/// render JSON.
+///
+/// Requires `sw_cli::define_build_info!()` to be called in build.rs.
+#[proc_macro]
+pub fn build_info(_input: TokenStream) -> TokenStream {
+    TokenStream::from(info_expr())
+}
+
+/// Expression that includes the caller's `version_info.rs` and evaluates to a
+/// `::sw_cli::version::VersionInfo`. Shared by the macros that need the
+/// caller's build information.
+fn info_expr() -> proc_macro2::TokenStream {
+    quote! {
         {
             // Include the generated version_info.rs
             mod __version_info {
                 include!(concat!(env!("OUT_DIR"), "/version_info.rs"));
             }
 
-            // Construct Version and return formatted string
-            let build_info = ::sw_cli::version::BuildInfo::new(
-                __version_info::BUILD_HOST.to_string(),
-                __version_info::GIT_COMMIT_SHA.to_string(),
-                __version_info::BUILD_TIMESTAMP,
-            );
-
-            let version_obj = ::sw_cli::version::Version::new(
-                __version_info::VERSION.to_string(),
-                __version_info::COPYRIGHT.to_string(),
-                __version_info::LICENSE_NAME.to_string(),
-                __version_info::LICENSE_URL.to_string(),
-                build_info,
-            );
-
-            format!("{}", version_obj)
+            ::sw_cli::version::VersionInfo {
+                name: __version_info::PACKAGE_NAME,
+                version: __version_info::VERSION,
+                copyright: __version_info::COPYRIGHT,
+                license_name: __version_info::LICENSE_NAME,
+                license_url: __version_info::LICENSE_URL,
+                licenses: __version_info::LICENSES,
+                repository: __version_info::REPOSITORY,
+                commit_url: __version_info::COMMIT_URL,
+                build_host: __version_info::BUILD_HOST,
+                commit_sha: __version_info::GIT_COMMIT_SHA,
+                build_timestamp_ms: __version_info::BUILD_TIMESTAMP,
+                git_branch: __version_info::GIT_BRANCH,
+                git_tag: __version_info::GIT_TAG,
+                git_describe: __version_info::GIT_DESCRIBE,
+                dirty: __version_info::GIT_DIRTY,
+                rustc_version: __version_info::RUSTC_VERSION,
+                target: __version_info::BUILD_TARGET,
+                profile: __version_info::BUILD_PROFILE,
+                opt_level: __version_info::OPT_LEVEL,
+                features: __version_info::CARGO_FEATURES,
+                dependencies: __version_info::DEPENDENCIES,
+                update_manifest: __version_info::UPDATE_MANIFEST,
+                update_check: __version_info::UPDATE_CHECK,
+                update_public_key: __version_info::UPDATE_PUBLIC_KEY,
+            }
         }
-    };
+    }
+}
 
-    TokenStream::from(expanded)
+/// Expression evaluating to an owned `::sw_cli::version::Version`.
+/// Shared by `version!()` and `web_footer!()`.
+fn version_expr() -> proc_macro2::TokenStream {
+    let info = info_expr();
+    quote! { #info.to_version() }
 }
 
 /// Deprecated: Use `version!()` instead.
@@ -62,6 +127,73 @@ pub fn create_version(input: TokenStream) -> TokenStream {
     version(input)
 }
 
+/// Returns an HTML footer with version information as a `String`.
+///
+/// # Usage
+///
+/// ```ignore
+/// let footer = sw_cli::web_footer!();
+/// let footer = sw_cli::web_footer!(style = "rich", class = "app-footer");
+/// ```
+///
+/// # Options
+/// - `style` - `"plain"` (default, single line) or `"rich"` (one element per field)
+/// - `class` - CSS class of the `<footer>` element (default `"sw-footer"`)
+///
+/// Requires `sw_cli::define_build_info!()` to be called in build.rs.
+/// No web framework is needed; the result is plain escaped HTML.
+#[proc_macro]
+pub fn web_footer(input: TokenStream) -> TokenStream {
+    let options = parse_macro_input!(input with Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated);
+
+    let mut style = quote! { ::sw_cli::version::html::FooterStyle::Plain };
+    let mut class = quote! { ::sw_cli::version::html::DEFAULT_FOOTER_CLASS };
+
+    for option in &options {
+        let value = match &option.value {
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(s),
+                ..
+            }) => s,
+            other => {
+                return syn::Error::new_spanned(other, "expected a string literal")
+                    .to_compile_error()
+                    .into();
+            }
+        };
+        if option.path.is_ident("style") {
+            style = match value.value().as_str() {
+                "plain" => quote! { ::sw_cli::version::html::FooterStyle::Plain },
+                "rich" => quote! { ::sw_cli::version::html::FooterStyle::Rich },
+                _ => {
+                    return syn::Error::new(value.span(), "style must be \"plain\" or \"rich\"")
+                        .to_compile_error()
+                        .into();
+                }
+            };
+        } else if option.path.is_ident("class") {
+            class = quote! { #value };
+        } else {
+            return syn::Error::new_spanned(
+                &option.path,
+                "unknown option (expected `style` or `class`)",
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+
+    let version = version_expr();
+    let expanded = quote! {
+        {
+            let version_obj = #version;
+            ::sw_cli::version::html::format_footer_styled(&version_obj, #style, #class)
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
 /// Generates `version_info.rs` with build metadata as const literals.
 ///
 /// This macro should be called in your build.rs file to generate version information.
@@ -78,85 +210,79 @@ pub fn create_version(input: TokenStream) -> TokenStream {
 /// - COPYRIGHT file in project root
 /// - Cargo.toml with version, license, repository fields
 ///
+/// Missing metadata produces a `cargo:warning` and a fallback: copyright from
+/// `authors`, a `license-file` link instead of `license`, and the commit from
+/// `SW_CLI_COMMIT_SHA` or `.cargo_vcs_info.json` outside a git checkout.
+/// `define_build_info!(strict)` (or `SW_CLI_STRICT=1`) fails the build instead.
+///
+/// # Reproducible builds
+/// `SOURCE_DATE_EPOCH` (seconds) always sets the build timestamp, and
+/// `SW_CLI_BUILD_HOST` overrides the recorded host (empty omits it).
+///
+/// `define_build_info!(reproducible)` (or `SW_CLI_REPRODUCIBLE=1`) additionally
+/// uses the commit time when `SOURCE_DATE_EPOCH` is unset, omits the host
+/// unless overridden, and emits `cargo:rerun-if-changed` lines for `.git/HEAD`,
+/// the current ref, tags, packed refs and the index instead of always rerunning.
+///
+/// # Licenses
+/// `license` is parsed as an SPDX expression (`MIT OR Apache-2.0`) and each
+/// license gets its own link, to `LICENSE-<ID>` / `LICENSES/<id>.txt` when
+/// present. `define_build_info!(embed_licenses)` also embeds the license
+/// texts so `--license` can print them offline.
+///
 /// # Generates
-/// Creates `$OUT_DIR/version_info.rs` with const literals for runtime use.
-/// Always runs on every build (unique timestamp per build).
+/// Creates `$OUT_DIR/version_info.rs` with const literals for runtime use:
+/// package metadata, build host and timestamp, and the git commit SHA, branch,
+/// nearest tag, `git describe --always --dirty` output and dirty-tree flag.
+/// Outside reproducible mode it reruns on every build (unique timestamp per build).
 #[proc_macro]
-pub fn define_build_info(_input: TokenStream) -> TokenStream {
+pub fn define_build_info(input: TokenStream) -> TokenStream {
+    let options = parse_macro_input!(input with Punctuated::<Ident, Token![,]>::parse_terminated);
+
+    let mut reproducible = false;
+    let mut strict = false;
+    let mut embed_licenses = false;
+    for option in &options {
+        match option.to_string().as_str() {
+            "reproducible" => reproducible = true,
+            "strict" => strict = true,
+            "embed_licenses" => embed_licenses = true,
+            _ => {
+                return syn::Error::new(
+                    option.span(),
+                    "unknown option (expected `reproducible`, `strict` or `embed_licenses`)",
+                )
+                .to_compile_error()
+                .into();
+            }
+        }
+    }
+
+    let build_script = build_script_tokens();
     let expanded = quote! {
         {
-            use std::process::Command;
-            use std::fs;
-            use std::path::Path;
-
-            let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR not set");
-            let dest_path = Path::new(&out_dir).join("version_info.rs");
-
-            // Read COPYRIGHT file
-            let copyright = fs::read_to_string("COPYRIGHT")
-                .expect("COPYRIGHT file not found")
-                .trim()
-                .to_string();
-
-            // Get version from Cargo
-            let version = std::env::var("CARGO_PKG_VERSION")
-                .expect("CARGO_PKG_VERSION not set");
-
-            // Get license from Cargo
-            let license_name = std::env::var("CARGO_PKG_LICENSE")
-                .expect("CARGO_PKG_LICENSE not set");
-
-            // Get repository from Cargo
-            let repository = std::env::var("CARGO_PKG_REPOSITORY")
-                .expect("CARGO_PKG_REPOSITORY not set");
-            let license_url = format!("{}/blob/main/LICENSE", repository);
-
-            // Get hostname
-            let hostname = Command::new("hostname")
-                .output()
-                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
-                .unwrap_or_else(|_| "unknown".to_string());
-
-            // Get git commit SHA
-            let commit_sha = Command::new("git")
-                .args(["rev-parse", "HEAD"])
-                .output()
-                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
-                .unwrap_or_else(|_| "unknown".to_string());
-
-            // Get build timestamp (always fresh, every build)
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_millis() as i64;
-
-            // Generate version_info.rs
-            let generated_code = format!(
-                r#"// Generated by sw_cli::define_build_info!()
-// Do not edit manually
-
-pub const VERSION: &str = "{}";
-pub const COPYRIGHT: &str = "{}";
-pub const LICENSE_NAME: &str = "{}";
-pub const LICENSE_URL: &str = "{}";
-pub const BUILD_HOST: &str = "{}";
-pub const GIT_COMMIT_SHA: &str = "{}";
-#[allow(clippy::unreadable_literal)]
-pub const BUILD_TIMESTAMP: i64 = {};
-"#,
-                version, copyright, license_name, license_url, hostname, commit_sha, timestamp
-            );
-
-            fs::write(&dest_path, generated_code)
-                .expect("Failed to write version_info.rs");
+            mod __sw_cli_build_info {
+                #build_script
+            }
 
-            // Always rerun (no rerun-if-changed) - each build gets unique timestamp
+            __sw_cli_build_info::generate(&__sw_cli_build_info::Options {
+                reproducible: #reproducible,
+                strict: #strict,
+                embed_licenses: #embed_licenses,
+            });
         }
     };
 
     TokenStream::from(expanded)
 }
 
+/// Source of `build_script.rs` as tokens, for splicing into the caller's build.rs
+fn build_script_tokens() -> proc_macro2::TokenStream {
+    include_str!("build_script.rs")
+        .parse()
+        .expect("build_script.rs must tokenize")
+}
+
 /// Generates `help_info.rs` with help text from src/help.txt or src/short-help.txt and src/long-help.txt files.
 ///
 /// This macro should be called in your build.rs file to generate help information.
@@ -300,7 +426,10 @@ pub fn long_help(_input: TokenStream) -> TokenStream {
 /// ```
 ///
 /// Automatically includes short and long help text from generated `help_info.rs`
-/// and registers `VersionCommand` and `HelpCommand` internally.
+/// and the application's build information from `version_info.rs`,
+/// and registers the built-in `VersionCommand`, `HelpCommand`, `LicenseCommand`,
+/// `NoticesCommand`, `SbomCommand`, `UpdateCheckCommand`, `SelfUpdateCommand`
+/// and `PrintConfigCommand` internally.
 #[proc_macro]
 pub fn dispatch(input: TokenStream) -> TokenStream {
     let commands = parse_macro_input!(input with syn::punctuated::Punctuated::<Expr, syn::Token![,]>::parse_terminated);
@@ -308,6 +437,7 @@ pub fn dispatch(input: TokenStream) -> TokenStream {
     let command_registrations = commands.iter().map(|cmd| {
         quote! { .register(#cmd) }
     });
+    let info = info_expr();
 
     let expanded = quote! {
         {
@@ -316,7 +446,8 @@ pub fn dispatch(input: TokenStream) -> TokenStream {
                 include!(concat!(env!("OUT_DIR"), "/help_info.rs"));
             }
 
-            ::sw_cli::Dispatcher::new(
+            ::sw_cli::Dispatcher::with_version(
+                #info,
                 __help_info::SHORT_HELP.to_string(),
                 __help_info::LONG_HELP.to_string()
             )
@@ -338,6 +469,23 @@ struct FieldDef {
     long: Option<String>,
     help: Option<String>,
     action: Option<String>,
+    env: Option<String>,
+    /// `range = 1..=100`
+    range: Option<Expr>,
+    /// `regex = "..."`
+    regex: Option<LitStr>,
+    /// `required`
+    required: bool,
+    /// `exists`
+    exists: bool,
+    /// `cli_only`: no variable or config file sets the value
+    cli_only: bool,
+    /// `requires = "field"`, repeatable
+    requires: Vec<LitStr>,
+    /// `conflicts_with = "field"`, repeatable
+    conflicts_with: Vec<LitStr>,
+    /// `default = "text"`, `default = 1` or `default = ["a", "b"]`
+    default: Option<Expr>,
 }
 
 impl Parse for FieldDef {
@@ -350,17 +498,41 @@ impl Parse for FieldDef {
         let mut long = None;
         let mut help = None;
         let mut action = None;
+        let mut env = None;
+        let mut range = None;
+        let mut regex = None;
+        let mut required = false;
+        let mut exists = false;
+        let mut cli_only = false;
+        let mut required_fields = Vec::new();
+        let mut conflicts_with = Vec::new();
+        let mut default = None;
 
         // Parse optional attributes (comma followed by key = value)
         while input.peek(Token![,]) {
             // Look ahead to see if this is an attribute or next field
             // Attributes: comma, ident, =
+            // Flags: comma, `required`, `exists` or `cli_only`, then comma or end
             // Next field: comma, ident, :
             let fork = input.fork();
             fork.parse::<Token![,]>()?;
             if fork.peek(Ident) {
-                let _test_ident: Ident = fork.parse()?;
-                if fork.peek(Token![=]) {
+                let test_ident: Ident = fork.parse()?;
+                let is_flag = matches!(
+                    test_ident.to_string().as_str(),
+                    "required" | "exists" | "cli_only"
+                ) && (fork.is_empty() || fork.peek(Token![,]));
+                if is_flag {
+                    input.parse::<Token![,]>()?;
+                    let flag: Ident = input.parse()?;
+                    if flag == "required" {
+                        required = true;
+                    } else if flag == "exists" {
+                        exists = true;
+                    } else {
+                        cli_only = true;
+                    }
+                } else if fork.peek(Token![=]) {
                     // This is an attribute
                     input.parse::<Token![,]>()?;
                     let attr: Ident = input.parse()?;
@@ -383,6 +555,15 @@ impl Parse for FieldDef {
                             let a: Ident = input.parse()?;
                             action = Some(a.to_string());
                         }
+                        "env" => {
+                            let s: LitStr = input.parse()?;
+                            env = Some(s.value());
+                        }
+                        "range" => range = Some(input.parse()?),
+                        "regex" => regex = Some(input.parse()?),
+                        "requires" => required_fields.push(input.parse()?),
+                        "conflicts_with" => conflicts_with.push(input.parse()?),
+                        "default" => default = Some(input.parse()?),
                         _ => return Err(syn::Error::new(attr.span(), "unknown attribute")),
                     }
                 } else {
@@ -394,7 +575,23 @@ impl Parse for FieldDef {
             }
         }
 
-        Ok(FieldDef { name, ty, short, long, help, action })
+        Ok(FieldDef {
+            name,
+            ty,
+            short,
+            long,
+            help,
+            action,
+            env,
+            range,
+            regex,
+            required,
+            exists,
+            cli_only,
+            requires: required_fields,
+            conflicts_with,
+            default,
+        })
     }
 }
 
@@ -402,6 +599,8 @@ struct CliAppInput {
     name: String,
     about: String,
     config_name: Ident,
+    env_prefix: Option<String>,
+    strict_config: bool,
     fields: Vec<FieldDef>,
 }
 
@@ -410,6 +609,8 @@ impl Parse for CliAppInput {
         let mut name = None;
         let mut about = None;
         let mut config_name = None;
+        let mut env_prefix = None;
+        let mut strict_config = false;
         let mut fields = Vec::new();
 
         while !input.is_empty() {
@@ -432,10 +633,21 @@ impl Parse for CliAppInput {
                     config_name = Some(id);
                     input.parse::<Token![,]>()?;
                 }
+                "env_prefix" => {
+                    let s: LitStr = input.parse()?;
+                    env_prefix = Some(s.value());
+                    input.parse::<Token![,]>()?;
+                }
+                "strict_config" => {
+                    let b: LitBool = input.parse()?;
+                    strict_config = b.value();
+                    input.parse::<Token![,]>()?;
+                }
                 "fields" => {
                     let content;
                     syn::braced!(content in input);
-                    let parsed_fields = Punctuated::<FieldDef, Token![,]>::parse_terminated(&content)?;
+                    let parsed_fields =
+                        Punctuated::<FieldDef, Token![,]>::parse_terminated(&content)?;
                     fields = parsed_fields.into_iter().collect();
                     if !input.is_empty() {
                         input.parse::<Token![,]>()?;
@@ -449,11 +661,182 @@ impl Parse for CliAppInput {
             name: name.ok_or_else(|| input.error("missing 'name'"))?,
             about: about.ok_or_else(|| input.error("missing 'about'"))?,
             config_name: config_name.ok_or_else(|| input.error("missing 'config'"))?,
+            env_prefix,
+            strict_config,
             fields,
         })
     }
 }
 
+/// The type each value of a numeric field parses to, e.g. `u16` for `Option<u16>`
+fn numeric_type(ty_string: &str) -> Option<proc_macro2::TokenStream> {
+    const NUMERIC: [&str; 12] = [
+        "usize", "u64", "u32", "u16", "u8", "isize", "i64", "i32", "i16", "i8", "f64", "f32",
+    ];
+    let name = NUMERIC.iter().find(|ty| ty_string.contains(*ty))?;
+    let ident = format_ident!("{}", name);
+    Some(quote!(#ident))
+}
+
+/// The clap default value(s) of a `default = ...` literal, or array of
+/// literals for list fields
+fn default_texts(expr: &Expr) -> syn::Result<Vec<String>> {
+    match expr {
+        Expr::Array(array) => array
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                Expr::Array(_) => Err(syn::Error::new_spanned(elem, "expected a literal")),
+                _ => Ok(default_texts(elem)?.remove(0)),
+            })
+            .collect(),
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) => Ok(vec![s.value()]),
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(_) | syn::Lit::Float(_) | syn::Lit::Bool(_),
+            ..
+        })
+        | Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            ..
+        }) => Ok(vec![quote!(#expr).to_string().replace(' ', "")]),
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "`default` needs a literal or an array of literals",
+        )),
+    }
+}
+
+/// The value of a field that no layer sets: its `default = ...` converted to
+/// the field's type, else `Default::default()`
+fn default_value(f: &FieldDef) -> proc_macro2::TokenStream {
+    let Some(default) = &f.default else {
+        return quote! { ::std::default::Default::default() };
+    };
+    let ty = &f.ty;
+    let ty_string = quote!(#ty).to_string();
+    let convert = |literal: &Expr| {
+        if ty_string.contains("PathBuf") {
+            quote! { ::std::path::PathBuf::from(#literal) }
+        } else if ty_string.contains("String") {
+            quote! { ::std::string::String::from(#literal) }
+        } else {
+            quote! { #literal }
+        }
+    };
+    let value = match default {
+        Expr::Array(array) => {
+            let elems = array.elems.iter().map(convert);
+            quote! { ::std::vec![#(#elems),*] }
+        }
+        literal => convert(literal),
+    };
+    if ty_string.starts_with("Option") {
+        quote! { ::std::option::Option::Some(#value) }
+    } else {
+        value
+    }
+}
+
+/// Reject validation attributes that do not fit the field they are on
+fn check_rules(fields: &[FieldDef]) -> syn::Result<()> {
+    for f in fields {
+        let ty = &f.ty;
+        let ty_string = quote!(#ty).to_string();
+        if let Some(range) = &f.range
+            && (numeric_type(&ty_string).is_none() || ty_string.contains("Vec"))
+        {
+            return Err(syn::Error::new_spanned(
+                range,
+                "`range` needs a numeric field",
+            ));
+        }
+        if let Some(regex) = &f.regex {
+            if !ty_string.contains("String") {
+                return Err(syn::Error::new(
+                    regex.span(),
+                    "`regex` needs a String field",
+                ));
+            }
+            if !cfg!(feature = "regex") {
+                return Err(syn::Error::new(
+                    regex.span(),
+                    "`regex` needs the `regex` feature of sw-cli",
+                ));
+            }
+        }
+        if let Some(default) = &f.default {
+            if ty_string == "bool" {
+                return Err(syn::Error::new_spanned(
+                    default,
+                    "flags are always false by default",
+                ));
+            }
+            if matches!(default, Expr::Array(_)) && !ty_string.contains("Vec") {
+                return Err(syn::Error::new_spanned(
+                    default,
+                    "a list default needs a Vec field",
+                ));
+            }
+            default_texts(default)?;
+        } else if ty_string != "bool"
+            && !ty_string.starts_with("Option")
+            && !ty_string.starts_with("Vec")
+        {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "fields without `default = ...` must be bool, Vec or Option",
+            ));
+        }
+        if let (true, Some(env)) = (f.cli_only, &f.env) {
+            return Err(syn::Error::new(
+                f.name.span(),
+                format!("`cli_only` fields cannot be bound to {env}"),
+            ));
+        }
+        if f.exists && !ty_string.contains("PathBuf") {
+            return Err(syn::Error::new(
+                f.name.span(),
+                "`exists` needs a PathBuf field",
+            ));
+        }
+        for other in f.requires.iter().chain(&f.conflicts_with) {
+            if f.name == other.value() || !fields.iter().any(|g| g.name == other.value()) {
+                return Err(syn::Error::new(
+                    other.span(),
+                    "expected the name of another field",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether the field called `name` is `cli_only`
+fn cli_only_field(fields: &[FieldDef], name: &LitStr) -> bool {
+    fields.iter().any(|g| g.cli_only && g.name == name.value())
+}
+
+/// Whether a layer set the field: true for flags, a value for options,
+/// an item for lists, else a recorded source
+fn field_is_set(f: &FieldDef) -> proc_macro2::TokenStream {
+    let name = &f.name;
+    let name_str = name.to_string();
+    let ty = &f.ty;
+    let ty_string = quote!(#ty).to_string();
+    if ty_string == "bool" {
+        quote! { self.#name }
+    } else if ty_string.starts_with("Option") {
+        quote! { self.#name.is_some() }
+    } else if ty_string.starts_with("Vec") {
+        quote! { !self.#name.is_empty() }
+    } else {
+        quote! { self.base.sources.contains_key(#name_str) }
+    }
+}
+
 /// Generates a complete CLI application with config struct, builder, parser, and main function.
 ///
 /// # Usage
@@ -463,26 +846,84 @@ impl Parse for CliAppInput {
 ///     name: "my-app",
 ///     about: "My CLI application",
 ///     config: MyAppConfig,
+///     env_prefix: "MYAPP",
 ///     fields: {
 ///         input: Option<Vec<PathBuf>>, short = 'i', long = "input", help = "Input files", action = Append,
-///         output: Option<PathBuf>, short = 'o', long = "output", help = "Output file",
+///         output: Option<PathBuf>, short = 'o', long = "output", help = "Output file", env = "MYAPP_OUT",
 ///         count: bool, long = "count", help = "Count lines",
 ///     }
 /// }
 /// ```
 ///
+/// Every field and the `verbose`, `quiet`, `dry_run` and `format` flags can
+/// be set from the environment as `<PREFIX>_<FIELD>`. The prefix defaults to
+/// the app name in upper case with `-` replaced by `_` (`MY_APP` here without
+/// `env_prefix`); `env = "..."` binds a field to another variable.
+///
+/// A config file key that is not a setting, such as a misspelt one, is
+/// reported as a warning; `strict_config: true` makes it an error.
+///
+/// Non-flag fields are `Option<T>` or `Vec<T>` (empty when unset), or a
+/// plain `T` with `default = ...`: a literal such as `"text"`, `1` or `0.5`,
+/// or an array of literals for `Vec` fields. Defaults become clap default
+/// values and show up in `--help` and `--print-config`.
+///
+/// Fields can also carry validation rules:
+/// - `range = 1..=100`: numeric values must be in the range
+/// - `regex = "[a-z]+"`: the whole value must match (needs sw-cli's `regex` feature)
+/// - `exists`: paths must exist
+/// - `required`: some layer must set the value
+/// - `requires = "field"`: the other field must be set too
+/// - `conflicts_with = "field"`: the other field must not be set
+/// - `cli_only`: only the command line sets the value; clap then checks
+///   `required`, and `requires` naming another `cli_only` field
+///
+/// Command-line values are checked by clap; values from config files and the
+/// environment by `CliConfig::validate()`, which `try_parse_config()` runs
+/// unless a built-in command such as `--help` handles the run. Numbers that
+/// do not parse are errors wherever they come from.
+///
 /// This generates:
 /// - Config struct with the specified fields
 /// - `build_cli()` function that creates the clap Command
-/// - `parse_config()` function that parses ArgMatches into the config
+/// - `try_parse_config()` function that layers config files, environment
+///   variables and `ArgMatches` into the config
+/// - `parse_config()` function that does the same and exits on errors
+/// - `CliConfig::settings()` listing every field for `--print-config`
+/// - `CliConfig::validate()` checking the validation rules
+///
+/// The config struct implements `Default` with the `default = ...` values.
+/// With sw-cli's `serde` feature it also derives `Serialize` and
+/// `Deserialize`; keys missing from a preset take those defaults.
 #[proc_macro]
+#[allow(clippy::too_many_lines)]
 pub fn cli_app(input: TokenStream) -> TokenStream {
     let cli_input = parse_macro_input!(input as CliAppInput);
+    if let Err(err) = check_rules(&cli_input.fields) {
+        return err.to_compile_error().into();
+    }
 
     let config_name = &cli_input.config_name;
     let app_name = &cli_input.name;
     let app_about = &cli_input.about;
 
+    let env_prefix = cli_input
+        .env_prefix
+        .as_ref()
+        .map(|prefix| quote! { .with_env_prefix(#prefix) });
+    let strict_keys = cli_input
+        .strict_config
+        .then(|| quote! { .with_strict_keys(true) });
+    let env_bindings = cli_input.fields.iter().filter_map(|f| {
+        let name_str = f.name.to_string();
+        let var = f.env.as_ref()?;
+        Some(quote! { .with_env_var(#name_str, #var) })
+    });
+    let cli_only = cli_input.fields.iter().filter(|f| f.cli_only).map(|f| {
+        let name_str = f.name.to_string();
+        quote! { .with_cli_only(#name_str) }
+    });
+
     // Generate config struct fields
     let config_fields = cli_input.fields.iter().map(|f| {
         let name = &f.name;
@@ -506,6 +947,66 @@ pub fn cli_app(input: TokenStream) -> TokenStream {
             arg = quote! { #arg.short(#short) };
         }
 
+        // Values on the command line are checked by clap and kept as text
+        // for the resolver; `validate()` checks the ones from other layers
+        let ty = &f.ty;
+        let ty_string = quote!(#ty).to_string();
+        let numeric = numeric_type(&ty_string).filter(|_| !ty_string.contains("Vec"));
+        if ty_string != "bool" && (numeric.is_some() || f.regex.is_some() || f.exists) {
+            let parse = numeric.map(|inner_ty| {
+                let range = f.range.as_ref().map(|range| {
+                    let text = quote!(#range).to_string().replace(' ', "");
+                    quote! { ::sw_cli::config::validate::in_range(&parsed, &(#range), #text)?; }
+                });
+                quote! {
+                    let parsed: #inner_ty = value.trim().parse().map_err(|e| format!("{e}"))?;
+                    #range
+                }
+            });
+            let regex = f.regex.as_ref().map(|pattern| {
+                quote! { ::sw_cli::config::validate::matches_regex(value, #pattern)?; }
+            });
+            let exists = f.exists.then(|| {
+                quote! { ::sw_cli::config::validate::exists(::std::path::Path::new(value))?; }
+            });
+            arg = quote! {
+                #arg.value_parser(|value: &str| -> Result<String, String> {
+                    #parse
+                    #regex
+                    #exists
+                    Ok(value.to_string())
+                })
+            };
+        }
+
+        for other in &f.conflicts_with {
+            arg = quote! { #arg.conflicts_with(#other) };
+        }
+
+        // Only the command line can set a `cli_only` value, so clap checks
+        // `required` and `requires` between such fields; built-in commands
+        // such as `--help` run without them
+        if f.cli_only && f.required {
+            arg = quote! { #arg.required_unless_present_any(::sw_cli::builder::BUILT_IN_ARGS) };
+        }
+        for other in f
+            .requires
+            .iter()
+            .filter(|other| f.cli_only && cli_only_field(&cli_input.fields, other))
+        {
+            arg = quote! { #arg.requires(#other) };
+        }
+
+        // Checked by check_rules
+        if let Some(default) = &f.default {
+            let texts = default_texts(default).unwrap_or_default();
+            arg = if matches!(default, Expr::Array(_)) {
+                quote! { #arg.default_values([#(#texts),*]) }
+            } else {
+                quote! { #arg.default_value(#(#texts)*) }
+            };
+        }
+
         if let Some(action) = &f.action {
             let action_ident = format_ident!("{}", action);
             arg = quote! { #arg.action(::clap::ArgAction::#action_ident) };
@@ -523,7 +1024,7 @@ pub fn cli_app(input: TokenStream) -> TokenStream {
         arg
     });
 
-    // Generate parser for each field
+    // Generate resolver calls for each field: flag, then environment, then config files
     let field_parsers = cli_input.fields.iter().map(|f| {
         let name = &f.name;
         let name_str = name.to_string();
@@ -531,71 +1032,177 @@ pub fn cli_app(input: TokenStream) -> TokenStream {
         let ty_string = quote!(#ty).to_string();
 
         if ty_string.contains("bool") {
-            quote! { #name: matches.get_flag(#name_str) }
-        } else if ty_string.contains("Vec") && ty_string.contains("PathBuf") {
-            quote! {
-                #name: matches.get_many::<String>(#name_str)
-                    .map(|vals| vals.map(::std::path::PathBuf::from).collect())
-            }
+            return quote! { #name: resolver.flag(#name_str)? };
+        }
+        let value = if ty_string.contains("Vec") && ty_string.contains("PathBuf") {
+            quote! { resolver.paths(#name_str)? }
         } else if ty_string.contains("Vec") {
-            quote! {
-                #name: matches.get_many::<String>(#name_str)
-                    .map(|vals| vals.cloned().collect())
-            }
+            quote! { resolver.strings(#name_str)? }
         } else if ty_string.contains("PathBuf") {
-            quote! { #name: matches.get_one::<String>(#name_str).map(::std::path::PathBuf::from) }
+            quote! { resolver.path(#name_str)? }
         } else if ty_string.contains("String") {
-            quote! { #name: matches.get_one::<String>(#name_str).cloned() }
-        } else if ty_string.contains("usize") {
-            quote! {
-                #name: matches.get_one::<String>(#name_str)
-                    .and_then(|s| s.parse::<usize>().ok())
-            }
-        } else if ty_string.contains("u64") || ty_string.contains("u32") || ty_string.contains("u16") || ty_string.contains("u8") {
-            // For unsigned integer types, parse from string
-            let inner_ty = if ty_string.contains("u64") { quote!(u64) }
-                else if ty_string.contains("u32") { quote!(u32) }
-                else if ty_string.contains("u16") { quote!(u16) }
-                else { quote!(u8) };
-            quote! {
-                #name: matches.get_one::<String>(#name_str)
-                    .and_then(|s| s.parse::<#inner_ty>().ok())
+            quote! { resolver.string(#name_str)? }
+        } else if let Some(inner_ty) = numeric_type(&ty_string) {
+            quote! { resolver.parse::<#inner_ty>(#name_str)? }
+        } else {
+            quote! { resolver.string(#name_str)? }
+        };
+
+        // Plain `T` fields: clap supplies the default, so the fallbacks only
+        // keep the types honest
+        if ty_string.starts_with("Option") {
+            quote! { #name: #value }
+        } else if ty_string.starts_with("Vec") || f.default.is_none() {
+            quote! { #name: #value.unwrap_or_default() }
+        } else {
+            let fallback = default_value(f);
+            quote! { #name: #value.unwrap_or_else(|| #fallback) }
+        }
+    });
+
+    // `Default` gives each field its `default = ...`; serde fills in the
+    // fields a preset leaves out from it
+    let field_defaults = cli_input.fields.iter().map(|f| {
+        let name = &f.name;
+        let value = default_value(f);
+        quote! { #name: #value }
+    });
+
+    // With sw-cli's `serde` feature the config derives Serialize/Deserialize
+    // through sw-cli's re-export, so apps need no serde dependency of their own
+    let serde_derive = if cfg!(feature = "serde") {
+        quote! {
+            #[derive(::sw_cli::serde::Serialize, ::sw_cli::serde::Deserialize)]
+            #[serde(crate = "::sw_cli::serde", default)]
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generate the checks of `validate()`: values first, then how fields
+    // relate to each other
+    let value_checks = cli_input.fields.iter().map(|f| {
+        let name = &f.name;
+        let name_str = name.to_string();
+        let ty = &f.ty;
+        let ty_string = quote!(#ty).to_string();
+        let items = if ty_string.starts_with("Option") && ty_string.contains("Vec") {
+            quote! { self.#name.iter().flatten() }
+        } else if ty_string.starts_with("Option") || ty_string.starts_with("Vec") {
+            quote! { self.#name.iter() }
+        } else {
+            quote! { ::std::iter::once(&self.#name) }
+        };
+        let range = f.range.as_ref().map(|range| {
+            let text = quote!(#range).to_string().replace(' ', "");
+            quote! { ::sw_cli::config::validate::in_range(item, &(#range), #text) }
+        });
+        let regex = f.regex.as_ref().map(|pattern| {
+            quote! { ::sw_cli::config::validate::matches_regex(item, #pattern) }
+        });
+        let exists = f.exists.then(|| {
+            quote! { ::sw_cli::config::validate::exists(::std::path::Path::new(item)) }
+        });
+        let checks: Vec<_> = [range, regex, exists].into_iter().flatten().collect();
+        if checks.is_empty() {
+            return quote! {};
+        }
+        quote! {
+            for item in #items {
+                #(
+                    #checks.map_err(|e| {
+                        format!("{}: invalid value for '{}': {e}", self.value_source(#name_str), #name_str)
+                    })?;
+                )*
             }
-        } else if ty_string.contains("i64") || ty_string.contains("i32") || ty_string.contains("i16") || ty_string.contains("i8") || ty_string.contains("isize") {
-            // For signed integer types, parse from string
-            let inner_ty = if ty_string.contains("i64") { quote!(i64) }
-                else if ty_string.contains("i32") { quote!(i32) }
-                else if ty_string.contains("i16") { quote!(i16) }
-                else if ty_string.contains("isize") { quote!(isize) }
-                else { quote!(i8) };
+        }
+    });
+    let relation_checks = cli_input.fields.iter().map(|f| {
+        let name_str = f.name.to_string();
+        let long = f.long.as_deref().unwrap_or(&name_str);
+        let is_set = field_is_set(f);
+        let required_check = (f.required && !f.cli_only).then(|| {
             quote! {
-                #name: matches.get_one::<String>(#name_str)
-                    .and_then(|s| s.parse::<#inner_ty>().ok())
+                if !(#is_set) {
+                    return Err(::sw_cli::config::validate::missing(#name_str, #long, self.env_var(#name_str)));
+                }
             }
-        } else if ty_string.contains("f64") || ty_string.contains("f32") {
-            // For float types, parse from string
-            let inner_ty = if ty_string.contains("f64") { quote!(f64) } else { quote!(f32) };
-            quote! {
-                #name: matches.get_one::<String>(#name_str)
-                    .and_then(|s| s.parse::<#inner_ty>().ok())
+        });
+        let requires_checks = f.requires.iter().filter_map(|other| {
+            if f.cli_only && cli_only_field(&cli_input.fields, other) {
+                return None;
             }
-        } else {
-            quote! { #name: matches.get_one::<String>(#name_str).cloned() }
+            let other_def = cli_input.fields.iter().find(|g| g.name == other.value())?;
+            let other_set = field_is_set(other_def);
+            Some(quote! {
+                if #is_set && !(#other_set) {
+                    return Err(format!("'{}' ({}) requires '{}'", #name_str, self.value_source(#name_str), #other));
+                }
+            })
+        });
+        let conflict_checks = f.conflicts_with.iter().filter_map(|other| {
+            let other_def = cli_input.fields.iter().find(|g| g.name == other.value())?;
+            let other_set = field_is_set(other_def);
+            Some(quote! {
+                if #is_set && #other_set {
+                    return Err(format!(
+                        "'{}' ({}) cannot be used with '{}' ({})",
+                        #name_str,
+                        self.value_source(#name_str),
+                        #other,
+                        self.value_source(#other),
+                    ));
+                }
+            })
+        });
+        quote! {
+            #required_check
+            #(#requires_checks)*
+            #(#conflict_checks)*
         }
     });
 
+    // Generate the settings shown by --print-config
+    let field_settings = cli_input.fields.iter().map(|f| {
+        let name = &f.name;
+        let name_str = name.to_string();
+        quote! { (#name_str.to_string(), ::sw_cli::output::Value::from(self.#name.clone())) }
+    });
+
     let expanded = quote! {
         #[derive(Debug, Clone)]
+        #serde_derive
         pub struct #config_name {
             pub base: ::sw_cli::BaseConfig,
             #(#config_fields),*
         }
 
+        impl ::std::default::Default for #config_name {
+            fn default() -> Self {
+                Self {
+                    base: ::sw_cli::BaseConfig::default(),
+                    #(#field_defaults),*
+                }
+            }
+        }
+
         impl ::sw_cli::CliConfig for #config_name {
             fn base(&self) -> &::sw_cli::BaseConfig {
                 &self.base
             }
 
+            fn settings(&self) -> ::std::vec::Vec<(::std::string::String, ::sw_cli::output::Value)> {
+                let mut settings = self.base.settings();
+                settings.extend([#(#field_settings),*]);
+                settings
+            }
+
+            fn validate(&self) -> ::std::result::Result<(), ::std::string::String> {
+                #(#value_checks)*
+                #(#relation_checks)*
+                Ok(())
+            }
+
             fn as_any(&self) -> &dyn ::std::any::Any {
                 self
             }
@@ -610,11 +1217,41 @@ pub fn cli_app(input: TokenStream) -> TokenStream {
                 #(.arg(#clap_args))*
         }
 
-        pub fn parse_config(matches: &::clap::ArgMatches) -> #config_name {
-            #config_name {
-                base: ::sw_cli::builder::parse_base_config(matches),
-                #(#field_parsers),*
+        /// Layer config files, environment variables and `matches` into the
+        /// config and check it with `CliConfig::validate` unless a built-in
+        /// command such as `--help` handles the run.
+        ///
+        /// # Errors
+        /// Returns a clap error if a config file cannot be read or, with
+        /// `strict_config`, holds an unknown key, or a value is invalid.
+        pub fn try_parse_config(matches: &::clap::ArgMatches) -> Result<#config_name, ::clap::Error> {
+            let layers = ::sw_cli::config::ConfigLayers::new(#app_name)
+                #env_prefix
+                #strict_keys
+                #(#env_bindings)*
+                #(#cli_only)*
+                .with_env()
+                .with_files_for(matches)
+                .map_err(::sw_cli::builder::config_error)?;
+            let mut resolver = layers.resolver(matches);
+            let resolve = |resolver: &mut ::sw_cli::config::layers::Resolver| -> Result<#config_name, String> {
+                Ok(#config_name {
+                    base: ::sw_cli::builder::resolve_base_config(resolver)?,
+                    #(#field_parsers),*
+                })
+            };
+            let mut config = resolve(&mut resolver).map_err(::sw_cli::builder::config_error)?;
+            config.base.env_vars = resolver.env_vars();
+            config.base.sources = resolver.finish().map_err(::sw_cli::builder::config_error)?;
+            if !config.base.runs_built_in() {
+                ::sw_cli::CliConfig::validate(&config).map_err(::sw_cli::builder::config_error)?;
             }
+            Ok(config)
+        }
+
+        /// Like `try_parse_config`, but prints the error and exits like clap does
+        pub fn parse_config(matches: &::clap::ArgMatches) -> #config_name {
+            try_parse_config(matches).unwrap_or_else(|e| e.exit())
         }
     };
 